@@ -1,61 +1,119 @@
-use crate::{Identifier, IdentifierNode, Node, NodeList, Primitive};
+use crate::{Identifier, IdentifierList, IdentifierNode, Node, NodeList, Primitive};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct InlineAssemblyBlock<'ast> {
     pub items: AssemblyItemList<'ast>,
 }
 
+/// A Yul statement.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AssemblyItem<'ast> {
-    Identifier(Identifier<'ast>),
-    FunctionalAssemblyExpression(FunctionalAssemblyExpression<'ast>),
     InlineAssemblyBlock(InlineAssemblyBlock<'ast>),
     AssemblyLocalBinding(AssemblyLocalBinding<'ast>),
     AssemblyAssignment(AssemblyAssignment<'ast>),
-    AssemblyLabel(AssemblyLabel<'ast>),
-    NumberLiteral(Primitive<'ast>),
-
-    // FIXME
-    StringLiteral,
+    AssemblyIf(AssemblyIf<'ast>),
+    AssemblySwitch(AssemblySwitch<'ast>),
+    AssemblyFor(AssemblyFor<'ast>),
+    AssemblyFunctionDefinition(AssemblyFunctionDefinition<'ast>),
+    FunctionalAssemblyExpression(FunctionalAssemblyExpression<'ast>),
+    Leave,
+    Break,
+    Continue,
+}
 
-    // FIXME,
-    HexLiteral,
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AssemblyExpression<'ast> {
+    /// Plain or dotted, like `x.slot` or `x.offset`
+    Identifier(Identifier<'ast>),
+    Literal(Primitive<'ast>),
+    FunctionalAssemblyExpression(FunctionalAssemblyExpression<'ast>),
 }
 
+/// `let a, b := f()`
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AssemblyLocalBinding<'ast> {
-    pub id: IdentifierNode<'ast>,
-    pub init: FunctionalAssemblyExpressionNode<'ast>,
+    pub ids: IdentifierList<'ast>,
+    pub init: Option<AssemblyExpressionNode<'ast>>,
 }
 
+/// `a, b := f()`
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AssemblyAssignment<'ast> {
-    pub id: IdentifierNode<'ast>,
-    pub init: FunctionalAssemblyExpressionNode<'ast>,
+    pub ids: IdentifierList<'ast>,
+    pub init: AssemblyExpressionNode<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct AssemblyLabel<'ast> {
-    pub id: IdentifierNode<'ast>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AssemblyIf<'ast> {
+    pub test: AssemblyExpressionNode<'ast>,
+    pub body: InlineAssemblyBlockNode<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AssemblySwitch<'ast> {
+    pub test: AssemblyExpressionNode<'ast>,
+    pub cases: AssemblyCaseList<'ast>,
+    pub default: Option<InlineAssemblyBlockNode<'ast>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AssemblyCase<'ast> {
+    pub value: Node<'ast, Primitive<'ast>>,
+    pub body: InlineAssemblyBlockNode<'ast>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AssemblyFor<'ast> {
+    pub init: InlineAssemblyBlockNode<'ast>,
+    pub test: AssemblyExpressionNode<'ast>,
+    pub update: InlineAssemblyBlockNode<'ast>,
+    pub body: InlineAssemblyBlockNode<'ast>,
+}
+
+/// `function name(a, b) -> c { ... }`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AssemblyFunctionDefinition<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub params: IdentifierList<'ast>,
+    pub returns: IdentifierList<'ast>,
+    pub body: InlineAssemblyBlockNode<'ast>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FunctionalAssemblyExpression<'ast> {
     pub id: IdentifierNode<'ast>,
-    pub arguments: AssemblyItemList<'ast>,
+    pub arguments: AssemblyExpressionList<'ast>,
 }
 
 pub type AssemblyItemNode<'ast> = Node<'ast, AssemblyItem<'ast>>;
 pub type AssemblyItemList<'ast> = NodeList<'ast, AssemblyItem<'ast>>;
-pub type FunctionalAssemblyExpressionNode<'ast> = Node<'ast, FunctionalAssemblyExpression<'ast>>;
+pub type AssemblyExpressionNode<'ast> = Node<'ast, AssemblyExpression<'ast>>;
+pub type AssemblyExpressionList<'ast> = NodeList<'ast, AssemblyExpression<'ast>>;
+pub type AssemblyCaseNode<'ast> = Node<'ast, AssemblyCase<'ast>>;
+pub type AssemblyCaseList<'ast> = NodeList<'ast, AssemblyCase<'ast>>;
 pub type InlineAssemblyBlockNode<'ast> = Node<'ast, InlineAssemblyBlock<'ast>>;
 
 impl_from! {
-    Identifier => AssemblyItem::Identifier,
-    FunctionalAssemblyExpression => AssemblyItem::FunctionalAssemblyExpression,
     InlineAssemblyBlock => AssemblyItem::InlineAssemblyBlock,
     AssemblyLocalBinding => AssemblyItem::AssemblyLocalBinding,
     AssemblyAssignment => AssemblyItem::AssemblyAssignment,
-    AssemblyLabel => AssemblyItem::AssemblyLabel,
-    Primitive => AssemblyItem::NumberLiteral,
+    AssemblyIf => AssemblyItem::AssemblyIf,
+    AssemblySwitch => AssemblyItem::AssemblySwitch,
+    AssemblyFor => AssemblyItem::AssemblyFor,
+    AssemblyFunctionDefinition => AssemblyItem::AssemblyFunctionDefinition,
+    FunctionalAssemblyExpression => AssemblyItem::FunctionalAssemblyExpression,
+    Identifier => AssemblyExpression::Identifier,
+    Primitive => AssemblyExpression::Literal,
+    FunctionalAssemblyExpression => AssemblyExpression::FunctionalAssemblyExpression,
 }
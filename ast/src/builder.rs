@@ -0,0 +1,300 @@
+use toolshed::Arena;
+
+use crate::*;
+
+/// Creates nodes on an `Arena` for rewriting the AST.
+///
+/// Nodes are synthesized, see `NodeInner::is_synthesized`, unless they are created
+/// with `node_at`. Lists are never modified in place since they can be shared by
+/// other parts of the tree, the list helpers return new lists instead.
+#[derive(Clone, Copy)]
+pub struct AstBuilder<'ast> {
+    arena: &'ast Arena,
+}
+
+impl<'ast> AstBuilder<'ast> {
+    #[inline]
+    pub fn new(arena: &'ast Arena) -> Self {
+        AstBuilder { arena }
+    }
+
+    #[inline]
+    pub fn arena(&self) -> &'ast Arena {
+        self.arena
+    }
+
+    #[inline]
+    pub fn node<T: Copy>(&self, value: impl Into<T>) -> Node<'ast, T> {
+        Node::new(self.arena.alloc(NodeInner::synthesized(value.into())))
+    }
+
+    /// A node standing in for the source between `start` and `end`.
+    #[inline]
+    pub fn node_at<T: Copy>(&self, start: u32, end: u32, value: impl Into<T>) -> Node<'ast, T> {
+        Node::new(self.arena.alloc(NodeInner::new(start, end, value.into())))
+    }
+
+    /// Copies `text` to the arena.
+    #[inline]
+    pub fn str(&self, text: &str) -> &'ast str {
+        self.arena.alloc_str(text)
+    }
+
+    pub fn ident(&self, name: &str) -> IdentifierNode<'ast> {
+        self.node(self.str(name))
+    }
+
+    /// An identifier used as an expression, like `x` in `x + 1`.
+    pub fn ident_expr(&self, name: &str) -> ExpressionNode<'ast> {
+        self.node(Expression::IdentifierExpression(self.str(name)))
+    }
+
+    pub fn uint_lit(&self, value: u128) -> ExpressionNode<'ast> {
+        let number = self.str(&value.to_string());
+
+        self.node(Primitive::IntegerNumber(number, NumberUnit::None))
+    }
+
+    pub fn bool_lit(&self, value: bool) -> ExpressionNode<'ast> {
+        self.node(Primitive::Bool(value))
+    }
+
+    pub fn binary(
+        &self,
+        left: ExpressionNode<'ast>,
+        operator: BinaryOperator,
+        right: ExpressionNode<'ast>,
+    ) -> ExpressionNode<'ast> {
+        self.node(BinaryExpression {
+            left,
+            operator: self.node(operator),
+            right,
+        })
+    }
+
+    pub fn assign(
+        &self,
+        left: ExpressionNode<'ast>,
+        operator: AssignmentOperator,
+        right: ExpressionNode<'ast>,
+    ) -> ExpressionNode<'ast> {
+        self.node(AssignmentExpression {
+            left,
+            operator: self.node(operator),
+            right,
+        })
+    }
+
+    pub fn prefix(
+        &self,
+        operator: PrefixOperator,
+        operand: ExpressionNode<'ast>,
+    ) -> ExpressionNode<'ast> {
+        self.node(PrefixExpression {
+            operator: self.node(operator),
+            operand,
+        })
+    }
+
+    /// `object.member`
+    pub fn member(&self, object: ExpressionNode<'ast>, member: &str) -> ExpressionNode<'ast> {
+        self.node(MemberAccessExpression {
+            object,
+            member: self.ident(member),
+        })
+    }
+
+    /// A call with positional arguments.
+    pub fn call(
+        &self,
+        callee: ExpressionNode<'ast>,
+        arguments: &[ExpressionNode<'ast>],
+    ) -> ExpressionNode<'ast> {
+        self.node(CallExpression {
+            callee,
+            arguments: self.list(arguments).into(),
+        })
+    }
+
+    pub fn elementary_type(&self, type_name: ElementaryTypeName) -> TypeNameNode<'ast> {
+        self.node(type_name)
+    }
+
+    pub fn user_type(&self, name: &str) -> TypeNameNode<'ast> {
+        self.node(TypeName::UserDefinedTypeName(self.str(name)))
+    }
+
+    /// `type_name location name = init;`
+    pub fn var_decl(
+        &self,
+        type_name: TypeNameNode<'ast>,
+        location: Option<StorageLocation>,
+        name: &str,
+        init: Option<ExpressionNode<'ast>>,
+    ) -> StatementNode<'ast> {
+        let declaration = self.node(VariableDeclaration {
+            type_name,
+            location: location.map(|location| self.node(location)),
+            id: self.ident(name),
+        });
+
+        self.node(VariableDefinitionStatement { declaration, init })
+    }
+
+    pub fn expression_statement(&self, expression: ExpressionNode<'ast>) -> StatementNode<'ast> {
+        self.node(expression)
+    }
+
+    pub fn block(&self, statements: &[StatementNode<'ast>]) -> BlockNode<'ast> {
+        self.node(Block {
+            body: self.list(statements),
+        })
+    }
+
+    /// `unchecked { ... }`
+    pub fn unchecked(&self, block: BlockNode<'ast>) -> StatementNode<'ast> {
+        self.node(UncheckedBlock { block })
+    }
+
+    pub fn list<T: Copy>(&self, nodes: &[Node<'ast, T>]) -> NodeList<'ast, T> {
+        NodeList::from_iter(self.arena, nodes.iter().copied())
+    }
+
+    /// A copy of `list` with `node` inserted at `index`, or appended if `index` is
+    /// past the end.
+    pub fn insert<T: Copy>(
+        &self,
+        list: NodeList<'ast, T>,
+        index: usize,
+        node: Node<'ast, T>,
+    ) -> NodeList<'ast, T> {
+        let mut nodes: Vec<_> = list.iter().copied().collect();
+
+        nodes.insert(index.min(nodes.len()), node);
+
+        self.list(&nodes)
+    }
+
+    /// A copy of `list` without the node at `index`.
+    pub fn remove<T: Copy>(&self, list: NodeList<'ast, T>, index: usize) -> NodeList<'ast, T> {
+        self.retain(list, |position, _| position != index)
+    }
+
+    /// A copy of `list` with the node at `index` replaced by `node`.
+    pub fn replace<T: Copy>(
+        &self,
+        list: NodeList<'ast, T>,
+        index: usize,
+        node: Node<'ast, T>,
+    ) -> NodeList<'ast, T> {
+        let nodes: Vec<_> = list
+            .iter()
+            .enumerate()
+            .map(|(position, old)| if position == index { node } else { *old })
+            .collect();
+
+        self.list(&nodes)
+    }
+
+    /// A copy of `list` with only the nodes `keep` returns `true` for, given their
+    /// index and the node.
+    pub fn retain<T: Copy>(
+        &self,
+        list: NodeList<'ast, T>,
+        mut keep: impl FnMut(usize, Node<'ast, T>) -> bool,
+    ) -> NodeList<'ast, T> {
+        let nodes: Vec<_> = list
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(index, node)| keep(index, node))
+            .map(|(_, node)| node)
+            .collect();
+
+        self.list(&nodes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn builds_synthesized_nodes() {
+        let arena = Arena::new();
+        let builder = AstBuilder::new(&arena);
+
+        // uint256 total = a + 1;
+        let init = builder.binary(
+            builder.ident_expr("a"),
+            BinaryOperator::Addition,
+            builder.uint_lit(1),
+        );
+        let statement = builder.var_decl(
+            builder.elementary_type(ElementaryTypeName::Uint(32)),
+            None,
+            "total",
+            Some(init),
+        );
+
+        assert!(statement.is_synthesized());
+
+        let definition = match statement.value {
+            Statement::VariableDefinitionStatement(definition) => definition,
+            _ => panic!("Expected a variable definition"),
+        };
+
+        assert_eq!(definition.declaration.value.id.value, "total");
+        assert_eq!(
+            definition.declaration.value.type_name.value,
+            TypeName::ElementaryTypeName(ElementaryTypeName::Uint(32))
+        );
+
+        match definition.init.unwrap().value {
+            Expression::BinaryExpression(binary) => {
+                assert_eq!(binary.left.value, Expression::IdentifierExpression("a"));
+                assert_eq!(binary.operator.value, BinaryOperator::Addition);
+                assert_eq!(
+                    binary.right.value,
+                    Expression::PrimitiveExpression(Primitive::IntegerNumber(
+                        "1",
+                        NumberUnit::None
+                    ))
+                );
+            }
+            _ => panic!("Expected a binary expression"),
+        }
+
+        let parsed: IdentifierNode = builder.node_at(4, 9, "total");
+
+        assert!(!parsed.is_synthesized());
+    }
+
+    #[test]
+    fn list_helpers_leave_the_original_list() {
+        let arena = Arena::new();
+        let builder = AstBuilder::new(&arena);
+
+        fn names<'ast>(list: IdentifierList<'ast>) -> Vec<&'ast str> {
+            list.iter().map(|id| id.value).collect()
+        }
+
+        let list = builder.list(&[builder.ident("a"), builder.ident("b")]);
+
+        let inserted = builder.insert(list, 1, builder.ident("c"));
+        let appended = builder.insert(list, 9, builder.ident("d"));
+        let removed = builder.remove(inserted, 0);
+        let replaced = builder.replace(list, 1, builder.ident("e"));
+        let retained = builder.retain(inserted, |_, id| id.value != "c");
+
+        assert_eq!(names(list), ["a", "b"]);
+        assert_eq!(names(inserted), ["a", "c", "b"]);
+        assert_eq!(names(appended), ["a", "b", "d"]);
+        assert_eq!(names(removed), ["c", "b"]);
+        assert_eq!(names(replaced), ["a", "e"]);
+        assert_eq!(names(retained), ["a", "b"]);
+        assert!(builder
+            .remove(builder.list(&[builder.ident("a")]), 0)
+            .is_empty());
+    }
+}
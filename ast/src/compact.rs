@@ -0,0 +1,1401 @@
+use serde_json::{json, Map, Value};
+
+use crate::*;
+
+/// Converts the AST to JSON resembling the `compactAST` output of solc.
+///
+/// Nodes carry a `nodeType` named after the solc node they stand for, a `src` of
+/// `start:length:file` and an `id`. Like in solc, ids are handed out as nodes are
+/// completed, so children are numbered before their parents and the `SourceUnit`
+/// gets the highest id. Nodes added by rewriting have a `src` of `-1:-1:-1`.
+///
+/// Only the syntax is covered, solc's annotations like `typeDescriptions` or
+/// `referencedDeclaration` need semantic analysis and are left out.
+pub fn compact_ast(body: SourceUnitList, file: usize) -> Value {
+    let mut compact = Compact { file, next_id: 0 };
+
+    let span = match (body.iter().next(), body.iter().last()) {
+        (Some(first), Some(last)) if !first.is_synthesized() => Some((0, last.end)),
+        _ => None,
+    };
+    let nodes: Vec<_> = body.iter().map(|unit| compact.source_unit(*unit)).collect();
+
+    compact.node("SourceUnit", span, json!({ "nodes": nodes }))
+}
+
+/// Start and end of a node, `None` for synthesized nodes.
+type Span = Option<(u32, u32)>;
+
+fn span<T>(node: &NodeInner<T>) -> Span {
+    match node.is_synthesized() {
+        true => None,
+        false => Some((node.start, node.end)),
+    }
+}
+
+struct Compact {
+    file: usize,
+    next_id: u32,
+}
+
+impl Compact {
+    fn src(&self, span: Span) -> String {
+        match span {
+            Some((start, end)) => format!("{}:{}:{}", start, end - start, self.file),
+            None => "-1:-1:-1".to_string(),
+        }
+    }
+
+    fn node(&mut self, node_type: &str, span: Span, fields: Value) -> Value {
+        let id = self.next_id;
+
+        self.next_id += 1;
+
+        let mut node = self.yul(node_type, span, fields);
+
+        node["id"] = id.into();
+        node
+    }
+
+    /// Yul nodes have no ids in solc.
+    fn yul(&self, node_type: &str, span: Span, fields: Value) -> Value {
+        let mut node = Map::new();
+
+        node.insert("nodeType".to_string(), node_type.into());
+        node.insert("src".to_string(), self.src(span).into());
+
+        if let Value::Object(fields) = fields {
+            node.extend(fields);
+        }
+
+        Value::Object(node)
+    }
+
+    fn identifier_path(&mut self, id: IdentifierNode) -> Value {
+        self.node("IdentifierPath", span(&id), json!({ "name": id.value }))
+    }
+
+    fn unknown(&mut self, span: Span, unknown: Unknown) -> Value {
+        self.node("Unknown", span, json!({ "raw": unknown.raw }))
+    }
+
+    fn source_unit(&mut self, unit: SourceUnitNode) -> Value {
+        let span = span(&unit);
+
+        match unit.value {
+            SourceUnit::PragmaDirective(pragma) => self.node(
+                "PragmaDirective",
+                span,
+                json!({ "literals": literals(pragma) }),
+            ),
+            SourceUnit::ImportDirective(import) => self.import_directive(span, import),
+            SourceUnit::ContractDefinition(contract) => self.contract_definition(span, contract),
+            SourceUnit::StructDefinition(definition) => self.struct_definition(span, definition),
+            SourceUnit::EnumDefinition(definition) => self.enum_definition(span, definition),
+            SourceUnit::FunctionDefinition(function) => self.function_definition(span, function),
+            SourceUnit::ErrorDefinition(definition) => self.error_definition(span, definition),
+            SourceUnit::ConstantDeclaration(constant) => self.variable(
+                span,
+                Some(constant.name),
+                Some(constant.type_name),
+                Some(constant.init),
+                json!({ "constant": true, "mutability": "constant" }),
+            ),
+            SourceUnit::UserDefinedValueTypeDefinition(definition) => {
+                self.user_defined_value_type(span, definition)
+            }
+            SourceUnit::UsingForDeclaration(using) => self.using_for(span, using),
+            SourceUnit::Unknown(unknown) => self.unknown(span, unknown),
+        }
+    }
+
+    fn import_directive(&mut self, span: Span, import: ImportDirective) -> Value {
+        let (source, unit_alias, symbols) = match import {
+            ImportDirective::Global { source, alias } => (source, alias, Vec::new()),
+            ImportDirective::From {
+                symbol: None,
+                alias,
+                source,
+            } => (source, alias, Vec::new()),
+            ImportDirective::From {
+                symbol: Some(symbol),
+                alias,
+                source,
+            } => (source, None, vec![(symbol, alias)]),
+            ImportDirective::ManyFrom { imports, source } => (
+                source,
+                None,
+                imports
+                    .iter()
+                    .map(|import| (import.value.symbol, import.value.alias))
+                    .collect(),
+            ),
+        };
+
+        let symbol_aliases: Vec<_> = symbols
+            .into_iter()
+            .map(|(symbol, alias)| {
+                let foreign = self.node(
+                    "Identifier",
+                    self::span(&symbol),
+                    json!({ "name": symbol.value }),
+                );
+
+                json!({ "foreign": foreign, "local": alias.map(|alias| alias.value) })
+            })
+            .collect();
+
+        self.node(
+            "ImportDirective",
+            span,
+            json!({
+                "file": unquote(source.value),
+                "unitAlias": unit_alias.map_or("", |alias| alias.value),
+                "symbolAliases": symbol_aliases,
+            }),
+        )
+    }
+
+    fn contract_definition(&mut self, span: Span, contract: ContractDefinition) -> Value {
+        let base_contracts: Vec<_> = contract
+            .inherits
+            .iter()
+            .map(|base| {
+                let base_name = self.identifier_path(*base);
+
+                self.node(
+                    "InheritanceSpecifier",
+                    self::span(base),
+                    json!({ "baseName": base_name }),
+                )
+            })
+            .collect();
+        let nodes: Vec<_> = contract
+            .body
+            .iter()
+            .map(|part| self.contract_part(*part))
+            .collect();
+
+        self.node(
+            "ContractDefinition",
+            span,
+            json!({
+                "name": contract.name.value,
+                "contractKind": "contract",
+                "baseContracts": base_contracts,
+                "nodes": nodes,
+            }),
+        )
+    }
+
+    fn contract_part(&mut self, part: ContractPartNode) -> Value {
+        let span = span(&part);
+
+        match part.value {
+            ContractPart::StateVariableDeclaration(declaration) => {
+                let constant = declaration.constant.is_some();
+
+                self.variable(
+                    span,
+                    Some(declaration.name),
+                    Some(declaration.type_name),
+                    declaration.init,
+                    json!({
+                        "constant": constant,
+                        "mutability": if constant { "constant" } else { "mutable" },
+                        "stateVariable": true,
+                        "visibility": match declaration.visibility.map(|visibility| visibility.value) {
+                            Some(StateVariableVisibility::Public) => "public",
+                            Some(StateVariableVisibility::Private) => "private",
+                            Some(StateVariableVisibility::Internal) | None => "internal",
+                        },
+                    }),
+                )
+            }
+            ContractPart::UsingForDeclaration(using) => self.using_for(span, using),
+            ContractPart::StructDefinition(definition) => self.struct_definition(span, definition),
+            ContractPart::ModifierDefinition(definition) => {
+                let parameters = self.parameter_list(span, definition.params);
+                let body = self.block(definition.block);
+
+                self.node(
+                    "ModifierDefinition",
+                    span,
+                    json!({
+                        "name": definition.name.value,
+                        "parameters": parameters,
+                        "body": body,
+                    }),
+                )
+            }
+            ContractPart::FunctionDefinition(function) => self.function_definition(span, function),
+            ContractPart::EventDefinition(definition) => {
+                let parameters: Vec<_> = definition
+                    .params
+                    .iter()
+                    .map(|param| {
+                        self.variable(
+                            self::span(param),
+                            param.value.name,
+                            Some(param.value.type_name),
+                            None,
+                            json!({ "indexed": param.value.indexed.is_some() }),
+                        )
+                    })
+                    .collect();
+                let parameters =
+                    self.node("ParameterList", span, json!({ "parameters": parameters }));
+
+                self.node(
+                    "EventDefinition",
+                    span,
+                    json!({
+                        "name": definition.name.value,
+                        "anonymous": definition.anonymous.is_some(),
+                        "parameters": parameters,
+                    }),
+                )
+            }
+            ContractPart::EnumDefinition(definition) => self.enum_definition(span, definition),
+            ContractPart::ErrorDefinition(definition) => self.error_definition(span, definition),
+            ContractPart::UserDefinedValueTypeDefinition(definition) => {
+                self.user_defined_value_type(span, definition)
+            }
+            ContractPart::Unknown(unknown) => self.unknown(span, unknown),
+        }
+    }
+
+    fn struct_definition(&mut self, span: Span, definition: StructDefinition) -> Value {
+        let members: Vec<_> = definition
+            .body
+            .iter()
+            .map(|member| self.variable_declaration(*member, json!({})))
+            .collect();
+
+        self.node(
+            "StructDefinition",
+            span,
+            json!({ "name": definition.name.value, "members": members }),
+        )
+    }
+
+    fn enum_definition(&mut self, span: Span, definition: EnumDefinition) -> Value {
+        let members: Vec<_> = definition
+            .variants
+            .iter()
+            .map(|variant| {
+                self.node(
+                    "EnumValue",
+                    self::span(variant),
+                    json!({ "name": variant.value }),
+                )
+            })
+            .collect();
+
+        self.node(
+            "EnumDefinition",
+            span,
+            json!({ "name": definition.name.value, "members": members }),
+        )
+    }
+
+    fn error_definition(&mut self, span: Span, definition: ErrorDefinition) -> Value {
+        let parameters = self.parameter_list(span, definition.params);
+
+        self.node(
+            "ErrorDefinition",
+            span,
+            json!({ "name": definition.name.value, "parameters": parameters }),
+        )
+    }
+
+    fn user_defined_value_type(
+        &mut self,
+        span: Span,
+        definition: UserDefinedValueTypeDefinition,
+    ) -> Value {
+        let underlying = self.elementary_type_name(
+            self::span(&definition.underlying),
+            definition.underlying.value,
+        );
+
+        self.node(
+            "UserDefinedValueTypeDefinition",
+            span,
+            json!({ "name": definition.name.value, "underlyingType": underlying }),
+        )
+    }
+
+    fn using_for(&mut self, span: Span, using: UsingForDeclaration) -> Value {
+        let library_name = using.id.map(|id| self.identifier_path(id));
+        let function_list: Vec<_> = using
+            .functions
+            .iter()
+            .map(|function| {
+                let path = self.identifier_path(function.value.id);
+
+                match function.value.operator {
+                    Some(operator) => json!({
+                        "definition": path,
+                        "operator": user_definable_operator(operator.value),
+                    }),
+                    None => json!({ "function": path }),
+                }
+            })
+            .collect();
+        let type_name = using.type_name.map(|type_name| self.type_name(type_name));
+
+        let mut fields = json!({ "typeName": type_name, "global": using.global.is_some() });
+
+        match library_name {
+            Some(library_name) => fields["libraryName"] = library_name,
+            None => fields["functionList"] = function_list.into(),
+        }
+
+        self.node("UsingForDirective", span, fields)
+    }
+
+    fn function_definition(&mut self, span: Span, function: FunctionDefinition) -> Value {
+        let parameters = self.parameter_list(span, function.params);
+        let return_parameters = self.parameter_list(span, function.returns);
+        let modifiers: Vec<_> = function
+            .modifiers
+            .iter()
+            .map(|modifier| {
+                let modifier_name = self.identifier_path(modifier.value.id);
+                let arguments = self.expressions(modifier.value.arguments);
+
+                self.node(
+                    "ModifierInvocation",
+                    self::span(modifier),
+                    json!({ "modifierName": modifier_name, "arguments": arguments }),
+                )
+            })
+            .collect();
+        let body = function.block.map(|block| self.block(block));
+
+        self.node(
+            "FunctionDefinition",
+            span,
+            json!({
+                "name": function.name.map_or("", |name| name.value),
+                "kind": if function.name.is_some() { "function" } else { "fallback" },
+                "visibility": match function.visibility.map(|visibility| visibility.value) {
+                    Some(FunctionVisibility::External) => "external",
+                    Some(FunctionVisibility::Public) | None => "public",
+                    Some(FunctionVisibility::Internal) => "internal",
+                    Some(FunctionVisibility::Private) => "private",
+                },
+                "stateMutability": match function.mutability.map(|mutability| mutability.value) {
+                    Some(StateMutability::Pure) => "pure",
+                    Some(StateMutability::Constant) | Some(StateMutability::View) => "view",
+                    Some(StateMutability::Payable) => "payable",
+                    None => "nonpayable",
+                },
+                "modifiers": modifiers,
+                "parameters": parameters,
+                "returnParameters": return_parameters,
+                "implemented": body.is_some(),
+                "body": body,
+            }),
+        )
+    }
+
+    /// Parameter lists have no span of their own, they take the span of `parent`.
+    fn parameter_list(&mut self, parent: Span, params: ParameterList) -> Value {
+        let parameters: Vec<_> = params
+            .iter()
+            .map(|param| {
+                self.variable(
+                    span(param),
+                    param.value.name,
+                    Some(param.value.type_name),
+                    None,
+                    json!({ "storageLocation": storage_location(param.value.location) }),
+                )
+            })
+            .collect();
+
+        self.node("ParameterList", parent, json!({ "parameters": parameters }))
+    }
+
+    fn variable_declaration(
+        &mut self,
+        declaration: VariableDeclarationNode,
+        fields: Value,
+    ) -> Value {
+        let mut fields = fields;
+
+        fields["storageLocation"] = storage_location(declaration.value.location).into();
+
+        self.variable(
+            span(&declaration),
+            Some(declaration.value.id),
+            Some(declaration.value.type_name),
+            None,
+            fields,
+        )
+    }
+
+    /// A `VariableDeclaration` with the defaults of a local variable, overridden by
+    /// `fields`.
+    fn variable(
+        &mut self,
+        span: Span,
+        name: Option<IdentifierNode>,
+        type_name: Option<TypeNameNode>,
+        value: Option<ExpressionNode>,
+        fields: Value,
+    ) -> Value {
+        let type_name = type_name.map(|type_name| self.type_name(type_name));
+        let value = value.map(|value| self.expression(value));
+        let mut variable = json!({
+            "name": name.map_or("", |name| name.value),
+            "typeName": type_name,
+            "constant": false,
+            "mutability": "mutable",
+            "stateVariable": false,
+            "storageLocation": "default",
+            "visibility": "internal",
+            "value": value,
+        });
+
+        if let Value::Object(fields) = fields {
+            for (key, value) in fields {
+                variable[key] = value;
+            }
+        }
+
+        self.node("VariableDeclaration", span, variable)
+    }
+
+    fn type_name(&mut self, type_name: TypeNameNode) -> Value {
+        let span = span(&type_name);
+
+        match type_name.value {
+            TypeName::ElementaryTypeName(elementary) => self.elementary_type_name(span, elementary),
+            TypeName::UserDefinedTypeName(name) => {
+                let path = self.node("IdentifierPath", span, json!({ "name": name }));
+
+                self.node("UserDefinedTypeName", span, json!({ "pathNode": path }))
+            }
+            TypeName::Mapping(mapping) => {
+                let key_type =
+                    self.elementary_type_name(self::span(&mapping.from), mapping.from.value);
+                let value_type = self.type_name(mapping.to);
+
+                self.node(
+                    "Mapping",
+                    span,
+                    json!({ "keyType": key_type, "valueType": value_type }),
+                )
+            }
+            TypeName::ArrayTypeName(array) => {
+                let base_type = self.type_name(array.base);
+                let length = array.length.map(|length| self.expression(length));
+
+                self.node(
+                    "ArrayTypeName",
+                    span,
+                    json!({ "baseType": base_type, "length": length }),
+                )
+            }
+            TypeName::FunctionTypeName => self.node("FunctionTypeName", span, json!({})),
+        }
+    }
+
+    fn elementary_type_name(&mut self, span: Span, elementary: ElementaryTypeName) -> Value {
+        let mut fields = json!({ "name": elementary_name(elementary) });
+
+        if elementary == ElementaryTypeName::AddressPayable {
+            fields["stateMutability"] = "payable".into();
+        }
+
+        self.node("ElementaryTypeName", span, fields)
+    }
+
+    fn block(&mut self, block: BlockNode) -> Value {
+        let statements = self.statements(block.value.body);
+
+        self.node("Block", span(&block), json!({ "statements": statements }))
+    }
+
+    fn statements(&mut self, list: StatementList) -> Vec<Value> {
+        list.iter()
+            .map(|statement| self.statement(*statement))
+            .collect()
+    }
+
+    fn statement(&mut self, statement: StatementNode) -> Value {
+        let span = span(&statement);
+
+        match statement.value {
+            Statement::Placeholder => self.node("PlaceholderStatement", span, json!({})),
+            Statement::IfStatement(statement) => {
+                let condition = self.expression(statement.test);
+                let true_body = self.statement(statement.consequent);
+                let false_body = statement
+                    .alternate
+                    .map(|alternate| self.statement(alternate));
+
+                self.node(
+                    "IfStatement",
+                    span,
+                    json!({
+                        "condition": condition,
+                        "trueBody": true_body,
+                        "falseBody": false_body,
+                    }),
+                )
+            }
+            Statement::WhileStatement(statement) => {
+                let condition = self.expression(statement.test);
+                let body = self.statement(statement.body);
+
+                self.node(
+                    "WhileStatement",
+                    span,
+                    json!({ "condition": condition, "body": body }),
+                )
+            }
+            Statement::ForStatement(statement) => {
+                let init = statement.init.map(|init| self.simple_statement(init));
+                let condition = statement.test.map(|test| self.expression(test));
+                let loop_expression = statement.update.map(|update| {
+                    let expression = self.expression(update);
+
+                    self.node(
+                        "ExpressionStatement",
+                        self::span(&update),
+                        json!({ "expression": expression }),
+                    )
+                });
+                let body = self.statement(statement.body);
+
+                self.node(
+                    "ForStatement",
+                    span,
+                    json!({
+                        "initializationExpression": init,
+                        "condition": condition,
+                        "loopExpression": loop_expression,
+                        "body": body,
+                    }),
+                )
+            }
+            Statement::BlockStatement(block) => {
+                let statements = self.statements(block.body);
+
+                self.node("Block", span, json!({ "statements": statements }))
+            }
+            Statement::InlineAssemblyStatement(assembly) => {
+                let ast = self.assembly_block(assembly.block);
+                let flags: Vec<_> = assembly
+                    .flags
+                    .iter()
+                    .map(|flag| unquote(flag.value))
+                    .collect();
+
+                self.node(
+                    "InlineAssembly",
+                    span,
+                    json!({ "AST": ast, "flags": flags }),
+                )
+            }
+            Statement::DoWhileStatement(statement) => {
+                let body = self.statement(statement.body);
+                let condition = self.expression(statement.test);
+
+                self.node(
+                    "DoWhileStatement",
+                    span,
+                    json!({ "condition": condition, "body": body }),
+                )
+            }
+            Statement::ContinueStatement => self.node("Continue", span, json!({})),
+            Statement::BreakStatement => self.node("Break", span, json!({})),
+            Statement::ReturnStatement(statement) => {
+                let expression = statement.value.map(|value| self.expression(value));
+
+                self.node("Return", span, json!({ "expression": expression }))
+            }
+            Statement::ThrowStatement => self.node("Throw", span, json!({})),
+            Statement::EmitStatement(statement) => {
+                let event_call = self.expression(statement.event);
+
+                self.node("EmitStatement", span, json!({ "eventCall": event_call }))
+            }
+            Statement::RevertStatement(statement) => {
+                let error_call = self.expression(statement.error);
+
+                self.node("RevertStatement", span, json!({ "errorCall": error_call }))
+            }
+            Statement::UncheckedBlock(unchecked) => {
+                let statements = self.statements(unchecked.block.value.body);
+
+                self.node("UncheckedBlock", span, json!({ "statements": statements }))
+            }
+            Statement::TryStatement(statement) => {
+                let external_call = self.expression(statement.call);
+                let parameters = match statement.returns.is_empty() {
+                    true => Value::Null,
+                    false => self.parameter_list(span, statement.returns),
+                };
+                let block = self.block(statement.body);
+                let mut clauses = vec![self.node(
+                    "TryCatchClause",
+                    self::span(&statement.body),
+                    json!({ "errorName": "", "parameters": parameters, "block": block }),
+                )];
+
+                for clause in statement.clauses.iter() {
+                    let clause_span = self::span(clause);
+                    let parameters = match clause.value.params.is_empty() {
+                        true => Value::Null,
+                        false => self.parameter_list(clause_span, clause.value.params),
+                    };
+                    let block = self.block(clause.value.body);
+
+                    clauses.push(self.node(
+                        "TryCatchClause",
+                        clause_span,
+                        json!({
+                            "errorName": clause.value.id.map_or("", |id| id.value),
+                            "parameters": parameters,
+                            "block": block,
+                        }),
+                    ));
+                }
+
+                self.node(
+                    "TryStatement",
+                    span,
+                    json!({ "externalCall": external_call, "clauses": clauses }),
+                )
+            }
+            Statement::VariableDefinitionStatement(definition) => {
+                self.variable_definition(span, definition)
+            }
+            Statement::InferredDefinitionStatement(definition) => {
+                self.inferred_definition(span, definition)
+            }
+            Statement::ExpressionStatement(expression) => {
+                let expression = self.expression(expression);
+
+                self.node(
+                    "ExpressionStatement",
+                    span,
+                    json!({ "expression": expression }),
+                )
+            }
+            Statement::Unknown(unknown) => self.unknown(span, unknown),
+        }
+    }
+
+    fn simple_statement(&mut self, statement: SimpleStatementNode) -> Value {
+        let span = span(&statement);
+
+        match statement.value {
+            SimpleStatement::VariableDefinitionStatement(definition) => {
+                self.variable_definition(span, definition)
+            }
+            SimpleStatement::InferredDefinitionStatement(definition) => {
+                self.inferred_definition(span, definition)
+            }
+            SimpleStatement::ExpressionStatement(expression) => {
+                let expression = self.expression(expression);
+
+                self.node(
+                    "ExpressionStatement",
+                    span,
+                    json!({ "expression": expression }),
+                )
+            }
+        }
+    }
+
+    fn variable_definition(
+        &mut self,
+        span: Span,
+        definition: VariableDefinitionStatement,
+    ) -> Value {
+        let declaration = self.variable_declaration(definition.declaration, json!({}));
+        let initial_value = definition.init.map(|init| self.expression(init));
+
+        self.node(
+            "VariableDeclarationStatement",
+            span,
+            json!({
+                "assignments": [declaration["id"]],
+                "declarations": [declaration],
+                "initialValue": initial_value,
+            }),
+        )
+    }
+
+    fn inferred_definition(
+        &mut self,
+        span: Span,
+        definition: InferredDefinitionStatement,
+    ) -> Value {
+        let declarations: Vec<_> = definition
+            .ids
+            .iter()
+            .map(|id| match id {
+                Some(id) => self.variable(self::span(id), Some(*id), None, None, json!({})),
+                None => Value::Null,
+            })
+            .collect();
+        let assignments: Vec<_> = declarations
+            .iter()
+            .map(|declaration| declaration["id"].clone())
+            .collect();
+        let initial_value = self.expression(definition.init);
+
+        self.node(
+            "VariableDeclarationStatement",
+            span,
+            json!({
+                "assignments": assignments,
+                "declarations": declarations,
+                "initialValue": initial_value,
+            }),
+        )
+    }
+
+    fn expressions(&mut self, list: ExpressionList) -> Vec<Value> {
+        list.iter()
+            .map(|expression| self.expression(*expression))
+            .collect()
+    }
+
+    fn expression(&mut self, expression: ExpressionNode) -> Value {
+        let span = span(&expression);
+
+        match expression.value {
+            Expression::ThisExpression => self.node("Identifier", span, json!({ "name": "this" })),
+            Expression::IdentifierExpression(name) => {
+                self.node("Identifier", span, json!({ "name": name }))
+            }
+            Expression::PrimitiveExpression(primitive) => {
+                self.node("Literal", span, literal(primitive))
+            }
+            Expression::PrefixExpression(prefix) => {
+                let sub_expression = self.expression(prefix.operand);
+
+                self.node(
+                    "UnaryOperation",
+                    span,
+                    json!({
+                        "operator": prefix_operator(prefix.operator.value),
+                        "prefix": true,
+                        "subExpression": sub_expression,
+                    }),
+                )
+            }
+            Expression::PostfixExpression(postfix) => {
+                let sub_expression = self.expression(postfix.operand);
+                let operator = match postfix.operator.value {
+                    PostfixOperator::Increment => "++",
+                    PostfixOperator::Decrement => "--",
+                };
+
+                self.node(
+                    "UnaryOperation",
+                    span,
+                    json!({
+                        "operator": operator,
+                        "prefix": false,
+                        "subExpression": sub_expression,
+                    }),
+                )
+            }
+            Expression::BinaryExpression(binary) => {
+                let left = self.expression(binary.left);
+                let right = self.expression(binary.right);
+
+                self.node(
+                    "BinaryOperation",
+                    span,
+                    json!({
+                        "operator": binary_operator(binary.operator.value),
+                        "leftExpression": left,
+                        "rightExpression": right,
+                    }),
+                )
+            }
+            Expression::AssignmentExpression(assignment) => {
+                let left = self.expression(assignment.left);
+                let right = self.expression(assignment.right);
+
+                self.node(
+                    "Assignment",
+                    span,
+                    json!({
+                        "operator": assignment_operator(assignment.operator.value),
+                        "leftHandSide": left,
+                        "rightHandSide": right,
+                    }),
+                )
+            }
+            Expression::TupleExpression(tuple) => {
+                let components = self.expressions(tuple.expressions);
+
+                self.node(
+                    "TupleExpression",
+                    span,
+                    json!({ "components": components, "isInlineArray": false }),
+                )
+            }
+            Expression::CallExpression(call) => {
+                let callee = self.expression(call.callee);
+                let (names, arguments) = match call.arguments {
+                    CallArguments::Positional(list) => (Vec::new(), self.expressions(list)),
+                    CallArguments::Named(list) => self.named_arguments(list),
+                };
+
+                self.node(
+                    "FunctionCall",
+                    span,
+                    json!({ "expression": callee, "names": names, "arguments": arguments }),
+                )
+            }
+            Expression::CallOptionsExpression(call) => {
+                let callee = self.expression(call.callee);
+                let (names, options) = self.named_arguments(call.options);
+
+                self.node(
+                    "FunctionCallOptions",
+                    span,
+                    json!({ "expression": callee, "names": names, "options": options }),
+                )
+            }
+            Expression::MemberAccessExpression(access) => {
+                let object = self.expression(access.object);
+
+                self.node(
+                    "MemberAccess",
+                    span,
+                    json!({ "expression": object, "memberName": access.member.value }),
+                )
+            }
+            Expression::IndexAccessExpression(access) => {
+                let base = self.expression(access.array);
+                let index = access.index.map(|index| self.expression(index));
+
+                self.node(
+                    "IndexAccess",
+                    span,
+                    json!({ "baseExpression": base, "indexExpression": index }),
+                )
+            }
+            Expression::ConditionalExpression(conditional) => {
+                let condition = self.expression(conditional.test);
+                let true_expression = self.expression(conditional.consequent);
+                let false_expression = self.expression(conditional.alternate);
+
+                self.node(
+                    "Conditional",
+                    span,
+                    json!({
+                        "condition": condition,
+                        "trueExpression": true_expression,
+                        "falseExpression": false_expression,
+                    }),
+                )
+            }
+            Expression::ElementaryTypeExpression(elementary) => {
+                let type_name = self.elementary_type_name(span, elementary);
+
+                self.node(
+                    "ElementaryTypeNameExpression",
+                    span,
+                    json!({ "typeName": type_name }),
+                )
+            }
+            Expression::NewExpression(new) => {
+                let type_name = self.type_name(new.type_name);
+
+                self.node("NewExpression", span, json!({ "typeName": type_name }))
+            }
+            Expression::TypeExpression(expression) => {
+                // solc parses `type(T)` as a call of the `type` builtin
+                let callee = self.node("Identifier", span, json!({ "name": "type" }));
+                let type_name = self.type_name(expression.type_name);
+
+                self.node(
+                    "FunctionCall",
+                    span,
+                    json!({ "expression": callee, "names": [], "arguments": [type_name] }),
+                )
+            }
+        }
+    }
+
+    fn named_arguments(&mut self, list: NamedArgumentList) -> (Vec<Value>, Vec<Value>) {
+        list.iter()
+            .map(|argument| {
+                (
+                    Value::from(argument.value.name.value),
+                    self.expression(argument.value.value),
+                )
+            })
+            .unzip()
+    }
+
+    fn assembly_block(&mut self, block: InlineAssemblyBlockNode) -> Value {
+        let statements = self.assembly_items(block.value.items);
+
+        self.yul(
+            "YulBlock",
+            span(&block),
+            json!({ "statements": statements }),
+        )
+    }
+
+    fn assembly_items(&mut self, items: AssemblyItemList) -> Vec<Value> {
+        items.iter().map(|item| self.assembly_item(*item)).collect()
+    }
+
+    fn assembly_item(&mut self, item: AssemblyItemNode) -> Value {
+        let span = span(&item);
+
+        match item.value {
+            AssemblyItem::InlineAssemblyBlock(block) => {
+                let statements = self.assembly_items(block.items);
+
+                self.yul("YulBlock", span, json!({ "statements": statements }))
+            }
+            AssemblyItem::AssemblyLocalBinding(binding) => {
+                let variables: Vec<_> = binding
+                    .ids
+                    .iter()
+                    .map(|id| self.yul("YulTypedName", self::span(id), json!({ "name": id.value })))
+                    .collect();
+                let value = binding.init.map(|init| self.assembly_expression(init));
+
+                self.yul(
+                    "YulVariableDeclaration",
+                    span,
+                    json!({ "variables": variables, "value": value }),
+                )
+            }
+            AssemblyItem::AssemblyAssignment(assignment) => {
+                let variable_names: Vec<_> = assignment
+                    .ids
+                    .iter()
+                    .map(|id| {
+                        self.yul("YulIdentifier", self::span(id), json!({ "name": id.value }))
+                    })
+                    .collect();
+                let value = self.assembly_expression(assignment.init);
+
+                self.yul(
+                    "YulAssignment",
+                    span,
+                    json!({ "variableNames": variable_names, "value": value }),
+                )
+            }
+            AssemblyItem::AssemblyIf(statement) => {
+                let condition = self.assembly_expression(statement.test);
+                let body = self.assembly_block(statement.body);
+
+                self.yul(
+                    "YulIf",
+                    span,
+                    json!({ "condition": condition, "body": body }),
+                )
+            }
+            AssemblyItem::AssemblySwitch(switch) => {
+                let expression = self.assembly_expression(switch.test);
+                let mut cases: Vec<_> = switch
+                    .cases
+                    .iter()
+                    .map(|case| {
+                        let value = self.yul(
+                            "YulLiteral",
+                            self::span(&case.value.value),
+                            literal(case.value.value.value),
+                        );
+                        let body = self.assembly_block(case.value.body);
+
+                        self.yul(
+                            "YulCase",
+                            self::span(case),
+                            json!({ "value": value, "body": body }),
+                        )
+                    })
+                    .collect();
+
+                if let Some(default) = switch.default {
+                    let body = self.assembly_block(default);
+
+                    cases.push(self.yul(
+                        "YulCase",
+                        self::span(&default),
+                        json!({ "value": "default", "body": body }),
+                    ));
+                }
+
+                self.yul(
+                    "YulSwitch",
+                    span,
+                    json!({ "expression": expression, "cases": cases }),
+                )
+            }
+            AssemblyItem::AssemblyFor(statement) => {
+                let pre = self.assembly_block(statement.init);
+                let condition = self.assembly_expression(statement.test);
+                let post = self.assembly_block(statement.update);
+                let body = self.assembly_block(statement.body);
+
+                self.yul(
+                    "YulForLoop",
+                    span,
+                    json!({ "pre": pre, "condition": condition, "post": post, "body": body }),
+                )
+            }
+            AssemblyItem::AssemblyFunctionDefinition(function) => {
+                let typed_names = |compact: &Compact, ids: IdentifierList| -> Vec<Value> {
+                    ids.iter()
+                        .map(|id| {
+                            compact.yul("YulTypedName", self::span(id), json!({ "name": id.value }))
+                        })
+                        .collect()
+                };
+                let parameters = typed_names(self, function.params);
+                let return_variables = typed_names(self, function.returns);
+                let body = self.assembly_block(function.body);
+
+                self.yul(
+                    "YulFunctionDefinition",
+                    span,
+                    json!({
+                        "name": function.name.value,
+                        "parameters": parameters,
+                        "returnVariables": return_variables,
+                        "body": body,
+                    }),
+                )
+            }
+            AssemblyItem::FunctionalAssemblyExpression(call) => {
+                let expression = self.assembly_call(span, call);
+
+                self.yul(
+                    "YulExpressionStatement",
+                    span,
+                    json!({ "expression": expression }),
+                )
+            }
+            AssemblyItem::Leave => self.yul("YulLeave", span, json!({})),
+            AssemblyItem::Break => self.yul("YulBreak", span, json!({})),
+            AssemblyItem::Continue => self.yul("YulContinue", span, json!({})),
+        }
+    }
+
+    fn assembly_expression(&mut self, expression: AssemblyExpressionNode) -> Value {
+        let span = span(&expression);
+
+        match expression.value {
+            AssemblyExpression::Identifier(name) => {
+                self.yul("YulIdentifier", span, json!({ "name": name }))
+            }
+            AssemblyExpression::Literal(primitive) => {
+                self.yul("YulLiteral", span, literal(primitive))
+            }
+            AssemblyExpression::FunctionalAssemblyExpression(call) => {
+                self.assembly_call(span, call)
+            }
+        }
+    }
+
+    fn assembly_call(&mut self, span: Span, call: FunctionalAssemblyExpression) -> Value {
+        let function_name = self.yul(
+            "YulIdentifier",
+            self::span(&call.id),
+            json!({ "name": call.id.value }),
+        );
+        let arguments: Vec<_> = call
+            .arguments
+            .iter()
+            .map(|argument| self.assembly_expression(*argument))
+            .collect();
+
+        self.yul(
+            "YulFunctionCall",
+            span,
+            json!({ "functionName": function_name, "arguments": arguments }),
+        )
+    }
+}
+
+/// Literals of a pragma, split like solc does for the name, then each operator and
+/// version of the ranges.
+fn literals(pragma: PragmaDirective) -> Vec<String> {
+    match pragma {
+        PragmaDirective::Solidity { ranges } => {
+            let mut literals = vec!["solidity".to_string()];
+
+            for (index, range) in ranges.iter().enumerate() {
+                if index > 0 {
+                    literals.push("||".to_string());
+                }
+
+                for comparator in range.value.comparators.iter() {
+                    let operator = match comparator.value.operator {
+                        VersionOperator::Exact => "",
+                        VersionOperator::Caret => "^",
+                        VersionOperator::Tilde => "~",
+                        VersionOperator::Greater => ">",
+                        VersionOperator::GreaterEquals => ">=",
+                        VersionOperator::Lesser => "<",
+                        VersionOperator::LesserEquals => "<=",
+                    };
+                    let version = comparator.value.version;
+                    let version = [Some(version.major), version.minor, version.patch]
+                        .iter()
+                        .flatten()
+                        .map(|part| part.to_string())
+                        .collect::<Vec<_>>()
+                        .join(".");
+
+                    if !operator.is_empty() {
+                        literals.push(operator.to_string());
+                    }
+
+                    literals.push(version);
+                }
+            }
+
+            literals
+        }
+        PragmaDirective::Abicoder { version } => {
+            vec!["abicoder".to_string(), version.value.to_string()]
+        }
+        PragmaDirective::Experimental { feature } => {
+            vec!["experimental".to_string(), feature.value.to_string()]
+        }
+    }
+}
+
+fn literal(primitive: Primitive) -> Value {
+    let (kind, value) = match primitive {
+        Primitive::Bool(value) => ("bool", value.to_string()),
+        Primitive::HexNumber(value) | Primitive::RationalNumber(value) => {
+            ("number", value.to_string())
+        }
+        Primitive::IntegerNumber(value, unit) => {
+            let subdenomination = match unit {
+                NumberUnit::None => None,
+                NumberUnit::Ether(unit) => Some(match unit {
+                    EtherUnit::Ether => "ether",
+                    EtherUnit::Finney => "finney",
+                    EtherUnit::Szabo => "szabo",
+                    EtherUnit::Gwei => "gwei",
+                    EtherUnit::Wei => "wei",
+                }),
+                NumberUnit::Time(unit) => Some(match unit {
+                    TimeUnit::Years => "years",
+                    TimeUnit::Months => "months",
+                    TimeUnit::Weeks => "weeks",
+                    TimeUnit::Days => "days",
+                    TimeUnit::Hours => "hours",
+                    TimeUnit::Minutes => "minutes",
+                    TimeUnit::Seconds => "seconds",
+                }),
+            };
+
+            return json!({ "kind": "number", "value": value, "subdenomination": subdenomination });
+        }
+        Primitive::String(value) => ("string", unquote(value).to_string()),
+        Primitive::HexString(value) => ("hexString", unquote(value).to_string()),
+        Primitive::UnicodeString(value) => ("unicodeString", unquote(value).to_string()),
+    };
+
+    json!({ "kind": kind, "value": value })
+}
+
+/// Contents of a string literal, without the quotes.
+fn unquote(literal: &str) -> &str {
+    match literal.len() {
+        0 | 1 => literal,
+        len => &literal[1..len - 1],
+    }
+}
+
+fn storage_location(location: Option<Node<StorageLocation>>) -> &'static str {
+    match location.map(|location| location.value) {
+        Some(StorageLocation::Memory) => "memory",
+        Some(StorageLocation::Storage) => "storage",
+        Some(StorageLocation::Calldata) => "calldata",
+        None => "default",
+    }
+}
+
+fn elementary_name(elementary: ElementaryTypeName) -> String {
+    match elementary {
+        ElementaryTypeName::Address | ElementaryTypeName::AddressPayable => "address".to_string(),
+        ElementaryTypeName::Bool => "bool".to_string(),
+        ElementaryTypeName::String => "string".to_string(),
+        ElementaryTypeName::Bytes => "bytes".to_string(),
+        ElementaryTypeName::Int(size) => format!("int{}", size as u32 * 8),
+        ElementaryTypeName::Uint(size) => format!("uint{}", size as u32 * 8),
+        ElementaryTypeName::Byte(size) => format!("bytes{}", size),
+        ElementaryTypeName::Fixed(size, decimals) => {
+            format!("fixed{}x{}", size as u32 * 8, decimals)
+        }
+        ElementaryTypeName::Ufixed(size, decimals) => {
+            format!("ufixed{}x{}", size as u32 * 8, decimals)
+        }
+    }
+}
+
+fn prefix_operator(operator: PrefixOperator) -> &'static str {
+    match operator {
+        PrefixOperator::LogicalNot => "!",
+        PrefixOperator::BitNot => "~",
+        PrefixOperator::Delete => "delete",
+        PrefixOperator::Increment => "++",
+        PrefixOperator::Decrement => "--",
+        PrefixOperator::Plus => "+",
+        PrefixOperator::Minus => "-",
+    }
+}
+
+fn binary_operator(operator: BinaryOperator) -> &'static str {
+    match operator {
+        BinaryOperator::Multiplication => "*",
+        BinaryOperator::Division => "/",
+        BinaryOperator::Remainder => "%",
+        BinaryOperator::Exponent => "**",
+        BinaryOperator::Addition => "+",
+        BinaryOperator::Subtraction => "-",
+        BinaryOperator::BitShiftLeft => "<<",
+        BinaryOperator::BitShiftRight => ">>",
+        BinaryOperator::Lesser => "<",
+        BinaryOperator::LesserEquals => "<=",
+        BinaryOperator::Greater => ">",
+        BinaryOperator::GreaterEquals => ">=",
+        BinaryOperator::Equality => "==",
+        BinaryOperator::Inequality => "!=",
+        BinaryOperator::BitAnd => "&",
+        BinaryOperator::BitXor => "^",
+        BinaryOperator::BitOr => "|",
+        BinaryOperator::LogicalAnd => "&&",
+        BinaryOperator::LogicalOr => "||",
+    }
+}
+
+fn assignment_operator(operator: AssignmentOperator) -> &'static str {
+    match operator {
+        AssignmentOperator::Plain => "=",
+        AssignmentOperator::Addition => "+=",
+        AssignmentOperator::Subtraction => "-=",
+        AssignmentOperator::Multiplication => "*=",
+        AssignmentOperator::Division => "/=",
+        AssignmentOperator::Remainder => "%=",
+        AssignmentOperator::BitShiftLeft => "<<=",
+        AssignmentOperator::BitShiftRight => ">>=",
+        AssignmentOperator::BitAnd => "&=",
+        AssignmentOperator::BitXor => "^=",
+        AssignmentOperator::BitOr => "|=",
+    }
+}
+
+fn user_definable_operator(operator: UserDefinableOperator) -> &'static str {
+    match operator {
+        UserDefinableOperator::BitAnd => "&",
+        UserDefinableOperator::BitOr => "|",
+        UserDefinableOperator::BitXor => "^",
+        UserDefinableOperator::BitNot => "~",
+        UserDefinableOperator::Addition => "+",
+        UserDefinableOperator::Subtraction => "-",
+        UserDefinableOperator::Multiplication => "*",
+        UserDefinableOperator::Division => "/",
+        UserDefinableOperator::Remainder => "%",
+        UserDefinableOperator::Equality => "==",
+        UserDefinableOperator::Inequality => "!=",
+        UserDefinableOperator::Lesser => "<",
+        UserDefinableOperator::LesserEquals => "<=",
+        UserDefinableOperator::Greater => ">",
+        UserDefinableOperator::GreaterEquals => ">=",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use toolshed::Arena;
+
+    #[test]
+    fn compact_ast_of_a_contract() {
+        let arena = Arena::new();
+        let builder = AstBuilder::new(&arena);
+
+        // contract A { uint256 x = y + 1; }
+        let init = builder.node_at(
+            25,
+            30,
+            BinaryExpression {
+                left: builder.node_at(25, 26, Expression::IdentifierExpression("y")),
+                operator: builder.node_at(27, 28, BinaryOperator::Addition),
+                right: builder.uint_lit(1),
+            },
+        );
+        let variable = builder.node_at(
+            13,
+            31,
+            StateVariableDeclaration {
+                type_name: builder.node_at(13, 20, ElementaryTypeName::Uint(32)),
+                visibility: None,
+                constant: None,
+                name: builder.node_at(21, 22, "x"),
+                init: Some(init),
+            },
+        );
+        let contract = builder.node_at(
+            0,
+            33,
+            ContractDefinition {
+                name: builder.node_at(9, 10, "A"),
+                inherits: NodeList::empty(),
+                body: builder.list(&[variable]),
+            },
+        );
+
+        let ast = compact_ast(builder.list(&[contract]), 2);
+
+        assert_eq!(ast["nodeType"], "SourceUnit");
+        assert_eq!(ast["src"], "0:33:2");
+
+        let contract = &ast["nodes"][0];
+
+        assert_eq!(contract["nodeType"], "ContractDefinition");
+        assert_eq!(contract["name"], "A");
+        assert_eq!(contract["src"], "0:33:2");
+
+        let variable = &contract["nodes"][0];
+
+        assert_eq!(variable["nodeType"], "VariableDeclaration");
+        assert_eq!(variable["src"], "13:18:2");
+        assert_eq!(variable["stateVariable"], true);
+        assert_eq!(variable["visibility"], "internal");
+        assert_eq!(variable["typeName"]["name"], "uint256");
+
+        let value = &variable["value"];
+
+        assert_eq!(value["nodeType"], "BinaryOperation");
+        assert_eq!(value["operator"], "+");
+        assert_eq!(value["leftExpression"]["name"], "y");
+        assert_eq!(value["rightExpression"]["kind"], "number");
+        assert_eq!(value["rightExpression"]["value"], "1");
+        assert_eq!(value["rightExpression"]["src"], "-1:-1:-1");
+
+        // Children are numbered before their parents
+        assert_eq!(variable["typeName"]["id"], 0);
+        assert_eq!(value["leftExpression"]["id"], 1);
+        assert_eq!(value["id"], 3);
+        assert_eq!(variable["id"], 4);
+        assert_eq!(ast["id"], 6);
+    }
+}
@@ -1,9 +1,11 @@
 use crate::{
-    BlockNode, ExpressionNode, Flag, FlagNode, FunctionDefinition, IdentifierList, IdentifierNode,
-    Node, NodeList, ParameterList, TypeNameNode, VariableDeclarationList,
+    BlockNode, ElementaryTypeNameNode, ExpressionNode, Flag, FlagNode, FunctionDefinition,
+    IdentifierList, IdentifierNode, Node, NodeList, ParameterList, TypeNameNode, Unknown,
+    VariableDeclarationList,
 };
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ContractDefinition<'ast> {
     pub name: IdentifierNode<'ast>,
     pub inherits: IdentifierList<'ast>,
@@ -11,6 +13,7 @@ pub struct ContractDefinition<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ContractPart<'ast> {
     StateVariableDeclaration(StateVariableDeclaration<'ast>),
     UsingForDeclaration(UsingForDeclaration<'ast>),
@@ -19,9 +22,13 @@ pub enum ContractPart<'ast> {
     FunctionDefinition(FunctionDefinition<'ast>),
     EventDefinition(EventDefinition<'ast>),
     EnumDefinition(EnumDefinition<'ast>),
+    ErrorDefinition(ErrorDefinition<'ast>),
+    UserDefinedValueTypeDefinition(UserDefinedValueTypeDefinition<'ast>),
+    Unknown(Unknown<'ast>),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StateVariableDeclaration<'ast> {
     pub type_name: TypeNameNode<'ast>,
     pub visibility: Option<Node<'ast, StateVariableVisibility>>,
@@ -30,26 +37,64 @@ pub struct StateVariableDeclaration<'ast> {
     pub init: Option<ExpressionNode<'ast>>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StateVariableVisibility {
     Public,
     Internal,
     Private,
 }
 
+/// `using L for T;`, `using L for *;` or `using {f, L.g as +} for T global;`
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UsingForDeclaration<'ast> {
-    pub id: IdentifierNode<'ast>,
+    /// The library, `None` when a list of functions is used instead
+    pub id: Option<IdentifierNode<'ast>>,
+    pub functions: UsingForFunctionList<'ast>,
+    /// `None` for `*`
     pub type_name: Option<TypeNameNode<'ast>>,
+    /// Only allowed at file level
+    pub global: Option<FlagNode<'ast>>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UsingForFunction<'ast> {
+    pub id: IdentifierNode<'ast>,
+    pub operator: Option<Node<'ast, UserDefinableOperator>>,
+}
+
+/// Operators that can be bound to functions for user defined value types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum UserDefinableOperator {
+    BitAnd,
+    BitOr,
+    BitXor,
+    BitNot,
+    Addition,
+    Subtraction,
+    Multiplication,
+    Division,
+    Remainder,
+    Equality,
+    Inequality,
+    Lesser,
+    LesserEquals,
+    Greater,
+    GreaterEquals,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StructDefinition<'ast> {
     pub name: IdentifierNode<'ast>,
     pub body: VariableDeclarationList<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ModifierDefinition<'ast> {
     pub name: IdentifierNode<'ast>,
     pub params: ParameterList<'ast>,
@@ -57,6 +102,7 @@ pub struct ModifierDefinition<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EventDefinition<'ast> {
     pub anonymous: Option<FlagNode<'ast>>,
     pub name: IdentifierNode<'ast>,
@@ -64,6 +110,7 @@ pub struct EventDefinition<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndexedParameter<'ast> {
     pub type_name: TypeNameNode<'ast>,
     pub indexed: Option<FlagNode<'ast>>,
@@ -71,14 +118,31 @@ pub struct IndexedParameter<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EnumDefinition<'ast> {
     pub name: IdentifierNode<'ast>,
     pub variants: IdentifierList<'ast>,
 }
 
+/// `type Price is uint128;`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UserDefinedValueTypeDefinition<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub underlying: ElementaryTypeNameNode<'ast>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorDefinition<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub params: ParameterList<'ast>,
+}
+
 pub type ContractPartNode<'ast> = Node<'ast, ContractPart<'ast>>;
 pub type ContractPartList<'ast> = NodeList<'ast, ContractPart<'ast>>;
 pub type IndexedParameterList<'ast> = NodeList<'ast, IndexedParameter<'ast>>;
+pub type UsingForFunctionList<'ast> = NodeList<'ast, UsingForFunction<'ast>>;
 
 impl_from! {
     StateVariableDeclaration => ContractPart::StateVariableDeclaration,
@@ -88,4 +152,7 @@ impl_from! {
     FunctionDefinition => ContractPart::FunctionDefinition,
     EventDefinition => ContractPart::EventDefinition,
     EnumDefinition => ContractPart::EnumDefinition,
+    ErrorDefinition => ContractPart::ErrorDefinition,
+    UserDefinedValueTypeDefinition => ContractPart::UserDefinedValueTypeDefinition,
+    Unknown => ContractPart::Unknown,
 }
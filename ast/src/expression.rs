@@ -1,6 +1,7 @@
-use crate::{ElementaryTypeName, Identifier, IdentifierNode, Node, NodeList};
+use crate::{ElementaryTypeName, Identifier, IdentifierNode, Node, NodeList, TypeNameNode};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Expression<'ast> {
     ThisExpression,
     IdentifierExpression(Identifier<'ast>),
@@ -11,30 +12,40 @@ pub enum Expression<'ast> {
     AssignmentExpression(AssignmentExpression<'ast>),
     TupleExpression(TupleExpression<'ast>),
     CallExpression(CallExpression<'ast>),
+    CallOptionsExpression(CallOptionsExpression<'ast>),
     MemberAccessExpression(MemberAccessExpression<'ast>),
     IndexAccessExpression(IndexAccessExpression<'ast>),
     ConditionalExpression(ConditionalExpression<'ast>),
     ElementaryTypeExpression(ElementaryTypeName),
+    NewExpression(NewExpression<'ast>),
+    TypeExpression(TypeExpression<'ast>),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Primitive<'ast> {
     Bool(bool),
     HexNumber(&'ast str),
     IntegerNumber(&'ast str, NumberUnit),
     RationalNumber(&'ast str),
     String(&'ast str),
+    /// `hex"deadbeef"`, holding the quoted part
+    HexString(&'ast str),
+    /// `unicode"..."`, holding the quoted part
+    UnicodeString(&'ast str),
 }
 
 // TODO: Exact units
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum NumberUnit {
     None,
     Ether(EtherUnit),
     Time(TimeUnit),
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TimeUnit {
     Years,
     Months,
@@ -45,15 +56,18 @@ pub enum TimeUnit {
     Seconds,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum EtherUnit {
     Ether,
     Finney,
     Szabo,
+    Gwei,
     Wei,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PrefixOperator {
     LogicalNot,
     BitNot,
@@ -64,13 +78,15 @@ pub enum PrefixOperator {
     Minus,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum PostfixOperator {
     Increment,
     Decrement,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum BinaryOperator {
     Multiplication,
     Division,
@@ -93,7 +109,8 @@ pub enum BinaryOperator {
     LogicalOr,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AssignmentOperator {
     Plain,
     Addition,
@@ -109,18 +126,21 @@ pub enum AssignmentOperator {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PrefixExpression<'ast> {
     pub operator: Node<'ast, PrefixOperator>,
     pub operand: ExpressionNode<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct PostfixExpression<'ast> {
     pub operand: ExpressionNode<'ast>,
     pub operator: Node<'ast, PostfixOperator>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BinaryExpression<'ast> {
     pub left: ExpressionNode<'ast>,
     pub operator: Node<'ast, BinaryOperator>,
@@ -128,6 +148,7 @@ pub struct BinaryExpression<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AssignmentExpression<'ast> {
     pub left: ExpressionNode<'ast>,
     pub operator: Node<'ast, AssignmentOperator>,
@@ -135,39 +156,117 @@ pub struct AssignmentExpression<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TupleExpression<'ast> {
     pub expressions: ExpressionList<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CallExpression<'ast> {
     pub callee: ExpressionNode<'ast>,
-    pub arguments: ExpressionList<'ast>,
+    pub arguments: CallArguments<'ast>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CallArguments<'ast> {
+    /// `f(a, b)`
+    Positional(ExpressionList<'ast>),
+    /// `f({to: a, amount: b})`
+    Named(NamedArgumentList<'ast>),
+}
+
+/// `addr.call{value: amount}`, the options apply to the call that follows.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CallOptionsExpression<'ast> {
+    pub callee: ExpressionNode<'ast>,
+    pub options: NamedArgumentList<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NamedArgument<'ast> {
+    pub name: IdentifierNode<'ast>,
+    pub value: ExpressionNode<'ast>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MemberAccessExpression<'ast> {
     pub object: ExpressionNode<'ast>,
     pub member: IdentifierNode<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IndexAccessExpression<'ast> {
     pub array: ExpressionNode<'ast>,
     pub index: Option<ExpressionNode<'ast>>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ConditionalExpression<'ast> {
     pub test: ExpressionNode<'ast>,
     pub consequent: ExpressionNode<'ast>,
     pub alternate: ExpressionNode<'ast>,
 }
 
+/// `new Token` or `new uint256[]`, the arguments are applied by the surrounding call.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NewExpression<'ast> {
+    pub type_name: TypeNameNode<'ast>,
+}
+
+/// `type(T)`, used to access members like `type(uint256).max`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TypeExpression<'ast> {
+    pub type_name: TypeNameNode<'ast>,
+}
+
+impl<'ast> CallArguments<'ast> {
+    /// Values of the arguments in source order, regardless of how they are passed.
+    pub fn values(&self) -> impl Iterator<Item = ExpressionNode<'ast>> {
+        let (positional, named) = match *self {
+            CallArguments::Positional(list) => (Some(list), None),
+            CallArguments::Named(list) => (None, Some(list)),
+        };
+
+        positional
+            .into_iter()
+            .flat_map(|list| list.iter().copied())
+            .chain(
+                named
+                    .into_iter()
+                    .flat_map(|list| list.iter().map(|argument| argument.value.value)),
+            )
+    }
+}
+
 pub use self::Expression::ThisExpression;
 
 pub type ExpressionNode<'ast> = Node<'ast, Expression<'ast>>;
 pub type ExpressionList<'ast> = NodeList<'ast, Expression<'ast>>;
+pub type NamedArgumentNode<'ast> = Node<'ast, NamedArgument<'ast>>;
+pub type NamedArgumentList<'ast> = NodeList<'ast, NamedArgument<'ast>>;
+
+impl<'ast> From<ExpressionList<'ast>> for CallArguments<'ast> {
+    #[inline]
+    fn from(list: ExpressionList<'ast>) -> CallArguments<'ast> {
+        CallArguments::Positional(list)
+    }
+}
+
+impl<'ast> From<NamedArgumentList<'ast>> for CallArguments<'ast> {
+    #[inline]
+    fn from(list: NamedArgumentList<'ast>) -> CallArguments<'ast> {
+        CallArguments::Named(list)
+    }
+}
 
 impl<'ast> From<ElementaryTypeName> for Expression<'ast> {
     #[inline]
@@ -185,7 +284,10 @@ impl_from! {
     AssignmentExpression => Expression::AssignmentExpression,
     TupleExpression => Expression::TupleExpression,
     CallExpression => Expression::CallExpression,
+    CallOptionsExpression => Expression::CallOptionsExpression,
     MemberAccessExpression => Expression::MemberAccessExpression,
     IndexAccessExpression => Expression::IndexAccessExpression,
     ConditionalExpression => Expression::ConditionalExpression,
+    NewExpression => Expression::NewExpression,
+    TypeExpression => Expression::TypeExpression,
 }
@@ -0,0 +1,1307 @@
+use toolshed::Arena;
+
+use crate::*;
+
+/// Traversal that rebuilds the AST, allocating the new nodes on `arena`.
+///
+/// Every `fold_*` method defaults to the matching free function, which folds the
+/// children of the node and allocates a copy holding the results with the original
+/// span. Overriding a method allows replacing any node, the rest of the tree is
+/// rebuilt around it. Leaves, like identifiers and pragmas, are shared with the
+/// original tree unless they are replaced.
+pub trait Folder<'ast>: Sized {
+    /// Arena the rebuilt nodes are allocated on, usually `Program::arena`.
+    fn arena(&self) -> &'ast Arena;
+
+    fn fold_source_unit(&mut self, unit: SourceUnitNode<'ast>) -> SourceUnitNode<'ast> {
+        fold_source_unit(self, unit)
+    }
+
+    fn fold_constant_declaration(
+        &mut self,
+        constant: ConstantDeclaration<'ast>,
+    ) -> ConstantDeclaration<'ast> {
+        fold_constant_declaration(self, constant)
+    }
+
+    fn fold_contract_definition(
+        &mut self,
+        contract: ContractDefinition<'ast>,
+    ) -> ContractDefinition<'ast> {
+        fold_contract_definition(self, contract)
+    }
+
+    fn fold_contract_part(&mut self, part: ContractPartNode<'ast>) -> ContractPartNode<'ast> {
+        fold_contract_part(self, part)
+    }
+
+    fn fold_state_variable_declaration(
+        &mut self,
+        variable: StateVariableDeclaration<'ast>,
+    ) -> StateVariableDeclaration<'ast> {
+        fold_state_variable_declaration(self, variable)
+    }
+
+    fn fold_using_for_declaration(
+        &mut self,
+        using: UsingForDeclaration<'ast>,
+    ) -> UsingForDeclaration<'ast> {
+        fold_using_for_declaration(self, using)
+    }
+
+    fn fold_struct_definition(
+        &mut self,
+        definition: StructDefinition<'ast>,
+    ) -> StructDefinition<'ast> {
+        fold_struct_definition(self, definition)
+    }
+
+    fn fold_modifier_definition(
+        &mut self,
+        modifier: ModifierDefinition<'ast>,
+    ) -> ModifierDefinition<'ast> {
+        fold_modifier_definition(self, modifier)
+    }
+
+    fn fold_event_definition(&mut self, event: EventDefinition<'ast>) -> EventDefinition<'ast> {
+        fold_event_definition(self, event)
+    }
+
+    fn fold_indexed_parameter(
+        &mut self,
+        parameter: Node<'ast, IndexedParameter<'ast>>,
+    ) -> Node<'ast, IndexedParameter<'ast>> {
+        fold_indexed_parameter(self, parameter)
+    }
+
+    fn fold_enum_definition(&mut self, definition: EnumDefinition<'ast>) -> EnumDefinition<'ast> {
+        fold_enum_definition(self, definition)
+    }
+
+    fn fold_error_definition(
+        &mut self,
+        definition: ErrorDefinition<'ast>,
+    ) -> ErrorDefinition<'ast> {
+        fold_error_definition(self, definition)
+    }
+
+    fn fold_function_definition(
+        &mut self,
+        function: FunctionDefinition<'ast>,
+    ) -> FunctionDefinition<'ast> {
+        fold_function_definition(self, function)
+    }
+
+    fn fold_modifier_invocation(
+        &mut self,
+        invocation: Node<'ast, ModifierInvocation<'ast>>,
+    ) -> Node<'ast, ModifierInvocation<'ast>> {
+        fold_modifier_invocation(self, invocation)
+    }
+
+    fn fold_parameter(
+        &mut self,
+        parameter: Node<'ast, Parameter<'ast>>,
+    ) -> Node<'ast, Parameter<'ast>> {
+        fold_parameter(self, parameter)
+    }
+
+    fn fold_statement(&mut self, statement: StatementNode<'ast>) -> StatementNode<'ast> {
+        fold_statement(self, statement)
+    }
+
+    fn fold_simple_statement(
+        &mut self,
+        statement: SimpleStatementNode<'ast>,
+    ) -> SimpleStatementNode<'ast> {
+        fold_simple_statement(self, statement)
+    }
+
+    fn fold_block(&mut self, block: Block<'ast>) -> Block<'ast> {
+        fold_block(self, block)
+    }
+
+    fn fold_if_statement(&mut self, statement: IfStatement<'ast>) -> IfStatement<'ast> {
+        fold_if_statement(self, statement)
+    }
+
+    fn fold_while_statement(&mut self, statement: WhileStatement<'ast>) -> WhileStatement<'ast> {
+        fold_while_statement(self, statement)
+    }
+
+    fn fold_for_statement(&mut self, statement: ForStatement<'ast>) -> ForStatement<'ast> {
+        fold_for_statement(self, statement)
+    }
+
+    fn fold_do_while_statement(
+        &mut self,
+        statement: DoWhileStatement<'ast>,
+    ) -> DoWhileStatement<'ast> {
+        fold_do_while_statement(self, statement)
+    }
+
+    fn fold_return_statement(&mut self, statement: ReturnStatement<'ast>) -> ReturnStatement<'ast> {
+        fold_return_statement(self, statement)
+    }
+
+    fn fold_emit_statement(&mut self, statement: EmitStatement<'ast>) -> EmitStatement<'ast> {
+        fold_emit_statement(self, statement)
+    }
+
+    fn fold_revert_statement(&mut self, statement: RevertStatement<'ast>) -> RevertStatement<'ast> {
+        fold_revert_statement(self, statement)
+    }
+
+    fn fold_unchecked_block(&mut self, unchecked: UncheckedBlock<'ast>) -> UncheckedBlock<'ast> {
+        fold_unchecked_block(self, unchecked)
+    }
+
+    fn fold_try_statement(&mut self, statement: TryStatement<'ast>) -> TryStatement<'ast> {
+        fold_try_statement(self, statement)
+    }
+
+    fn fold_catch_clause(&mut self, clause: CatchClauseNode<'ast>) -> CatchClauseNode<'ast> {
+        fold_catch_clause(self, clause)
+    }
+
+    fn fold_variable_definition_statement(
+        &mut self,
+        statement: VariableDefinitionStatement<'ast>,
+    ) -> VariableDefinitionStatement<'ast> {
+        fold_variable_definition_statement(self, statement)
+    }
+
+    fn fold_inferred_definition_statement(
+        &mut self,
+        statement: InferredDefinitionStatement<'ast>,
+    ) -> InferredDefinitionStatement<'ast> {
+        fold_inferred_definition_statement(self, statement)
+    }
+
+    fn fold_inline_assembly_statement(
+        &mut self,
+        statement: InlineAssemblyStatement<'ast>,
+    ) -> InlineAssemblyStatement<'ast> {
+        fold_inline_assembly_statement(self, statement)
+    }
+
+    fn fold_type_name(&mut self, type_name: TypeNameNode<'ast>) -> TypeNameNode<'ast> {
+        fold_type_name(self, type_name)
+    }
+
+    fn fold_variable_declaration(
+        &mut self,
+        declaration: VariableDeclarationNode<'ast>,
+    ) -> VariableDeclarationNode<'ast> {
+        fold_variable_declaration(self, declaration)
+    }
+
+    fn fold_mapping(&mut self, mapping: Mapping<'ast>) -> Mapping<'ast> {
+        fold_mapping(self, mapping)
+    }
+
+    fn fold_array_type_name(&mut self, array: ArrayTypeName<'ast>) -> ArrayTypeName<'ast> {
+        fold_array_type_name(self, array)
+    }
+
+    fn fold_expression(&mut self, expression: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+        fold_expression(self, expression)
+    }
+
+    fn fold_prefix_expression(&mut self, prefix: PrefixExpression<'ast>) -> PrefixExpression<'ast> {
+        fold_prefix_expression(self, prefix)
+    }
+
+    fn fold_postfix_expression(
+        &mut self,
+        postfix: PostfixExpression<'ast>,
+    ) -> PostfixExpression<'ast> {
+        fold_postfix_expression(self, postfix)
+    }
+
+    fn fold_binary_expression(&mut self, binary: BinaryExpression<'ast>) -> BinaryExpression<'ast> {
+        fold_binary_expression(self, binary)
+    }
+
+    fn fold_assignment_expression(
+        &mut self,
+        assignment: AssignmentExpression<'ast>,
+    ) -> AssignmentExpression<'ast> {
+        fold_assignment_expression(self, assignment)
+    }
+
+    fn fold_tuple_expression(&mut self, tuple: TupleExpression<'ast>) -> TupleExpression<'ast> {
+        fold_tuple_expression(self, tuple)
+    }
+
+    fn fold_call_expression(&mut self, call: CallExpression<'ast>) -> CallExpression<'ast> {
+        fold_call_expression(self, call)
+    }
+
+    fn fold_call_options_expression(
+        &mut self,
+        call: CallOptionsExpression<'ast>,
+    ) -> CallOptionsExpression<'ast> {
+        fold_call_options_expression(self, call)
+    }
+
+    fn fold_named_argument(
+        &mut self,
+        argument: NamedArgumentNode<'ast>,
+    ) -> NamedArgumentNode<'ast> {
+        fold_named_argument(self, argument)
+    }
+
+    fn fold_member_access_expression(
+        &mut self,
+        access: MemberAccessExpression<'ast>,
+    ) -> MemberAccessExpression<'ast> {
+        fold_member_access_expression(self, access)
+    }
+
+    fn fold_index_access_expression(
+        &mut self,
+        access: IndexAccessExpression<'ast>,
+    ) -> IndexAccessExpression<'ast> {
+        fold_index_access_expression(self, access)
+    }
+
+    fn fold_conditional_expression(
+        &mut self,
+        conditional: ConditionalExpression<'ast>,
+    ) -> ConditionalExpression<'ast> {
+        fold_conditional_expression(self, conditional)
+    }
+
+    fn fold_new_expression(&mut self, new: NewExpression<'ast>) -> NewExpression<'ast> {
+        fold_new_expression(self, new)
+    }
+
+    fn fold_type_expression(&mut self, expression: TypeExpression<'ast>) -> TypeExpression<'ast> {
+        fold_type_expression(self, expression)
+    }
+
+    fn fold_inline_assembly_block(
+        &mut self,
+        block: InlineAssemblyBlock<'ast>,
+    ) -> InlineAssemblyBlock<'ast> {
+        fold_inline_assembly_block(self, block)
+    }
+
+    fn fold_assembly_item(&mut self, item: AssemblyItemNode<'ast>) -> AssemblyItemNode<'ast> {
+        fold_assembly_item(self, item)
+    }
+
+    fn fold_assembly_expression(
+        &mut self,
+        expression: AssemblyExpressionNode<'ast>,
+    ) -> AssemblyExpressionNode<'ast> {
+        fold_assembly_expression(self, expression)
+    }
+
+    fn fold_assembly_local_binding(
+        &mut self,
+        binding: AssemblyLocalBinding<'ast>,
+    ) -> AssemblyLocalBinding<'ast> {
+        fold_assembly_local_binding(self, binding)
+    }
+
+    fn fold_assembly_assignment(
+        &mut self,
+        assignment: AssemblyAssignment<'ast>,
+    ) -> AssemblyAssignment<'ast> {
+        fold_assembly_assignment(self, assignment)
+    }
+
+    fn fold_assembly_if(&mut self, statement: AssemblyIf<'ast>) -> AssemblyIf<'ast> {
+        fold_assembly_if(self, statement)
+    }
+
+    fn fold_assembly_switch(&mut self, switch: AssemblySwitch<'ast>) -> AssemblySwitch<'ast> {
+        fold_assembly_switch(self, switch)
+    }
+
+    fn fold_assembly_case(&mut self, case: AssemblyCaseNode<'ast>) -> AssemblyCaseNode<'ast> {
+        fold_assembly_case(self, case)
+    }
+
+    fn fold_assembly_for(&mut self, statement: AssemblyFor<'ast>) -> AssemblyFor<'ast> {
+        fold_assembly_for(self, statement)
+    }
+
+    fn fold_assembly_function_definition(
+        &mut self,
+        function: AssemblyFunctionDefinition<'ast>,
+    ) -> AssemblyFunctionDefinition<'ast> {
+        fold_assembly_function_definition(self, function)
+    }
+
+    fn fold_functional_assembly_expression(
+        &mut self,
+        expression: FunctionalAssemblyExpression<'ast>,
+    ) -> FunctionalAssemblyExpression<'ast> {
+        fold_functional_assembly_expression(self, expression)
+    }
+
+    /// Identifiers that are nodes of their own, see `Visitor::visit_identifier`.
+    fn fold_identifier(&mut self, identifier: IdentifierNode<'ast>) -> IdentifierNode<'ast> {
+        identifier
+    }
+}
+
+/// Folds every source unit of the program into a new list.
+pub fn fold_program<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    program: &Program<'ast>,
+) -> SourceUnitList<'ast> {
+    fold_list(folder, program.body(), F::fold_source_unit)
+}
+
+pub fn fold_source_unit<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    unit: SourceUnitNode<'ast>,
+) -> SourceUnitNode<'ast> {
+    let value = match unit.value {
+        SourceUnit::ContractDefinition(contract) => {
+            folder.fold_contract_definition(contract).into()
+        }
+        SourceUnit::StructDefinition(definition) => {
+            folder.fold_struct_definition(definition).into()
+        }
+        SourceUnit::EnumDefinition(definition) => folder.fold_enum_definition(definition).into(),
+        SourceUnit::FunctionDefinition(function) => {
+            folder.fold_function_definition(function).into()
+        }
+        SourceUnit::ErrorDefinition(definition) => folder.fold_error_definition(definition).into(),
+        SourceUnit::ConstantDeclaration(constant) => {
+            folder.fold_constant_declaration(constant).into()
+        }
+        SourceUnit::UserDefinedValueTypeDefinition(definition) => UserDefinedValueTypeDefinition {
+            name: folder.fold_identifier(definition.name),
+            ..definition
+        }
+        .into(),
+        SourceUnit::UsingForDeclaration(using) => folder.fold_using_for_declaration(using).into(),
+        SourceUnit::PragmaDirective(_)
+        | SourceUnit::ImportDirective(_)
+        | SourceUnit::Unknown(_) => return unit,
+    };
+
+    rebuild(folder, unit, value)
+}
+
+pub fn fold_constant_declaration<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    constant: ConstantDeclaration<'ast>,
+) -> ConstantDeclaration<'ast> {
+    ConstantDeclaration {
+        type_name: folder.fold_type_name(constant.type_name),
+        name: folder.fold_identifier(constant.name),
+        init: folder.fold_expression(constant.init),
+    }
+}
+
+pub fn fold_contract_definition<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    contract: ContractDefinition<'ast>,
+) -> ContractDefinition<'ast> {
+    ContractDefinition {
+        name: folder.fold_identifier(contract.name),
+        inherits: fold_list(folder, contract.inherits, F::fold_identifier),
+        body: fold_list(folder, contract.body, F::fold_contract_part),
+    }
+}
+
+pub fn fold_contract_part<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    part: ContractPartNode<'ast>,
+) -> ContractPartNode<'ast> {
+    let value = match part.value {
+        ContractPart::StateVariableDeclaration(variable) => {
+            folder.fold_state_variable_declaration(variable).into()
+        }
+        ContractPart::UsingForDeclaration(using) => folder.fold_using_for_declaration(using).into(),
+        ContractPart::StructDefinition(definition) => {
+            folder.fold_struct_definition(definition).into()
+        }
+        ContractPart::ModifierDefinition(modifier) => {
+            folder.fold_modifier_definition(modifier).into()
+        }
+        ContractPart::FunctionDefinition(function) => {
+            folder.fold_function_definition(function).into()
+        }
+        ContractPart::EventDefinition(event) => folder.fold_event_definition(event).into(),
+        ContractPart::EnumDefinition(definition) => folder.fold_enum_definition(definition).into(),
+        ContractPart::ErrorDefinition(definition) => {
+            folder.fold_error_definition(definition).into()
+        }
+        ContractPart::UserDefinedValueTypeDefinition(definition) => {
+            UserDefinedValueTypeDefinition {
+                name: folder.fold_identifier(definition.name),
+                ..definition
+            }
+            .into()
+        }
+        ContractPart::Unknown(_) => return part,
+    };
+
+    rebuild(folder, part, value)
+}
+
+pub fn fold_state_variable_declaration<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    variable: StateVariableDeclaration<'ast>,
+) -> StateVariableDeclaration<'ast> {
+    StateVariableDeclaration {
+        type_name: folder.fold_type_name(variable.type_name),
+        name: folder.fold_identifier(variable.name),
+        init: variable.init.map(|init| folder.fold_expression(init)),
+        ..variable
+    }
+}
+
+pub fn fold_using_for_declaration<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    using: UsingForDeclaration<'ast>,
+) -> UsingForDeclaration<'ast> {
+    UsingForDeclaration {
+        id: using.id.map(|id| folder.fold_identifier(id)),
+        functions: fold_list(folder, using.functions, |folder, function| {
+            let value = UsingForFunction {
+                id: folder.fold_identifier(function.value.id),
+                ..function.value
+            };
+
+            rebuild(folder, function, value)
+        }),
+        type_name: using
+            .type_name
+            .map(|type_name| folder.fold_type_name(type_name)),
+        ..using
+    }
+}
+
+pub fn fold_struct_definition<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    definition: StructDefinition<'ast>,
+) -> StructDefinition<'ast> {
+    StructDefinition {
+        name: folder.fold_identifier(definition.name),
+        body: fold_list(folder, definition.body, F::fold_variable_declaration),
+    }
+}
+
+pub fn fold_modifier_definition<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    modifier: ModifierDefinition<'ast>,
+) -> ModifierDefinition<'ast> {
+    ModifierDefinition {
+        name: folder.fold_identifier(modifier.name),
+        params: fold_list(folder, modifier.params, F::fold_parameter),
+        block: fold_block_node(folder, modifier.block),
+    }
+}
+
+pub fn fold_event_definition<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    event: EventDefinition<'ast>,
+) -> EventDefinition<'ast> {
+    EventDefinition {
+        name: folder.fold_identifier(event.name),
+        params: fold_list(folder, event.params, F::fold_indexed_parameter),
+        ..event
+    }
+}
+
+pub fn fold_indexed_parameter<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    parameter: Node<'ast, IndexedParameter<'ast>>,
+) -> Node<'ast, IndexedParameter<'ast>> {
+    let value = IndexedParameter {
+        type_name: folder.fold_type_name(parameter.value.type_name),
+        name: parameter
+            .value
+            .name
+            .map(|name| folder.fold_identifier(name)),
+        ..parameter.value
+    };
+
+    rebuild(folder, parameter, value)
+}
+
+pub fn fold_enum_definition<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    definition: EnumDefinition<'ast>,
+) -> EnumDefinition<'ast> {
+    EnumDefinition {
+        name: folder.fold_identifier(definition.name),
+        variants: fold_list(folder, definition.variants, F::fold_identifier),
+    }
+}
+
+pub fn fold_error_definition<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    definition: ErrorDefinition<'ast>,
+) -> ErrorDefinition<'ast> {
+    ErrorDefinition {
+        name: folder.fold_identifier(definition.name),
+        params: fold_list(folder, definition.params, F::fold_parameter),
+    }
+}
+
+pub fn fold_function_definition<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    function: FunctionDefinition<'ast>,
+) -> FunctionDefinition<'ast> {
+    FunctionDefinition {
+        name: function.name.map(|name| folder.fold_identifier(name)),
+        params: fold_list(folder, function.params, F::fold_parameter),
+        modifiers: fold_list(folder, function.modifiers, F::fold_modifier_invocation),
+        returns: fold_list(folder, function.returns, F::fold_parameter),
+        block: function.block.map(|block| fold_block_node(folder, block)),
+        ..function
+    }
+}
+
+pub fn fold_modifier_invocation<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    invocation: Node<'ast, ModifierInvocation<'ast>>,
+) -> Node<'ast, ModifierInvocation<'ast>> {
+    let value = ModifierInvocation {
+        id: folder.fold_identifier(invocation.value.id),
+        arguments: fold_list(folder, invocation.value.arguments, F::fold_expression),
+    };
+
+    rebuild(folder, invocation, value)
+}
+
+pub fn fold_parameter<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    parameter: Node<'ast, Parameter<'ast>>,
+) -> Node<'ast, Parameter<'ast>> {
+    let value = Parameter {
+        type_name: folder.fold_type_name(parameter.value.type_name),
+        name: parameter
+            .value
+            .name
+            .map(|name| folder.fold_identifier(name)),
+        ..parameter.value
+    };
+
+    rebuild(folder, parameter, value)
+}
+
+pub fn fold_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: StatementNode<'ast>,
+) -> StatementNode<'ast> {
+    let value = match statement.value {
+        Statement::IfStatement(statement) => folder.fold_if_statement(statement).into(),
+        Statement::WhileStatement(statement) => folder.fold_while_statement(statement).into(),
+        Statement::ForStatement(statement) => folder.fold_for_statement(statement).into(),
+        Statement::BlockStatement(block) => folder.fold_block(block).into(),
+        Statement::InlineAssemblyStatement(statement) => {
+            folder.fold_inline_assembly_statement(statement).into()
+        }
+        Statement::DoWhileStatement(statement) => folder.fold_do_while_statement(statement).into(),
+        Statement::ReturnStatement(statement) => folder.fold_return_statement(statement).into(),
+        Statement::EmitStatement(statement) => folder.fold_emit_statement(statement).into(),
+        Statement::RevertStatement(statement) => folder.fold_revert_statement(statement).into(),
+        Statement::UncheckedBlock(unchecked) => folder.fold_unchecked_block(unchecked).into(),
+        Statement::TryStatement(statement) => folder.fold_try_statement(statement).into(),
+        Statement::VariableDefinitionStatement(statement) => {
+            Statement::VariableDefinitionStatement(
+                folder.fold_variable_definition_statement(statement),
+            )
+        }
+        Statement::InferredDefinitionStatement(statement) => {
+            Statement::InferredDefinitionStatement(
+                folder.fold_inferred_definition_statement(statement),
+            )
+        }
+        Statement::ExpressionStatement(expression) => {
+            Statement::ExpressionStatement(folder.fold_expression(expression))
+        }
+        Statement::Unknown(_)
+        | Statement::Placeholder
+        | Statement::ContinueStatement
+        | Statement::BreakStatement
+        | Statement::ThrowStatement => return statement,
+    };
+
+    rebuild(folder, statement, value)
+}
+
+pub fn fold_simple_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: SimpleStatementNode<'ast>,
+) -> SimpleStatementNode<'ast> {
+    let value = match statement.value {
+        SimpleStatement::VariableDefinitionStatement(statement) => {
+            SimpleStatement::VariableDefinitionStatement(
+                folder.fold_variable_definition_statement(statement),
+            )
+        }
+        SimpleStatement::InferredDefinitionStatement(statement) => {
+            SimpleStatement::InferredDefinitionStatement(
+                folder.fold_inferred_definition_statement(statement),
+            )
+        }
+        SimpleStatement::ExpressionStatement(expression) => {
+            SimpleStatement::ExpressionStatement(folder.fold_expression(expression))
+        }
+    };
+
+    rebuild(folder, statement, value)
+}
+
+pub fn fold_block<'ast, F: Folder<'ast>>(folder: &mut F, block: Block<'ast>) -> Block<'ast> {
+    Block {
+        body: fold_list(folder, block.body, F::fold_statement),
+    }
+}
+
+pub fn fold_if_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: IfStatement<'ast>,
+) -> IfStatement<'ast> {
+    IfStatement {
+        test: folder.fold_expression(statement.test),
+        consequent: folder.fold_statement(statement.consequent),
+        alternate: statement
+            .alternate
+            .map(|alternate| folder.fold_statement(alternate)),
+    }
+}
+
+pub fn fold_while_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: WhileStatement<'ast>,
+) -> WhileStatement<'ast> {
+    WhileStatement {
+        test: folder.fold_expression(statement.test),
+        body: folder.fold_statement(statement.body),
+    }
+}
+
+pub fn fold_for_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: ForStatement<'ast>,
+) -> ForStatement<'ast> {
+    ForStatement {
+        init: statement
+            .init
+            .map(|init| folder.fold_simple_statement(init)),
+        test: statement.test.map(|test| folder.fold_expression(test)),
+        update: statement
+            .update
+            .map(|update| folder.fold_expression(update)),
+        body: folder.fold_statement(statement.body),
+    }
+}
+
+pub fn fold_do_while_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: DoWhileStatement<'ast>,
+) -> DoWhileStatement<'ast> {
+    DoWhileStatement {
+        body: folder.fold_statement(statement.body),
+        test: folder.fold_expression(statement.test),
+    }
+}
+
+pub fn fold_return_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: ReturnStatement<'ast>,
+) -> ReturnStatement<'ast> {
+    ReturnStatement {
+        value: statement.value.map(|value| folder.fold_expression(value)),
+    }
+}
+
+pub fn fold_emit_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: EmitStatement<'ast>,
+) -> EmitStatement<'ast> {
+    EmitStatement {
+        event: folder.fold_expression(statement.event),
+    }
+}
+
+pub fn fold_revert_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: RevertStatement<'ast>,
+) -> RevertStatement<'ast> {
+    RevertStatement {
+        error: folder.fold_expression(statement.error),
+    }
+}
+
+pub fn fold_unchecked_block<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    unchecked: UncheckedBlock<'ast>,
+) -> UncheckedBlock<'ast> {
+    UncheckedBlock {
+        block: fold_block_node(folder, unchecked.block),
+    }
+}
+
+pub fn fold_try_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: TryStatement<'ast>,
+) -> TryStatement<'ast> {
+    TryStatement {
+        call: folder.fold_expression(statement.call),
+        returns: fold_list(folder, statement.returns, F::fold_parameter),
+        body: fold_block_node(folder, statement.body),
+        clauses: fold_list(folder, statement.clauses, F::fold_catch_clause),
+    }
+}
+
+pub fn fold_catch_clause<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    clause: CatchClauseNode<'ast>,
+) -> CatchClauseNode<'ast> {
+    let value = CatchClause {
+        id: clause.value.id.map(|id| folder.fold_identifier(id)),
+        params: fold_list(folder, clause.value.params, F::fold_parameter),
+        body: fold_block_node(folder, clause.value.body),
+    };
+
+    rebuild(folder, clause, value)
+}
+
+pub fn fold_variable_definition_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: VariableDefinitionStatement<'ast>,
+) -> VariableDefinitionStatement<'ast> {
+    VariableDefinitionStatement {
+        declaration: folder.fold_variable_declaration(statement.declaration),
+        init: statement.init.map(|init| folder.fold_expression(init)),
+    }
+}
+
+pub fn fold_inferred_definition_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: InferredDefinitionStatement<'ast>,
+) -> InferredDefinitionStatement<'ast> {
+    let ids: Vec<_> = statement
+        .ids
+        .iter()
+        .map(|id| id.map(|id| folder.fold_identifier(id)))
+        .collect();
+
+    InferredDefinitionStatement {
+        ids: List::from_iter(folder.arena(), ids),
+        init: folder.fold_expression(statement.init),
+    }
+}
+
+pub fn fold_inline_assembly_statement<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: InlineAssemblyStatement<'ast>,
+) -> InlineAssemblyStatement<'ast> {
+    InlineAssemblyStatement {
+        block: fold_assembly_block_node(folder, statement.block),
+        ..statement
+    }
+}
+
+pub fn fold_type_name<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    type_name: TypeNameNode<'ast>,
+) -> TypeNameNode<'ast> {
+    let value = match type_name.value {
+        TypeName::Mapping(mapping) => folder.fold_mapping(mapping).into(),
+        TypeName::ArrayTypeName(array) => folder.fold_array_type_name(array).into(),
+        TypeName::ElementaryTypeName(_)
+        | TypeName::UserDefinedTypeName(_)
+        | TypeName::FunctionTypeName => return type_name,
+    };
+
+    rebuild(folder, type_name, value)
+}
+
+pub fn fold_variable_declaration<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    declaration: VariableDeclarationNode<'ast>,
+) -> VariableDeclarationNode<'ast> {
+    let value = VariableDeclaration {
+        type_name: folder.fold_type_name(declaration.value.type_name),
+        id: folder.fold_identifier(declaration.value.id),
+        ..declaration.value
+    };
+
+    rebuild(folder, declaration, value)
+}
+
+pub fn fold_mapping<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    mapping: Mapping<'ast>,
+) -> Mapping<'ast> {
+    Mapping {
+        to: folder.fold_type_name(mapping.to),
+        ..mapping
+    }
+}
+
+pub fn fold_array_type_name<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    array: ArrayTypeName<'ast>,
+) -> ArrayTypeName<'ast> {
+    ArrayTypeName {
+        base: folder.fold_type_name(array.base),
+        length: array.length.map(|length| folder.fold_expression(length)),
+    }
+}
+
+pub fn fold_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    expression: ExpressionNode<'ast>,
+) -> ExpressionNode<'ast> {
+    let value = match expression.value {
+        Expression::PrefixExpression(prefix) => folder.fold_prefix_expression(prefix).into(),
+        Expression::PostfixExpression(postfix) => folder.fold_postfix_expression(postfix).into(),
+        Expression::BinaryExpression(binary) => folder.fold_binary_expression(binary).into(),
+        Expression::AssignmentExpression(assignment) => {
+            folder.fold_assignment_expression(assignment).into()
+        }
+        Expression::TupleExpression(tuple) => folder.fold_tuple_expression(tuple).into(),
+        Expression::CallExpression(call) => folder.fold_call_expression(call).into(),
+        Expression::CallOptionsExpression(call) => folder.fold_call_options_expression(call).into(),
+        Expression::MemberAccessExpression(access) => {
+            folder.fold_member_access_expression(access).into()
+        }
+        Expression::IndexAccessExpression(access) => {
+            folder.fold_index_access_expression(access).into()
+        }
+        Expression::ConditionalExpression(conditional) => {
+            folder.fold_conditional_expression(conditional).into()
+        }
+        Expression::NewExpression(new) => folder.fold_new_expression(new).into(),
+        Expression::TypeExpression(expression) => folder.fold_type_expression(expression).into(),
+        Expression::ThisExpression
+        | Expression::IdentifierExpression(_)
+        | Expression::PrimitiveExpression(_)
+        | Expression::ElementaryTypeExpression(_) => return expression,
+    };
+
+    rebuild(folder, expression, value)
+}
+
+pub fn fold_prefix_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    prefix: PrefixExpression<'ast>,
+) -> PrefixExpression<'ast> {
+    PrefixExpression {
+        operand: folder.fold_expression(prefix.operand),
+        ..prefix
+    }
+}
+
+pub fn fold_postfix_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    postfix: PostfixExpression<'ast>,
+) -> PostfixExpression<'ast> {
+    PostfixExpression {
+        operand: folder.fold_expression(postfix.operand),
+        ..postfix
+    }
+}
+
+pub fn fold_binary_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    binary: BinaryExpression<'ast>,
+) -> BinaryExpression<'ast> {
+    BinaryExpression {
+        left: folder.fold_expression(binary.left),
+        right: folder.fold_expression(binary.right),
+        ..binary
+    }
+}
+
+pub fn fold_assignment_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    assignment: AssignmentExpression<'ast>,
+) -> AssignmentExpression<'ast> {
+    AssignmentExpression {
+        left: folder.fold_expression(assignment.left),
+        right: folder.fold_expression(assignment.right),
+        ..assignment
+    }
+}
+
+pub fn fold_tuple_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    tuple: TupleExpression<'ast>,
+) -> TupleExpression<'ast> {
+    TupleExpression {
+        expressions: fold_list(folder, tuple.expressions, F::fold_expression),
+    }
+}
+
+pub fn fold_call_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    call: CallExpression<'ast>,
+) -> CallExpression<'ast> {
+    let callee = folder.fold_expression(call.callee);
+    let arguments = match call.arguments {
+        CallArguments::Positional(arguments) => {
+            fold_list(folder, arguments, F::fold_expression).into()
+        }
+        CallArguments::Named(arguments) => {
+            fold_list(folder, arguments, F::fold_named_argument).into()
+        }
+    };
+
+    CallExpression { callee, arguments }
+}
+
+pub fn fold_call_options_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    call: CallOptionsExpression<'ast>,
+) -> CallOptionsExpression<'ast> {
+    CallOptionsExpression {
+        callee: folder.fold_expression(call.callee),
+        options: fold_list(folder, call.options, F::fold_named_argument),
+    }
+}
+
+pub fn fold_named_argument<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    argument: NamedArgumentNode<'ast>,
+) -> NamedArgumentNode<'ast> {
+    let value = NamedArgument {
+        name: folder.fold_identifier(argument.value.name),
+        value: folder.fold_expression(argument.value.value),
+    };
+
+    rebuild(folder, argument, value)
+}
+
+pub fn fold_member_access_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    access: MemberAccessExpression<'ast>,
+) -> MemberAccessExpression<'ast> {
+    MemberAccessExpression {
+        object: folder.fold_expression(access.object),
+        member: folder.fold_identifier(access.member),
+    }
+}
+
+pub fn fold_index_access_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    access: IndexAccessExpression<'ast>,
+) -> IndexAccessExpression<'ast> {
+    IndexAccessExpression {
+        array: folder.fold_expression(access.array),
+        index: access.index.map(|index| folder.fold_expression(index)),
+    }
+}
+
+pub fn fold_conditional_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    conditional: ConditionalExpression<'ast>,
+) -> ConditionalExpression<'ast> {
+    ConditionalExpression {
+        test: folder.fold_expression(conditional.test),
+        consequent: folder.fold_expression(conditional.consequent),
+        alternate: folder.fold_expression(conditional.alternate),
+    }
+}
+
+pub fn fold_new_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    new: NewExpression<'ast>,
+) -> NewExpression<'ast> {
+    NewExpression {
+        type_name: folder.fold_type_name(new.type_name),
+    }
+}
+
+pub fn fold_type_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    expression: TypeExpression<'ast>,
+) -> TypeExpression<'ast> {
+    TypeExpression {
+        type_name: folder.fold_type_name(expression.type_name),
+    }
+}
+
+pub fn fold_inline_assembly_block<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    block: InlineAssemblyBlock<'ast>,
+) -> InlineAssemblyBlock<'ast> {
+    InlineAssemblyBlock {
+        items: fold_list(folder, block.items, F::fold_assembly_item),
+    }
+}
+
+pub fn fold_assembly_item<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    item: AssemblyItemNode<'ast>,
+) -> AssemblyItemNode<'ast> {
+    let value = match item.value {
+        AssemblyItem::InlineAssemblyBlock(block) => folder.fold_inline_assembly_block(block).into(),
+        AssemblyItem::AssemblyLocalBinding(binding) => {
+            folder.fold_assembly_local_binding(binding).into()
+        }
+        AssemblyItem::AssemblyAssignment(assignment) => {
+            folder.fold_assembly_assignment(assignment).into()
+        }
+        AssemblyItem::AssemblyIf(statement) => folder.fold_assembly_if(statement).into(),
+        AssemblyItem::AssemblySwitch(switch) => folder.fold_assembly_switch(switch).into(),
+        AssemblyItem::AssemblyFor(statement) => folder.fold_assembly_for(statement).into(),
+        AssemblyItem::AssemblyFunctionDefinition(function) => {
+            folder.fold_assembly_function_definition(function).into()
+        }
+        AssemblyItem::FunctionalAssemblyExpression(expression) => {
+            AssemblyItem::FunctionalAssemblyExpression(
+                folder.fold_functional_assembly_expression(expression),
+            )
+        }
+        AssemblyItem::Leave | AssemblyItem::Break | AssemblyItem::Continue => return item,
+    };
+
+    rebuild(folder, item, value)
+}
+
+pub fn fold_assembly_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    expression: AssemblyExpressionNode<'ast>,
+) -> AssemblyExpressionNode<'ast> {
+    let value = match expression.value {
+        AssemblyExpression::FunctionalAssemblyExpression(functional) => {
+            AssemblyExpression::FunctionalAssemblyExpression(
+                folder.fold_functional_assembly_expression(functional),
+            )
+        }
+        AssemblyExpression::Identifier(_) | AssemblyExpression::Literal(_) => return expression,
+    };
+
+    rebuild(folder, expression, value)
+}
+
+pub fn fold_assembly_local_binding<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    binding: AssemblyLocalBinding<'ast>,
+) -> AssemblyLocalBinding<'ast> {
+    AssemblyLocalBinding {
+        ids: fold_list(folder, binding.ids, F::fold_identifier),
+        init: binding
+            .init
+            .map(|init| folder.fold_assembly_expression(init)),
+    }
+}
+
+pub fn fold_assembly_assignment<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    assignment: AssemblyAssignment<'ast>,
+) -> AssemblyAssignment<'ast> {
+    AssemblyAssignment {
+        ids: fold_list(folder, assignment.ids, F::fold_identifier),
+        init: folder.fold_assembly_expression(assignment.init),
+    }
+}
+
+pub fn fold_assembly_if<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: AssemblyIf<'ast>,
+) -> AssemblyIf<'ast> {
+    AssemblyIf {
+        test: folder.fold_assembly_expression(statement.test),
+        body: fold_assembly_block_node(folder, statement.body),
+    }
+}
+
+pub fn fold_assembly_switch<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    switch: AssemblySwitch<'ast>,
+) -> AssemblySwitch<'ast> {
+    AssemblySwitch {
+        test: folder.fold_assembly_expression(switch.test),
+        cases: fold_list(folder, switch.cases, F::fold_assembly_case),
+        default: switch
+            .default
+            .map(|default| fold_assembly_block_node(folder, default)),
+    }
+}
+
+pub fn fold_assembly_case<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    case: AssemblyCaseNode<'ast>,
+) -> AssemblyCaseNode<'ast> {
+    let value = AssemblyCase {
+        body: fold_assembly_block_node(folder, case.value.body),
+        ..case.value
+    };
+
+    rebuild(folder, case, value)
+}
+
+pub fn fold_assembly_for<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    statement: AssemblyFor<'ast>,
+) -> AssemblyFor<'ast> {
+    AssemblyFor {
+        init: fold_assembly_block_node(folder, statement.init),
+        test: folder.fold_assembly_expression(statement.test),
+        update: fold_assembly_block_node(folder, statement.update),
+        body: fold_assembly_block_node(folder, statement.body),
+    }
+}
+
+pub fn fold_assembly_function_definition<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    function: AssemblyFunctionDefinition<'ast>,
+) -> AssemblyFunctionDefinition<'ast> {
+    AssemblyFunctionDefinition {
+        name: folder.fold_identifier(function.name),
+        params: fold_list(folder, function.params, F::fold_identifier),
+        returns: fold_list(folder, function.returns, F::fold_identifier),
+        body: fold_assembly_block_node(folder, function.body),
+    }
+}
+
+pub fn fold_functional_assembly_expression<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    expression: FunctionalAssemblyExpression<'ast>,
+) -> FunctionalAssemblyExpression<'ast> {
+    FunctionalAssemblyExpression {
+        id: folder.fold_identifier(expression.id),
+        arguments: fold_list(folder, expression.arguments, F::fold_assembly_expression),
+    }
+}
+
+/// Allocates `value` with the span of `node`.
+fn rebuild<'ast, F: Folder<'ast>, T: Copy>(
+    folder: &F,
+    node: Node<'ast, T>,
+    value: T,
+) -> Node<'ast, T> {
+    Node::new(
+        folder
+            .arena()
+            .alloc(NodeInner::new(node.start, node.end, value)),
+    )
+}
+
+fn fold_list<'ast, F, T>(
+    folder: &mut F,
+    list: NodeList<'ast, T>,
+    mut fold: impl FnMut(&mut F, Node<'ast, T>) -> Node<'ast, T>,
+) -> NodeList<'ast, T>
+where
+    F: Folder<'ast>,
+    T: Copy,
+{
+    let nodes: Vec<_> = list.iter().map(|node| fold(folder, *node)).collect();
+
+    NodeList::from_iter(folder.arena(), nodes)
+}
+
+fn fold_block_node<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    block: BlockNode<'ast>,
+) -> BlockNode<'ast> {
+    let value = folder.fold_block(block.value);
+
+    rebuild(folder, block, value)
+}
+
+fn fold_assembly_block_node<'ast, F: Folder<'ast>>(
+    folder: &mut F,
+    block: InlineAssemblyBlockNode<'ast>,
+) -> InlineAssemblyBlockNode<'ast> {
+    let value = folder.fold_inline_assembly_block(block.value);
+
+    rebuild(folder, block, value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn node<'ast, T: Copy>(arena: &'ast Arena, start: u32, value: T) -> Node<'ast, T> {
+        Node::new(arena.alloc(NodeInner::new(start, start + 1, value)))
+    }
+
+    /// Replaces every `x` with `y`.
+    struct Rename<'ast> {
+        arena: &'ast Arena,
+    }
+
+    impl<'ast> Folder<'ast> for Rename<'ast> {
+        fn arena(&self) -> &'ast Arena {
+            self.arena
+        }
+
+        fn fold_expression(&mut self, expression: ExpressionNode<'ast>) -> ExpressionNode<'ast> {
+            match expression.value {
+                Expression::IdentifierExpression("x") => node(
+                    self.arena,
+                    expression.start,
+                    Expression::IdentifierExpression("y"),
+                ),
+                _ => fold_expression(self, expression),
+            }
+        }
+    }
+
+    #[test]
+    fn rebuilds_around_replaced_nodes() {
+        let arena = Arena::new();
+
+        // x = x + 1;
+        let binary = BinaryExpression {
+            left: node(&arena, 4, Expression::IdentifierExpression("x")),
+            operator: node(&arena, 6, BinaryOperator::Addition),
+            right: node(
+                &arena,
+                8,
+                Primitive::IntegerNumber("1", NumberUnit::None).into(),
+            ),
+        };
+
+        let assignment = AssignmentExpression {
+            left: node(&arena, 0, Expression::IdentifierExpression("x")),
+            operator: node(&arena, 2, AssignmentOperator::Plain),
+            right: node(&arena, 4, binary.into()),
+        };
+
+        let block = Block {
+            body: NodeList::from(
+                &arena,
+                node(&arena, 0, node(&arena, 0, assignment.into()).into()),
+            ),
+        };
+
+        let folded = Rename { arena: &arena }.fold_block(block);
+
+        let statement = folded.body.only_element().unwrap();
+        let expected = AssignmentExpression {
+            left: node(&arena, 0, Expression::IdentifierExpression("y")),
+            right: node(
+                &arena,
+                4,
+                BinaryExpression {
+                    left: node(&arena, 4, Expression::IdentifierExpression("y")),
+                    ..binary
+                }
+                .into(),
+            ),
+            ..assignment
+        };
+
+        assert_eq!(
+            statement.value,
+            Statement::ExpressionStatement(node(&arena, 0, expected.into()))
+        );
+
+        // The original tree is left untouched
+        assert_eq!(
+            block.body.only_element().unwrap().value,
+            Statement::ExpressionStatement(node(&arena, 0, assignment.into()))
+        );
+    }
+}
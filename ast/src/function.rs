@@ -1,6 +1,9 @@
-use crate::{BlockNode, ExpressionList, IdentifierNode, Node, NodeList, TypeNameNode};
+use crate::{
+    BlockNode, ExpressionList, IdentifierNode, Node, NodeList, StorageLocation, TypeNameNode,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FunctionDefinition<'ast> {
     pub name: Option<IdentifierNode<'ast>>,
     pub params: ParameterList<'ast>,
@@ -11,7 +14,8 @@ pub struct FunctionDefinition<'ast> {
     pub block: Option<BlockNode<'ast>>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum FunctionVisibility {
     External,
     Public,
@@ -19,7 +23,8 @@ pub enum FunctionVisibility {
     Private,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StateMutability {
     Pure,
     Constant,
@@ -28,14 +33,17 @@ pub enum StateMutability {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ModifierInvocation<'ast> {
     pub id: IdentifierNode<'ast>,
     pub arguments: ExpressionList<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Parameter<'ast> {
     pub type_name: TypeNameNode<'ast>,
+    pub location: Option<Node<'ast, StorageLocation>>,
     pub name: Option<IdentifierNode<'ast>>,
 }
 
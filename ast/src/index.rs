@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+
+use crate::*;
+
+/// Identifies a node of a parsed `Program`, nodes are numbered in source order with
+/// parents coming before their children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(pub u32);
+
+/// What a node of the index holds, named after the variant of the AST it stands for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    // Source units and contract parts
+    PragmaDirective,
+    ImportDirective,
+    ContractDefinition,
+    StructDefinition,
+    EnumDefinition,
+    FunctionDefinition,
+    ErrorDefinition,
+    ConstantDeclaration,
+    UserDefinedValueTypeDefinition,
+    UsingForDeclaration,
+    StateVariableDeclaration,
+    ModifierDefinition,
+    EventDefinition,
+    Unknown,
+
+    // Parts of declarations
+    UsingForFunction,
+    IndexedParameter,
+    ModifierInvocation,
+    Parameter,
+    VariableDeclaration,
+    CatchClause,
+    NamedArgument,
+    Identifier,
+
+    // Type names
+    ElementaryTypeName,
+    UserDefinedTypeName,
+    Mapping,
+    ArrayTypeName,
+    FunctionTypeName,
+
+    // Statements
+    Placeholder,
+    IfStatement,
+    WhileStatement,
+    ForStatement,
+    BlockStatement,
+    InlineAssemblyStatement,
+    DoWhileStatement,
+    ContinueStatement,
+    BreakStatement,
+    ReturnStatement,
+    ThrowStatement,
+    EmitStatement,
+    RevertStatement,
+    UncheckedBlock,
+    TryStatement,
+    VariableDefinitionStatement,
+    InferredDefinitionStatement,
+    ExpressionStatement,
+
+    // Expressions
+    ThisExpression,
+    IdentifierExpression,
+    PrimitiveExpression,
+    PrefixExpression,
+    PostfixExpression,
+    BinaryExpression,
+    AssignmentExpression,
+    TupleExpression,
+    CallExpression,
+    CallOptionsExpression,
+    MemberAccessExpression,
+    IndexAccessExpression,
+    ConditionalExpression,
+    ElementaryTypeExpression,
+    NewExpression,
+    TypeExpression,
+
+    // Assembly
+    AssemblyItem,
+    AssemblyExpression,
+    AssemblyCase,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeEntry {
+    pub parent: Option<NodeId>,
+    pub kind: NodeKind,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Parent, kind and span of every node of a parsed `Program` that has a span of its
+/// own, built right after parsing.
+///
+/// Nodes created by rewriting the AST afterwards aren't part of the index.
+#[derive(Clone, Debug, Default)]
+pub struct NodeIndex {
+    entries: Vec<NodeEntry>,
+    /// Ids by the address of the nodes in the arena
+    ids: HashMap<usize, NodeId>,
+}
+
+impl NodeIndex {
+    pub fn build(body: SourceUnitList) -> Self {
+        let mut indexer = Indexer {
+            index: NodeIndex::default(),
+            parents: Vec::new(),
+        };
+
+        for unit in body.iter() {
+            indexer.visit_source_unit(*unit);
+        }
+
+        indexer.index
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entry(&self, id: NodeId) -> &NodeEntry {
+        &self.entries[id.0 as usize]
+    }
+
+    pub fn kind(&self, id: NodeId) -> NodeKind {
+        self.entry(id).kind
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.entry(id).parent
+    }
+
+    /// Id of a node of the indexed tree.
+    pub fn id<T>(&self, node: Node<T>) -> Option<NodeId> {
+        self.ids.get(&address(&node)).copied()
+    }
+
+    /// Parents of the node, innermost first.
+    pub fn ancestors(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        std::iter::successors(self.parent(id), move |&id| self.parent(id))
+    }
+
+    /// The innermost ancestor of the node of the given `kind`, like the function
+    /// enclosing an expression.
+    pub fn enclosing(&self, id: NodeId, kind: NodeKind) -> Option<NodeId> {
+        self.ancestors(id).find(|&ancestor| self.kind(ancestor) == kind)
+    }
+
+    /// The innermost node spanning the byte `offset`.
+    pub fn at_offset(&self, offset: u32) -> Option<NodeId> {
+        // Spans of siblings don't overlap, so the nodes spanning the offset are all
+        // ancestors of the innermost one, which is numbered last
+        self.entries
+            .iter()
+            .rposition(|entry| entry.start <= offset && offset < entry.end)
+            .map(|index| NodeId(index as u32))
+    }
+}
+
+fn address<T>(node: &Node<T>) -> usize {
+    &**node as *const NodeInner<T> as usize
+}
+
+struct Indexer {
+    index: NodeIndex,
+    parents: Vec<NodeId>,
+}
+
+impl Indexer {
+    /// Adds the node as a child of the current parent, unless it has already been added.
+    fn enter<T>(&mut self, node: Node<T>, kind: NodeKind) -> bool {
+        let id = NodeId(self.index.entries.len() as u32);
+
+        if self.index.ids.contains_key(&address(&node)) {
+            return false;
+        }
+
+        self.index.ids.insert(address(&node), id);
+
+        self.index.entries.push(NodeEntry {
+            parent: self.parents.last().copied(),
+            kind,
+            start: node.start,
+            end: node.end,
+        });
+        self.parents.push(id);
+
+        true
+    }
+
+    fn leave(&mut self) {
+        self.parents.pop();
+    }
+}
+
+impl<'ast> Visitor<'ast> for Indexer {
+    fn visit_source_unit(&mut self, unit: SourceUnitNode<'ast>) {
+        let kind = match unit.value {
+            SourceUnit::PragmaDirective(_) => NodeKind::PragmaDirective,
+            SourceUnit::ImportDirective(_) => NodeKind::ImportDirective,
+            SourceUnit::ContractDefinition(_) => NodeKind::ContractDefinition,
+            SourceUnit::StructDefinition(_) => NodeKind::StructDefinition,
+            SourceUnit::EnumDefinition(_) => NodeKind::EnumDefinition,
+            SourceUnit::FunctionDefinition(_) => NodeKind::FunctionDefinition,
+            SourceUnit::ErrorDefinition(_) => NodeKind::ErrorDefinition,
+            SourceUnit::ConstantDeclaration(_) => NodeKind::ConstantDeclaration,
+            SourceUnit::UserDefinedValueTypeDefinition(_) => {
+                NodeKind::UserDefinedValueTypeDefinition
+            }
+            SourceUnit::UsingForDeclaration(_) => NodeKind::UsingForDeclaration,
+            SourceUnit::Unknown(_) => NodeKind::Unknown,
+        };
+
+        if self.enter(unit, kind) {
+            walk_source_unit(self, unit);
+            self.leave();
+        }
+    }
+
+    fn visit_contract_part(&mut self, part: ContractPartNode<'ast>) {
+        let kind = match part.value {
+            ContractPart::StateVariableDeclaration(_) => NodeKind::StateVariableDeclaration,
+            ContractPart::UsingForDeclaration(_) => NodeKind::UsingForDeclaration,
+            ContractPart::StructDefinition(_) => NodeKind::StructDefinition,
+            ContractPart::ModifierDefinition(_) => NodeKind::ModifierDefinition,
+            ContractPart::FunctionDefinition(_) => NodeKind::FunctionDefinition,
+            ContractPart::EventDefinition(_) => NodeKind::EventDefinition,
+            ContractPart::EnumDefinition(_) => NodeKind::EnumDefinition,
+            ContractPart::ErrorDefinition(_) => NodeKind::ErrorDefinition,
+            ContractPart::UserDefinedValueTypeDefinition(_) => {
+                NodeKind::UserDefinedValueTypeDefinition
+            }
+            ContractPart::Unknown(_) => NodeKind::Unknown,
+        };
+
+        if self.enter(part, kind) {
+            walk_contract_part(self, part);
+            self.leave();
+        }
+    }
+
+    fn visit_using_for_function(&mut self, function: Node<'ast, UsingForFunction<'ast>>) {
+        if self.enter(function, NodeKind::UsingForFunction) {
+            walk_using_for_function(self, function);
+            self.leave();
+        }
+    }
+
+    fn visit_indexed_parameter(&mut self, parameter: Node<'ast, IndexedParameter<'ast>>) {
+        if self.enter(parameter, NodeKind::IndexedParameter) {
+            walk_indexed_parameter(self, parameter);
+            self.leave();
+        }
+    }
+
+    fn visit_modifier_invocation(&mut self, invocation: Node<'ast, ModifierInvocation<'ast>>) {
+        if self.enter(invocation, NodeKind::ModifierInvocation) {
+            walk_modifier_invocation(self, invocation);
+            self.leave();
+        }
+    }
+
+    fn visit_parameter(&mut self, parameter: Node<'ast, Parameter<'ast>>) {
+        if self.enter(parameter, NodeKind::Parameter) {
+            walk_parameter(self, parameter);
+            self.leave();
+        }
+    }
+
+    fn visit_statement(&mut self, statement: StatementNode<'ast>) {
+        let kind = match statement.value {
+            Statement::Placeholder => NodeKind::Placeholder,
+            Statement::IfStatement(_) => NodeKind::IfStatement,
+            Statement::WhileStatement(_) => NodeKind::WhileStatement,
+            Statement::ForStatement(_) => NodeKind::ForStatement,
+            Statement::BlockStatement(_) => NodeKind::BlockStatement,
+            Statement::InlineAssemblyStatement(_) => NodeKind::InlineAssemblyStatement,
+            Statement::DoWhileStatement(_) => NodeKind::DoWhileStatement,
+            Statement::ContinueStatement => NodeKind::ContinueStatement,
+            Statement::BreakStatement => NodeKind::BreakStatement,
+            Statement::ReturnStatement(_) => NodeKind::ReturnStatement,
+            Statement::ThrowStatement => NodeKind::ThrowStatement,
+            Statement::EmitStatement(_) => NodeKind::EmitStatement,
+            Statement::RevertStatement(_) => NodeKind::RevertStatement,
+            Statement::UncheckedBlock(_) => NodeKind::UncheckedBlock,
+            Statement::TryStatement(_) => NodeKind::TryStatement,
+            Statement::VariableDefinitionStatement(_) => NodeKind::VariableDefinitionStatement,
+            Statement::InferredDefinitionStatement(_) => NodeKind::InferredDefinitionStatement,
+            Statement::ExpressionStatement(_) => NodeKind::ExpressionStatement,
+            Statement::Unknown(_) => NodeKind::Unknown,
+        };
+
+        if self.enter(statement, kind) {
+            walk_statement(self, statement);
+            self.leave();
+        }
+    }
+
+    fn visit_simple_statement(&mut self, statement: SimpleStatementNode<'ast>) {
+        let kind = match statement.value {
+            SimpleStatement::VariableDefinitionStatement(_) => {
+                NodeKind::VariableDefinitionStatement
+            }
+            SimpleStatement::InferredDefinitionStatement(_) => {
+                NodeKind::InferredDefinitionStatement
+            }
+            SimpleStatement::ExpressionStatement(_) => NodeKind::ExpressionStatement,
+        };
+
+        if self.enter(statement, kind) {
+            walk_simple_statement(self, statement);
+            self.leave();
+        }
+    }
+
+    fn visit_catch_clause(&mut self, clause: CatchClauseNode<'ast>) {
+        if self.enter(clause, NodeKind::CatchClause) {
+            walk_catch_clause(self, clause);
+            self.leave();
+        }
+    }
+
+    fn visit_type_name(&mut self, type_name: TypeNameNode<'ast>) {
+        let kind = match type_name.value {
+            TypeName::ElementaryTypeName(_) => NodeKind::ElementaryTypeName,
+            TypeName::UserDefinedTypeName(_) => NodeKind::UserDefinedTypeName,
+            TypeName::Mapping(_) => NodeKind::Mapping,
+            TypeName::ArrayTypeName(_) => NodeKind::ArrayTypeName,
+            TypeName::FunctionTypeName => NodeKind::FunctionTypeName,
+        };
+
+        if self.enter(type_name, kind) {
+            walk_type_name(self, type_name);
+            self.leave();
+        }
+    }
+
+    fn visit_variable_declaration(&mut self, declaration: VariableDeclarationNode<'ast>) {
+        if self.enter(declaration, NodeKind::VariableDeclaration) {
+            walk_variable_declaration(self, declaration);
+            self.leave();
+        }
+    }
+
+    fn visit_expression(&mut self, expression: ExpressionNode<'ast>) {
+        let kind = match expression.value {
+            Expression::ThisExpression => NodeKind::ThisExpression,
+            Expression::IdentifierExpression(_) => NodeKind::IdentifierExpression,
+            Expression::PrimitiveExpression(_) => NodeKind::PrimitiveExpression,
+            Expression::PrefixExpression(_) => NodeKind::PrefixExpression,
+            Expression::PostfixExpression(_) => NodeKind::PostfixExpression,
+            Expression::BinaryExpression(_) => NodeKind::BinaryExpression,
+            Expression::AssignmentExpression(_) => NodeKind::AssignmentExpression,
+            Expression::TupleExpression(_) => NodeKind::TupleExpression,
+            Expression::CallExpression(_) => NodeKind::CallExpression,
+            Expression::CallOptionsExpression(_) => NodeKind::CallOptionsExpression,
+            Expression::MemberAccessExpression(_) => NodeKind::MemberAccessExpression,
+            Expression::IndexAccessExpression(_) => NodeKind::IndexAccessExpression,
+            Expression::ConditionalExpression(_) => NodeKind::ConditionalExpression,
+            Expression::ElementaryTypeExpression(_) => NodeKind::ElementaryTypeExpression,
+            Expression::NewExpression(_) => NodeKind::NewExpression,
+            Expression::TypeExpression(_) => NodeKind::TypeExpression,
+        };
+
+        if self.enter(expression, kind) {
+            walk_expression(self, expression);
+            self.leave();
+        }
+    }
+
+    fn visit_named_argument(&mut self, argument: NamedArgumentNode<'ast>) {
+        if self.enter(argument, NodeKind::NamedArgument) {
+            walk_named_argument(self, argument);
+            self.leave();
+        }
+    }
+
+    fn visit_assembly_item(&mut self, item: AssemblyItemNode<'ast>) {
+        if self.enter(item, NodeKind::AssemblyItem) {
+            walk_assembly_item(self, item);
+            self.leave();
+        }
+    }
+
+    fn visit_assembly_expression(&mut self, expression: AssemblyExpressionNode<'ast>) {
+        if self.enter(expression, NodeKind::AssemblyExpression) {
+            walk_assembly_expression(self, expression);
+            self.leave();
+        }
+    }
+
+    fn visit_assembly_case(&mut self, case: AssemblyCaseNode<'ast>) {
+        if self.enter(case, NodeKind::AssemblyCase) {
+            walk_assembly_case(self, case);
+            self.leave();
+        }
+    }
+
+    fn visit_identifier(&mut self, identifier: IdentifierNode<'ast>) {
+        if self.enter(identifier, NodeKind::Identifier) {
+            self.leave();
+        }
+    }
+}
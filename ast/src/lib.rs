@@ -7,32 +7,63 @@ extern crate pretty_assertions;
 #[macro_use]
 mod impl_from;
 mod assembly;
+mod builder;
+#[cfg(feature = "serde")]
+mod compact;
 mod contract;
 mod expression;
+mod fold;
 mod function;
+mod index;
 mod node;
+pub mod owned;
+mod shift;
 mod source;
+mod source_map;
 mod statement;
+mod structural;
 mod type_name;
+mod visit;
 
+use std::cell::Cell;
 use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
 use toolshed::list::{List, UnsafeList};
 use toolshed::Arena;
 
 pub use self::assembly::*;
+pub use self::builder::*;
+#[cfg(feature = "serde")]
+pub use self::compact::*;
 pub use self::contract::*;
 pub use self::expression::*;
+pub use self::fold::*;
 pub use self::function::*;
+pub use self::index::*;
 pub use self::node::{Node, NodeInner, OptionalLocation};
+pub use self::owned::{IntoOwned, ToArena};
+pub use self::shift::*;
 pub use self::source::*;
+pub use self::source_map::*;
 pub use self::statement::*;
+pub use self::structural::*;
 pub use self::type_name::*;
+pub use self::visit::*;
 
 /// Useful for boolean flags that need location information via FlagNode,
 /// for example: `indexed` or `anonymous`.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Flag;
 
+/// Source the parser couldn't make sense of, skipped up to the next `;` or
+/// closing `}` so that the rest of the file can still be analyzed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Unknown<'ast> {
+    pub raw: &'ast str,
+}
+
 pub type Identifier<'ast> = &'ast str;
 pub type StringLiteral<'ast> = &'ast str;
 pub type VersionLiteral<'ast> = &'ast str;
@@ -44,34 +75,112 @@ pub type SourceUnitList<'ast> = NodeList<'ast, SourceUnit<'ast>>; // AST
 pub type IdentifierNode<'ast> = Node<'ast, Identifier<'ast>>;
 pub type IdentifierList<'ast> = NodeList<'ast, Identifier<'ast>>;
 pub type StringLiteralNode<'ast> = Node<'ast, StringLiteral<'ast>>;
+pub type StringLiteralList<'ast> = NodeList<'ast, StringLiteral<'ast>>;
 
 /// A Solidity source code parsed to an AST
 pub struct Program<'ast> {
     /// `SourceUnitList<'ast>` converted to an `UnsafeList` to deal with
     /// the fact that the `Arena` on which it lives is also in this struct.
-    body: UnsafeList,
+    body: Cell<UnsafeList>,
 
     /// `Arena` on which the entire AST is allocated.
     arena: Arena,
 
+    /// Ids, parents and spans of the parsed nodes.
+    index: NodeIndex,
+
+    /// The source the program was parsed from.
+    source: SourceMap,
+
+    file: FileId,
+    path: Option<PathBuf>,
+
     /// For lifetime safety :).
     _phantom: PhantomData<SourceUnitList<'ast>>,
 }
 
 impl<'ast> Program<'ast> {
     #[inline]
-    pub fn new(body: UnsafeList, arena: Arena) -> Self {
+    pub fn new(body: UnsafeList, arena: Arena, source: SourceMap) -> Self {
+        let index = NodeIndex::build(unsafe { body.into_list() });
+
         Program {
-            body,
+            body: Cell::new(body),
             arena,
+            index,
+            source,
+            file: FileId::default(),
+            path: None,
             _phantom: PhantomData,
         }
     }
 
+    pub fn with_file(mut self, file: FileId) -> Self {
+        self.file = file;
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
     /// Get the list of `SourceUnit`s.
     #[inline]
     pub fn body(&self) -> SourceUnitList<'ast> {
-        unsafe { self.body.into_list() }
+        unsafe { self.body.get().into_list() }
+    }
+
+    /// Index of the nodes as they were parsed.
+    #[inline]
+    pub fn index(&self) -> &NodeIndex {
+        &self.index
+    }
+
+    #[inline]
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    /// Path of the file the program was parsed from, if it was read from one.
+    #[inline]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    #[inline]
+    pub fn source(&self) -> &str {
+        self.source.source()
+    }
+
+    #[inline]
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source
+    }
+
+    /// Zero based line and column of a byte `offset` of the source.
+    #[inline]
+    pub fn line_col(&self, offset: u32) -> LineCol {
+        self.source.line_col(offset)
+    }
+
+    /// Source text of a node, `None` for nodes added by rewriting.
+    #[inline]
+    pub fn slice<T>(&self, node: &NodeInner<T>) -> Option<&str> {
+        self.source.slice(node)
+    }
+
+    /// Id assigned to a parsed node, `None` for nodes added by rewriting.
+    #[inline]
+    pub fn node_id<T>(&self, node: Node<'ast, T>) -> Option<NodeId> {
+        self.index.id(node)
+    }
+
+    /// Replaces the list of `SourceUnit`s, like with the result of a `Folder`. The
+    /// index keeps describing the parsed nodes.
+    #[inline]
+    pub fn set_body(&'ast self, body: SourceUnitList<'ast>) {
+        self.body.set(body.into_unsafe())
     }
 
     /// Get a reference to the `Arena` on which the AST is allocated.
@@ -79,4 +188,43 @@ impl<'ast> Program<'ast> {
     pub fn arena(&'ast self) -> &'ast Arena {
         &self.arena
     }
+
+    /// Builder allocating new nodes on the `Arena` of the program.
+    #[inline]
+    pub fn builder(&'ast self) -> AstBuilder<'ast> {
+        AstBuilder::new(&self.arena)
+    }
+
+    /// Builds the program of an edited `source` on the `Arena` of this one, so that
+    /// `build` can keep nodes of the current body instead of parsing them again. The
+    /// file and path carry over. `None` if `build` gives up, dropping the program.
+    ///
+    /// # Safety
+    ///
+    /// The `Arena` moves to the returned program, so the reference `build` gets is
+    /// only valid for the duration of the call. `build` must not let it, or anything
+    /// borrowing it, escape; nodes allocated on it are fine to return.
+    pub unsafe fn rebuild<F>(self, source: SourceMap, build: F) -> Option<Program<'ast>>
+    where
+        F: FnOnce(SourceUnitList<'ast>, &'ast Arena) -> Option<SourceUnitList<'ast>>,
+    {
+        // Nodes live in the chunks of the arena, which stay put as it moves to the new program
+        let arena: &'ast Arena = &*(&self.arena as *const Arena);
+        let body = build(self.body(), arena)?;
+
+        let mut program = Program::new(body.into_unsafe(), self.arena, source);
+
+        program.file = self.file;
+        program.path = self.path;
+
+        Some(program)
+    }
+}
+
+/// Serialized as the list of `SourceUnit`s.
+#[cfg(feature = "serde")]
+impl<'ast> serde::Serialize for Program<'ast> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.body().serialize(serializer)
+    }
 }
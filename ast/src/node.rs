@@ -33,17 +33,32 @@ pub struct Node<'ast, T: 'ast> {
 }
 
 #[derive(Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NodeInner<T> {
     pub start: u32,
     pub end: u32,
     pub value: T,
 }
 
+/// Span of nodes that weren't parsed from the source, so they have no original text.
+const SYNTHESIZED: u32 = u32::MAX;
+
 impl<T> NodeInner<T> {
     #[inline]
     pub fn new(start: u32, end: u32, value: T) -> Self {
         NodeInner { start, end, value }
     }
+
+    /// A node created while rewriting the AST rather than parsed.
+    #[inline]
+    pub fn synthesized(value: T) -> Self {
+        NodeInner::new(SYNTHESIZED, SYNTHESIZED, value)
+    }
+
+    #[inline]
+    pub fn is_synthesized(&self) -> bool {
+        self.start == SYNTHESIZED
+    }
 }
 
 impl<'ast, T: 'ast> Node<'ast, T> {
@@ -88,6 +103,14 @@ impl<'ast, T: 'ast + Debug> Debug for Node<'ast, T> {
     }
 }
 
+/// Serialized as the `NodeInner` it points to.
+#[cfg(feature = "serde")]
+impl<'ast, T: 'ast + serde::Serialize> serde::Serialize for Node<'ast, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.deref().serialize(serializer)
+    }
+}
+
 impl<T: Debug> Debug for NodeInner<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "({}:{}) ", self.start, self.end)?;
@@ -0,0 +1,1404 @@
+//! AST that owns its nodes instead of borrowing them from an `Arena`.
+//!
+//! The types mirror the ones of the crate root, with `Node`s held in an `Arc`, lists
+//! turned into `Vec`s and `&str`s into `String`s. It's `Send + Sync` and has no
+//! lifetime, so it can be cached or handed to other threads, while the arena form is
+//! still the faster one to build and walk. `IntoOwned` and `ToArena` convert between
+//! the two, keeping the spans of the nodes.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use toolshed::list::List;
+use toolshed::Arena;
+
+pub use crate::{
+    AssignmentOperator, BinaryOperator, ElementaryTypeName, EtherUnit, FileId, Flag,
+    FunctionVisibility, NodeInner, NumberUnit, PostfixOperator, PragmaVersion, PrefixOperator,
+    SourceMap, StateMutability, StateVariableVisibility, StorageLocation, TimeUnit,
+    UserDefinableOperator, VersionComparator, VersionOperator,
+};
+
+/// Conversion of the arena allocated AST to the owned one.
+pub trait IntoOwned {
+    type Owned;
+
+    fn into_owned(self) -> Self::Owned;
+}
+
+/// Conversion of the owned AST back to one allocated on `arena`.
+pub trait ToArena<'ast> {
+    type Arena;
+
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Arena;
+}
+
+pub type Node<T> = Arc<NodeInner<T>>;
+pub type NodeList<T> = Vec<Node<T>>;
+
+pub type Identifier = String;
+pub type StringLiteral = String;
+
+pub type FlagNode = Node<Flag>;
+pub type IdentifierNode = Node<Identifier>;
+pub type IdentifierList = NodeList<Identifier>;
+pub type StringLiteralNode = Node<StringLiteral>;
+pub type StringLiteralList = NodeList<StringLiteral>;
+pub type SourceUnitNode = Node<SourceUnit>;
+pub type SourceUnitList = NodeList<SourceUnit>;
+pub type VersionRangeList = NodeList<VersionRange>;
+pub type ContractPartNode = Node<ContractPart>;
+pub type ContractPartList = NodeList<ContractPart>;
+pub type IndexedParameterList = NodeList<IndexedParameter>;
+pub type UsingForFunctionList = NodeList<UsingForFunction>;
+pub type ParameterList = NodeList<Parameter>;
+pub type ModifierInvocationList = NodeList<ModifierInvocation>;
+pub type StatementNode = Node<Statement>;
+pub type StatementList = NodeList<Statement>;
+pub type SimpleStatementNode = Node<SimpleStatement>;
+pub type BlockNode = Node<Block>;
+pub type CatchClauseList = NodeList<CatchClause>;
+pub type ExpressionNode = Node<Expression>;
+pub type ExpressionList = NodeList<Expression>;
+pub type NamedArgumentList = NodeList<NamedArgument>;
+pub type TypeNameNode = Node<TypeName>;
+pub type ElementaryTypeNameNode = Node<ElementaryTypeName>;
+pub type VariableDeclarationNode = Node<VariableDeclaration>;
+pub type VariableDeclarationList = NodeList<VariableDeclaration>;
+pub type AssemblyItemList = NodeList<AssemblyItem>;
+pub type AssemblyExpressionNode = Node<AssemblyExpression>;
+pub type AssemblyExpressionList = NodeList<AssemblyExpression>;
+pub type AssemblyCaseList = NodeList<AssemblyCase>;
+pub type InlineAssemblyBlockNode = Node<InlineAssemblyBlock>;
+
+/// A parsed program, detached from the `Arena` it was parsed on.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Program {
+    pub body: SourceUnitList,
+    pub source: SourceMap,
+    pub file: FileId,
+    pub path: Option<PathBuf>,
+}
+
+impl Program {
+    /// Allocates the program on a new `Arena`.
+    pub fn to_program<'ast>(&self) -> crate::Program<'ast> {
+        let arena = Arena::new();
+        let body = self.body.to_arena(&arena).into_unsafe();
+        let program = crate::Program::new(body, arena, self.source.clone()).with_file(self.file);
+
+        match &self.path {
+            Some(path) => program.with_path(path),
+            None => program,
+        }
+    }
+}
+
+impl<'ast> crate::Program<'ast> {
+    /// Copies the AST out of the `Arena`.
+    pub fn to_owned_ast(&self) -> Program {
+        Program {
+            body: self.body().into_owned(),
+            source: self.source_map().clone(),
+            file: self.file(),
+            path: self.path().map(PathBuf::from),
+        }
+    }
+}
+
+impl<'ast, T: Copy + IntoOwned> IntoOwned for crate::Node<'ast, T> {
+    type Owned = Node<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        Arc::new(NodeInner::new(
+            self.start,
+            self.end,
+            self.value.into_owned(),
+        ))
+    }
+}
+
+impl<'ast, T: ToArena<'ast>> ToArena<'ast> for Node<T>
+where
+    T::Arena: Copy + 'ast,
+{
+    type Arena = crate::Node<'ast, T::Arena>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Arena {
+        let value = self.value.to_arena(arena);
+
+        crate::Node::new(arena.alloc(NodeInner::new(self.start, self.end, value)))
+    }
+}
+
+impl<'ast, T: Copy + IntoOwned> IntoOwned for List<'ast, T> {
+    type Owned = Vec<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.iter().map(|item| item.into_owned()).collect()
+    }
+}
+
+impl<'ast, T: ToArena<'ast>> ToArena<'ast> for Vec<T>
+where
+    T::Arena: Copy + 'ast,
+{
+    type Arena = List<'ast, T::Arena>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Arena {
+        List::from_iter(arena, self.iter().map(|item| item.to_arena(arena)))
+    }
+}
+
+impl<T: IntoOwned> IntoOwned for Option<T> {
+    type Owned = Option<T::Owned>;
+
+    fn into_owned(self) -> Self::Owned {
+        self.map(IntoOwned::into_owned)
+    }
+}
+
+impl<'ast, T: ToArena<'ast>> ToArena<'ast> for Option<T> {
+    type Arena = Option<T::Arena>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> Self::Arena {
+        self.as_ref().map(|value| value.to_arena(arena))
+    }
+}
+
+impl IntoOwned for &str {
+    type Owned = String;
+
+    fn into_owned(self) -> String {
+        self.to_string()
+    }
+}
+
+impl<'ast> ToArena<'ast> for String {
+    type Arena = &'ast str;
+
+    fn to_arena(&self, arena: &'ast Arena) -> &'ast str {
+        arena.alloc_str(self)
+    }
+}
+
+/// Types without references to the `Arena` are shared by both ASTs.
+macro_rules! impl_shared {
+    ($( $type:ty ),* $(,)?) => {$(
+        impl IntoOwned for $type {
+            type Owned = $type;
+
+            #[inline]
+            fn into_owned(self) -> $type {
+                self
+            }
+        }
+
+        impl<'ast> ToArena<'ast> for $type {
+            type Arena = $type;
+
+            #[inline]
+            fn to_arena(&self, _: &'ast Arena) -> $type {
+                *self
+            }
+        }
+    )*};
+}
+
+impl_shared! {
+    bool,
+    Flag,
+    VersionComparator,
+    StateVariableVisibility,
+    UserDefinableOperator,
+    FunctionVisibility,
+    StateMutability,
+    NumberUnit,
+    PrefixOperator,
+    PostfixOperator,
+    BinaryOperator,
+    AssignmentOperator,
+    StorageLocation,
+    ElementaryTypeName,
+}
+
+/// Declares owned structs converted field by field from the structs of the same
+/// name in the crate root. Enums are written out below.
+macro_rules! owned_structs {
+    ($( $name:ident { $( $field:ident: $type:ty, )* } )*) => {$(
+        #[derive(Clone, Debug, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+        pub struct $name {
+            $( pub $field: $type, )*
+        }
+
+        impl<'ast> IntoOwned for crate::$name<'ast> {
+            type Owned = $name;
+
+            fn into_owned(self) -> $name {
+                $name {
+                    $( $field: self.$field.into_owned(), )*
+                }
+            }
+        }
+
+        impl<'ast> ToArena<'ast> for $name {
+            type Arena = crate::$name<'ast>;
+
+            fn to_arena(&self, arena: &'ast Arena) -> crate::$name<'ast> {
+                crate::$name {
+                    $( $field: self.$field.to_arena(arena), )*
+                }
+            }
+        }
+    )*};
+}
+
+// Source units
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SourceUnit {
+    PragmaDirective(PragmaDirective),
+    ImportDirective(ImportDirective),
+    ContractDefinition(ContractDefinition),
+    StructDefinition(StructDefinition),
+    EnumDefinition(EnumDefinition),
+    FunctionDefinition(FunctionDefinition),
+    ErrorDefinition(ErrorDefinition),
+    ConstantDeclaration(ConstantDeclaration),
+    UserDefinedValueTypeDefinition(UserDefinedValueTypeDefinition),
+    UsingForDeclaration(UsingForDeclaration),
+    Unknown(Unknown),
+}
+
+impl<'ast> IntoOwned for crate::SourceUnit<'ast> {
+    type Owned = SourceUnit;
+
+    fn into_owned(self) -> SourceUnit {
+        match self {
+            crate::SourceUnit::PragmaDirective(value) => {
+                SourceUnit::PragmaDirective(value.into_owned())
+            }
+            crate::SourceUnit::ImportDirective(value) => {
+                SourceUnit::ImportDirective(value.into_owned())
+            }
+            crate::SourceUnit::ContractDefinition(value) => {
+                SourceUnit::ContractDefinition(value.into_owned())
+            }
+            crate::SourceUnit::StructDefinition(value) => {
+                SourceUnit::StructDefinition(value.into_owned())
+            }
+            crate::SourceUnit::EnumDefinition(value) => {
+                SourceUnit::EnumDefinition(value.into_owned())
+            }
+            crate::SourceUnit::FunctionDefinition(value) => {
+                SourceUnit::FunctionDefinition(value.into_owned())
+            }
+            crate::SourceUnit::ErrorDefinition(value) => {
+                SourceUnit::ErrorDefinition(value.into_owned())
+            }
+            crate::SourceUnit::ConstantDeclaration(value) => {
+                SourceUnit::ConstantDeclaration(value.into_owned())
+            }
+            crate::SourceUnit::UserDefinedValueTypeDefinition(value) => {
+                SourceUnit::UserDefinedValueTypeDefinition(value.into_owned())
+            }
+            crate::SourceUnit::UsingForDeclaration(value) => {
+                SourceUnit::UsingForDeclaration(value.into_owned())
+            }
+            crate::SourceUnit::Unknown(value) => SourceUnit::Unknown(value.into_owned()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for SourceUnit {
+    type Arena = crate::SourceUnit<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::SourceUnit<'ast> {
+        match self {
+            SourceUnit::PragmaDirective(value) => {
+                crate::SourceUnit::PragmaDirective(value.to_arena(arena))
+            }
+            SourceUnit::ImportDirective(value) => {
+                crate::SourceUnit::ImportDirective(value.to_arena(arena))
+            }
+            SourceUnit::ContractDefinition(value) => {
+                crate::SourceUnit::ContractDefinition(value.to_arena(arena))
+            }
+            SourceUnit::StructDefinition(value) => {
+                crate::SourceUnit::StructDefinition(value.to_arena(arena))
+            }
+            SourceUnit::EnumDefinition(value) => {
+                crate::SourceUnit::EnumDefinition(value.to_arena(arena))
+            }
+            SourceUnit::FunctionDefinition(value) => {
+                crate::SourceUnit::FunctionDefinition(value.to_arena(arena))
+            }
+            SourceUnit::ErrorDefinition(value) => {
+                crate::SourceUnit::ErrorDefinition(value.to_arena(arena))
+            }
+            SourceUnit::ConstantDeclaration(value) => {
+                crate::SourceUnit::ConstantDeclaration(value.to_arena(arena))
+            }
+            SourceUnit::UserDefinedValueTypeDefinition(value) => {
+                crate::SourceUnit::UserDefinedValueTypeDefinition(value.to_arena(arena))
+            }
+            SourceUnit::UsingForDeclaration(value) => {
+                crate::SourceUnit::UsingForDeclaration(value.to_arena(arena))
+            }
+            SourceUnit::Unknown(value) => crate::SourceUnit::Unknown(value.to_arena(arena)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PragmaDirective {
+    Solidity { ranges: VersionRangeList },
+    Abicoder { version: IdentifierNode },
+    Experimental { feature: IdentifierNode },
+}
+
+impl<'ast> IntoOwned for crate::PragmaDirective<'ast> {
+    type Owned = PragmaDirective;
+
+    fn into_owned(self) -> PragmaDirective {
+        match self {
+            crate::PragmaDirective::Solidity { ranges } => PragmaDirective::Solidity {
+                ranges: ranges.into_owned(),
+            },
+            crate::PragmaDirective::Abicoder { version } => PragmaDirective::Abicoder {
+                version: version.into_owned(),
+            },
+            crate::PragmaDirective::Experimental { feature } => PragmaDirective::Experimental {
+                feature: feature.into_owned(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for PragmaDirective {
+    type Arena = crate::PragmaDirective<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::PragmaDirective<'ast> {
+        match self {
+            PragmaDirective::Solidity { ranges } => crate::PragmaDirective::Solidity {
+                ranges: ranges.to_arena(arena),
+            },
+            PragmaDirective::Abicoder { version } => crate::PragmaDirective::Abicoder {
+                version: version.to_arena(arena),
+            },
+            PragmaDirective::Experimental { feature } => crate::PragmaDirective::Experimental {
+                feature: feature.to_arena(arena),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ImportDirective {
+    Global {
+        source: StringLiteralNode,
+        alias: Option<IdentifierNode>,
+    },
+    From {
+        symbol: Option<IdentifierNode>,
+        alias: Option<IdentifierNode>,
+        source: StringLiteralNode,
+    },
+    ManyFrom {
+        imports: NodeList<Import>,
+        source: StringLiteralNode,
+    },
+}
+
+impl<'ast> IntoOwned for crate::ImportDirective<'ast> {
+    type Owned = ImportDirective;
+
+    fn into_owned(self) -> ImportDirective {
+        match self {
+            crate::ImportDirective::Global { source, alias } => ImportDirective::Global {
+                source: source.into_owned(),
+                alias: alias.into_owned(),
+            },
+            crate::ImportDirective::From {
+                symbol,
+                alias,
+                source,
+            } => ImportDirective::From {
+                symbol: symbol.into_owned(),
+                alias: alias.into_owned(),
+                source: source.into_owned(),
+            },
+            crate::ImportDirective::ManyFrom { imports, source } => ImportDirective::ManyFrom {
+                imports: imports.into_owned(),
+                source: source.into_owned(),
+            },
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for ImportDirective {
+    type Arena = crate::ImportDirective<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::ImportDirective<'ast> {
+        match self {
+            ImportDirective::Global { source, alias } => crate::ImportDirective::Global {
+                source: source.to_arena(arena),
+                alias: alias.to_arena(arena),
+            },
+            ImportDirective::From {
+                symbol,
+                alias,
+                source,
+            } => crate::ImportDirective::From {
+                symbol: symbol.to_arena(arena),
+                alias: alias.to_arena(arena),
+                source: source.to_arena(arena),
+            },
+            ImportDirective::ManyFrom { imports, source } => crate::ImportDirective::ManyFrom {
+                imports: imports.to_arena(arena),
+                source: source.to_arena(arena),
+            },
+        }
+    }
+}
+
+owned_structs! {
+    VersionRange {
+        comparators: NodeList<VersionComparator>,
+    }
+    ConstantDeclaration {
+        type_name: TypeNameNode,
+        name: IdentifierNode,
+        init: ExpressionNode,
+    }
+    Import {
+        symbol: IdentifierNode,
+        alias: Option<IdentifierNode>,
+    }
+}
+
+// Contracts
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum ContractPart {
+    StateVariableDeclaration(StateVariableDeclaration),
+    UsingForDeclaration(UsingForDeclaration),
+    StructDefinition(StructDefinition),
+    ModifierDefinition(ModifierDefinition),
+    FunctionDefinition(FunctionDefinition),
+    EventDefinition(EventDefinition),
+    EnumDefinition(EnumDefinition),
+    ErrorDefinition(ErrorDefinition),
+    UserDefinedValueTypeDefinition(UserDefinedValueTypeDefinition),
+    Unknown(Unknown),
+}
+
+impl<'ast> IntoOwned for crate::ContractPart<'ast> {
+    type Owned = ContractPart;
+
+    fn into_owned(self) -> ContractPart {
+        match self {
+            crate::ContractPart::StateVariableDeclaration(value) => {
+                ContractPart::StateVariableDeclaration(value.into_owned())
+            }
+            crate::ContractPart::UsingForDeclaration(value) => {
+                ContractPart::UsingForDeclaration(value.into_owned())
+            }
+            crate::ContractPart::StructDefinition(value) => {
+                ContractPart::StructDefinition(value.into_owned())
+            }
+            crate::ContractPart::ModifierDefinition(value) => {
+                ContractPart::ModifierDefinition(value.into_owned())
+            }
+            crate::ContractPart::FunctionDefinition(value) => {
+                ContractPart::FunctionDefinition(value.into_owned())
+            }
+            crate::ContractPart::EventDefinition(value) => {
+                ContractPart::EventDefinition(value.into_owned())
+            }
+            crate::ContractPart::EnumDefinition(value) => {
+                ContractPart::EnumDefinition(value.into_owned())
+            }
+            crate::ContractPart::ErrorDefinition(value) => {
+                ContractPart::ErrorDefinition(value.into_owned())
+            }
+            crate::ContractPart::UserDefinedValueTypeDefinition(value) => {
+                ContractPart::UserDefinedValueTypeDefinition(value.into_owned())
+            }
+            crate::ContractPart::Unknown(value) => ContractPart::Unknown(value.into_owned()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for ContractPart {
+    type Arena = crate::ContractPart<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::ContractPart<'ast> {
+        match self {
+            ContractPart::StateVariableDeclaration(value) => {
+                crate::ContractPart::StateVariableDeclaration(value.to_arena(arena))
+            }
+            ContractPart::UsingForDeclaration(value) => {
+                crate::ContractPart::UsingForDeclaration(value.to_arena(arena))
+            }
+            ContractPart::StructDefinition(value) => {
+                crate::ContractPart::StructDefinition(value.to_arena(arena))
+            }
+            ContractPart::ModifierDefinition(value) => {
+                crate::ContractPart::ModifierDefinition(value.to_arena(arena))
+            }
+            ContractPart::FunctionDefinition(value) => {
+                crate::ContractPart::FunctionDefinition(value.to_arena(arena))
+            }
+            ContractPart::EventDefinition(value) => {
+                crate::ContractPart::EventDefinition(value.to_arena(arena))
+            }
+            ContractPart::EnumDefinition(value) => {
+                crate::ContractPart::EnumDefinition(value.to_arena(arena))
+            }
+            ContractPart::ErrorDefinition(value) => {
+                crate::ContractPart::ErrorDefinition(value.to_arena(arena))
+            }
+            ContractPart::UserDefinedValueTypeDefinition(value) => {
+                crate::ContractPart::UserDefinedValueTypeDefinition(value.to_arena(arena))
+            }
+            ContractPart::Unknown(value) => crate::ContractPart::Unknown(value.to_arena(arena)),
+        }
+    }
+}
+
+owned_structs! {
+    ContractDefinition {
+        name: IdentifierNode,
+        inherits: IdentifierList,
+        body: ContractPartList,
+    }
+    StateVariableDeclaration {
+        type_name: TypeNameNode,
+        visibility: Option<Node<StateVariableVisibility>>,
+        constant: Option<FlagNode>,
+        name: IdentifierNode,
+        init: Option<ExpressionNode>,
+    }
+    UsingForDeclaration {
+        id: Option<IdentifierNode>,
+        functions: UsingForFunctionList,
+        type_name: Option<TypeNameNode>,
+        global: Option<FlagNode>,
+    }
+    UsingForFunction {
+        id: IdentifierNode,
+        operator: Option<Node<UserDefinableOperator>>,
+    }
+    StructDefinition {
+        name: IdentifierNode,
+        body: VariableDeclarationList,
+    }
+    ModifierDefinition {
+        name: IdentifierNode,
+        params: ParameterList,
+        block: BlockNode,
+    }
+    EventDefinition {
+        anonymous: Option<FlagNode>,
+        name: IdentifierNode,
+        params: IndexedParameterList,
+    }
+    IndexedParameter {
+        type_name: TypeNameNode,
+        indexed: Option<FlagNode>,
+        name: Option<IdentifierNode>,
+    }
+    EnumDefinition {
+        name: IdentifierNode,
+        variants: IdentifierList,
+    }
+    UserDefinedValueTypeDefinition {
+        name: IdentifierNode,
+        underlying: ElementaryTypeNameNode,
+    }
+    ErrorDefinition {
+        name: IdentifierNode,
+        params: ParameterList,
+    }
+}
+
+// Functions
+
+owned_structs! {
+    FunctionDefinition {
+        name: Option<IdentifierNode>,
+        params: ParameterList,
+        visibility: Option<Node<FunctionVisibility>>,
+        mutability: Option<Node<StateMutability>>,
+        modifiers: ModifierInvocationList,
+        returns: ParameterList,
+        block: Option<BlockNode>,
+    }
+    ModifierInvocation {
+        id: IdentifierNode,
+        arguments: ExpressionList,
+    }
+    Parameter {
+        type_name: TypeNameNode,
+        location: Option<Node<StorageLocation>>,
+        name: Option<IdentifierNode>,
+    }
+}
+
+// Statements
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Statement {
+    Placeholder,
+    IfStatement(IfStatement),
+    WhileStatement(WhileStatement),
+    ForStatement(ForStatement),
+    BlockStatement(Block),
+    InlineAssemblyStatement(InlineAssemblyStatement),
+    DoWhileStatement(DoWhileStatement),
+    ContinueStatement,
+    BreakStatement,
+    ReturnStatement(ReturnStatement),
+    ThrowStatement,
+    EmitStatement(EmitStatement),
+    RevertStatement(RevertStatement),
+    UncheckedBlock(UncheckedBlock),
+    TryStatement(TryStatement),
+    VariableDefinitionStatement(VariableDefinitionStatement),
+    InferredDefinitionStatement(InferredDefinitionStatement),
+    ExpressionStatement(ExpressionNode),
+    Unknown(Unknown),
+}
+
+impl<'ast> IntoOwned for crate::Statement<'ast> {
+    type Owned = Statement;
+
+    fn into_owned(self) -> Statement {
+        match self {
+            crate::Statement::Placeholder => Statement::Placeholder,
+            crate::Statement::IfStatement(value) => Statement::IfStatement(value.into_owned()),
+            crate::Statement::WhileStatement(value) => {
+                Statement::WhileStatement(value.into_owned())
+            }
+            crate::Statement::ForStatement(value) => Statement::ForStatement(value.into_owned()),
+            crate::Statement::BlockStatement(value) => {
+                Statement::BlockStatement(value.into_owned())
+            }
+            crate::Statement::InlineAssemblyStatement(value) => {
+                Statement::InlineAssemblyStatement(value.into_owned())
+            }
+            crate::Statement::DoWhileStatement(value) => {
+                Statement::DoWhileStatement(value.into_owned())
+            }
+            crate::Statement::ContinueStatement => Statement::ContinueStatement,
+            crate::Statement::BreakStatement => Statement::BreakStatement,
+            crate::Statement::ReturnStatement(value) => {
+                Statement::ReturnStatement(value.into_owned())
+            }
+            crate::Statement::ThrowStatement => Statement::ThrowStatement,
+            crate::Statement::EmitStatement(value) => Statement::EmitStatement(value.into_owned()),
+            crate::Statement::RevertStatement(value) => {
+                Statement::RevertStatement(value.into_owned())
+            }
+            crate::Statement::UncheckedBlock(value) => {
+                Statement::UncheckedBlock(value.into_owned())
+            }
+            crate::Statement::TryStatement(value) => Statement::TryStatement(value.into_owned()),
+            crate::Statement::VariableDefinitionStatement(value) => {
+                Statement::VariableDefinitionStatement(value.into_owned())
+            }
+            crate::Statement::InferredDefinitionStatement(value) => {
+                Statement::InferredDefinitionStatement(value.into_owned())
+            }
+            crate::Statement::ExpressionStatement(value) => {
+                Statement::ExpressionStatement(value.into_owned())
+            }
+            crate::Statement::Unknown(value) => Statement::Unknown(value.into_owned()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Statement {
+    type Arena = crate::Statement<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::Statement<'ast> {
+        match self {
+            Statement::Placeholder => crate::Statement::Placeholder,
+            Statement::IfStatement(value) => crate::Statement::IfStatement(value.to_arena(arena)),
+            Statement::WhileStatement(value) => {
+                crate::Statement::WhileStatement(value.to_arena(arena))
+            }
+            Statement::ForStatement(value) => crate::Statement::ForStatement(value.to_arena(arena)),
+            Statement::BlockStatement(value) => {
+                crate::Statement::BlockStatement(value.to_arena(arena))
+            }
+            Statement::InlineAssemblyStatement(value) => {
+                crate::Statement::InlineAssemblyStatement(value.to_arena(arena))
+            }
+            Statement::DoWhileStatement(value) => {
+                crate::Statement::DoWhileStatement(value.to_arena(arena))
+            }
+            Statement::ContinueStatement => crate::Statement::ContinueStatement,
+            Statement::BreakStatement => crate::Statement::BreakStatement,
+            Statement::ReturnStatement(value) => {
+                crate::Statement::ReturnStatement(value.to_arena(arena))
+            }
+            Statement::ThrowStatement => crate::Statement::ThrowStatement,
+            Statement::EmitStatement(value) => {
+                crate::Statement::EmitStatement(value.to_arena(arena))
+            }
+            Statement::RevertStatement(value) => {
+                crate::Statement::RevertStatement(value.to_arena(arena))
+            }
+            Statement::UncheckedBlock(value) => {
+                crate::Statement::UncheckedBlock(value.to_arena(arena))
+            }
+            Statement::TryStatement(value) => crate::Statement::TryStatement(value.to_arena(arena)),
+            Statement::VariableDefinitionStatement(value) => {
+                crate::Statement::VariableDefinitionStatement(value.to_arena(arena))
+            }
+            Statement::InferredDefinitionStatement(value) => {
+                crate::Statement::InferredDefinitionStatement(value.to_arena(arena))
+            }
+            Statement::ExpressionStatement(value) => {
+                crate::Statement::ExpressionStatement(value.to_arena(arena))
+            }
+            Statement::Unknown(value) => crate::Statement::Unknown(value.to_arena(arena)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum SimpleStatement {
+    VariableDefinitionStatement(VariableDefinitionStatement),
+    InferredDefinitionStatement(InferredDefinitionStatement),
+    ExpressionStatement(ExpressionNode),
+}
+
+impl<'ast> IntoOwned for crate::SimpleStatement<'ast> {
+    type Owned = SimpleStatement;
+
+    fn into_owned(self) -> SimpleStatement {
+        match self {
+            crate::SimpleStatement::VariableDefinitionStatement(value) => {
+                SimpleStatement::VariableDefinitionStatement(value.into_owned())
+            }
+            crate::SimpleStatement::InferredDefinitionStatement(value) => {
+                SimpleStatement::InferredDefinitionStatement(value.into_owned())
+            }
+            crate::SimpleStatement::ExpressionStatement(value) => {
+                SimpleStatement::ExpressionStatement(value.into_owned())
+            }
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for SimpleStatement {
+    type Arena = crate::SimpleStatement<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::SimpleStatement<'ast> {
+        match self {
+            SimpleStatement::VariableDefinitionStatement(value) => {
+                crate::SimpleStatement::VariableDefinitionStatement(value.to_arena(arena))
+            }
+            SimpleStatement::InferredDefinitionStatement(value) => {
+                crate::SimpleStatement::InferredDefinitionStatement(value.to_arena(arena))
+            }
+            SimpleStatement::ExpressionStatement(value) => {
+                crate::SimpleStatement::ExpressionStatement(value.to_arena(arena))
+            }
+        }
+    }
+}
+
+owned_structs! {
+    IfStatement {
+        test: ExpressionNode,
+        consequent: StatementNode,
+        alternate: Option<StatementNode>,
+    }
+    WhileStatement {
+        test: ExpressionNode,
+        body: StatementNode,
+    }
+    ForStatement {
+        init: Option<SimpleStatementNode>,
+        test: Option<ExpressionNode>,
+        update: Option<ExpressionNode>,
+        body: StatementNode,
+    }
+    Block {
+        body: StatementList,
+    }
+    InlineAssemblyStatement {
+        string: Option<StringLiteralNode>,
+        flags: StringLiteralList,
+        block: InlineAssemblyBlockNode,
+    }
+    DoWhileStatement {
+        body: StatementNode,
+        test: ExpressionNode,
+    }
+    ReturnStatement {
+        value: Option<ExpressionNode>,
+    }
+    EmitStatement {
+        event: ExpressionNode,
+    }
+    RevertStatement {
+        error: ExpressionNode,
+    }
+    UncheckedBlock {
+        block: BlockNode,
+    }
+    TryStatement {
+        call: ExpressionNode,
+        returns: ParameterList,
+        body: BlockNode,
+        clauses: CatchClauseList,
+    }
+    CatchClause {
+        id: Option<IdentifierNode>,
+        params: ParameterList,
+        body: BlockNode,
+    }
+    VariableDefinitionStatement {
+        declaration: VariableDeclarationNode,
+        init: Option<ExpressionNode>,
+    }
+    InferredDefinitionStatement {
+        ids: Vec<Option<IdentifierNode>>,
+        init: ExpressionNode,
+    }
+}
+
+// Expressions
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Expression {
+    ThisExpression,
+    IdentifierExpression(Identifier),
+    PrimitiveExpression(Primitive),
+    PrefixExpression(PrefixExpression),
+    PostfixExpression(PostfixExpression),
+    BinaryExpression(BinaryExpression),
+    AssignmentExpression(AssignmentExpression),
+    TupleExpression(TupleExpression),
+    CallExpression(CallExpression),
+    CallOptionsExpression(CallOptionsExpression),
+    MemberAccessExpression(MemberAccessExpression),
+    IndexAccessExpression(IndexAccessExpression),
+    ConditionalExpression(ConditionalExpression),
+    ElementaryTypeExpression(ElementaryTypeName),
+    NewExpression(NewExpression),
+    TypeExpression(TypeExpression),
+}
+
+impl<'ast> IntoOwned for crate::Expression<'ast> {
+    type Owned = Expression;
+
+    fn into_owned(self) -> Expression {
+        match self {
+            crate::Expression::ThisExpression => Expression::ThisExpression,
+            crate::Expression::IdentifierExpression(value) => {
+                Expression::IdentifierExpression(value.into_owned())
+            }
+            crate::Expression::PrimitiveExpression(value) => {
+                Expression::PrimitiveExpression(value.into_owned())
+            }
+            crate::Expression::PrefixExpression(value) => {
+                Expression::PrefixExpression(value.into_owned())
+            }
+            crate::Expression::PostfixExpression(value) => {
+                Expression::PostfixExpression(value.into_owned())
+            }
+            crate::Expression::BinaryExpression(value) => {
+                Expression::BinaryExpression(value.into_owned())
+            }
+            crate::Expression::AssignmentExpression(value) => {
+                Expression::AssignmentExpression(value.into_owned())
+            }
+            crate::Expression::TupleExpression(value) => {
+                Expression::TupleExpression(value.into_owned())
+            }
+            crate::Expression::CallExpression(value) => {
+                Expression::CallExpression(value.into_owned())
+            }
+            crate::Expression::CallOptionsExpression(value) => {
+                Expression::CallOptionsExpression(value.into_owned())
+            }
+            crate::Expression::MemberAccessExpression(value) => {
+                Expression::MemberAccessExpression(value.into_owned())
+            }
+            crate::Expression::IndexAccessExpression(value) => {
+                Expression::IndexAccessExpression(value.into_owned())
+            }
+            crate::Expression::ConditionalExpression(value) => {
+                Expression::ConditionalExpression(value.into_owned())
+            }
+            crate::Expression::ElementaryTypeExpression(value) => {
+                Expression::ElementaryTypeExpression(value.into_owned())
+            }
+            crate::Expression::NewExpression(value) => {
+                Expression::NewExpression(value.into_owned())
+            }
+            crate::Expression::TypeExpression(value) => {
+                Expression::TypeExpression(value.into_owned())
+            }
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Expression {
+    type Arena = crate::Expression<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::Expression<'ast> {
+        match self {
+            Expression::ThisExpression => crate::Expression::ThisExpression,
+            Expression::IdentifierExpression(value) => {
+                crate::Expression::IdentifierExpression(value.to_arena(arena))
+            }
+            Expression::PrimitiveExpression(value) => {
+                crate::Expression::PrimitiveExpression(value.to_arena(arena))
+            }
+            Expression::PrefixExpression(value) => {
+                crate::Expression::PrefixExpression(value.to_arena(arena))
+            }
+            Expression::PostfixExpression(value) => {
+                crate::Expression::PostfixExpression(value.to_arena(arena))
+            }
+            Expression::BinaryExpression(value) => {
+                crate::Expression::BinaryExpression(value.to_arena(arena))
+            }
+            Expression::AssignmentExpression(value) => {
+                crate::Expression::AssignmentExpression(value.to_arena(arena))
+            }
+            Expression::TupleExpression(value) => {
+                crate::Expression::TupleExpression(value.to_arena(arena))
+            }
+            Expression::CallExpression(value) => {
+                crate::Expression::CallExpression(value.to_arena(arena))
+            }
+            Expression::CallOptionsExpression(value) => {
+                crate::Expression::CallOptionsExpression(value.to_arena(arena))
+            }
+            Expression::MemberAccessExpression(value) => {
+                crate::Expression::MemberAccessExpression(value.to_arena(arena))
+            }
+            Expression::IndexAccessExpression(value) => {
+                crate::Expression::IndexAccessExpression(value.to_arena(arena))
+            }
+            Expression::ConditionalExpression(value) => {
+                crate::Expression::ConditionalExpression(value.to_arena(arena))
+            }
+            Expression::ElementaryTypeExpression(value) => {
+                crate::Expression::ElementaryTypeExpression(value.to_arena(arena))
+            }
+            Expression::NewExpression(value) => {
+                crate::Expression::NewExpression(value.to_arena(arena))
+            }
+            Expression::TypeExpression(value) => {
+                crate::Expression::TypeExpression(value.to_arena(arena))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Primitive {
+    Bool(bool),
+    HexNumber(String),
+    IntegerNumber(String, NumberUnit),
+    RationalNumber(String),
+    String(String),
+    HexString(String),
+    UnicodeString(String),
+}
+
+impl<'ast> IntoOwned for crate::Primitive<'ast> {
+    type Owned = Primitive;
+
+    fn into_owned(self) -> Primitive {
+        match self {
+            crate::Primitive::Bool(value) => Primitive::Bool(value.into_owned()),
+            crate::Primitive::HexNumber(value) => Primitive::HexNumber(value.into_owned()),
+            crate::Primitive::IntegerNumber(value, unit) => {
+                Primitive::IntegerNumber(value.into_owned(), unit.into_owned())
+            }
+            crate::Primitive::RationalNumber(value) => {
+                Primitive::RationalNumber(value.into_owned())
+            }
+            crate::Primitive::String(value) => Primitive::String(value.into_owned()),
+            crate::Primitive::HexString(value) => Primitive::HexString(value.into_owned()),
+            crate::Primitive::UnicodeString(value) => Primitive::UnicodeString(value.into_owned()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for Primitive {
+    type Arena = crate::Primitive<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::Primitive<'ast> {
+        match self {
+            Primitive::Bool(value) => crate::Primitive::Bool(value.to_arena(arena)),
+            Primitive::HexNumber(value) => crate::Primitive::HexNumber(value.to_arena(arena)),
+            Primitive::IntegerNumber(value, unit) => {
+                crate::Primitive::IntegerNumber(value.to_arena(arena), unit.to_arena(arena))
+            }
+            Primitive::RationalNumber(value) => {
+                crate::Primitive::RationalNumber(value.to_arena(arena))
+            }
+            Primitive::String(value) => crate::Primitive::String(value.to_arena(arena)),
+            Primitive::HexString(value) => crate::Primitive::HexString(value.to_arena(arena)),
+            Primitive::UnicodeString(value) => {
+                crate::Primitive::UnicodeString(value.to_arena(arena))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum CallArguments {
+    Positional(ExpressionList),
+    Named(NamedArgumentList),
+}
+
+impl<'ast> IntoOwned for crate::CallArguments<'ast> {
+    type Owned = CallArguments;
+
+    fn into_owned(self) -> CallArguments {
+        match self {
+            crate::CallArguments::Positional(value) => {
+                CallArguments::Positional(value.into_owned())
+            }
+            crate::CallArguments::Named(value) => CallArguments::Named(value.into_owned()),
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for CallArguments {
+    type Arena = crate::CallArguments<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::CallArguments<'ast> {
+        match self {
+            CallArguments::Positional(value) => {
+                crate::CallArguments::Positional(value.to_arena(arena))
+            }
+            CallArguments::Named(value) => crate::CallArguments::Named(value.to_arena(arena)),
+        }
+    }
+}
+
+owned_structs! {
+    PrefixExpression {
+        operator: Node<PrefixOperator>,
+        operand: ExpressionNode,
+    }
+    PostfixExpression {
+        operand: ExpressionNode,
+        operator: Node<PostfixOperator>,
+    }
+    BinaryExpression {
+        left: ExpressionNode,
+        operator: Node<BinaryOperator>,
+        right: ExpressionNode,
+    }
+    AssignmentExpression {
+        left: ExpressionNode,
+        operator: Node<AssignmentOperator>,
+        right: ExpressionNode,
+    }
+    TupleExpression {
+        expressions: ExpressionList,
+    }
+    CallExpression {
+        callee: ExpressionNode,
+        arguments: CallArguments,
+    }
+    CallOptionsExpression {
+        callee: ExpressionNode,
+        options: NamedArgumentList,
+    }
+    NamedArgument {
+        name: IdentifierNode,
+        value: ExpressionNode,
+    }
+    MemberAccessExpression {
+        object: ExpressionNode,
+        member: IdentifierNode,
+    }
+    IndexAccessExpression {
+        array: ExpressionNode,
+        index: Option<ExpressionNode>,
+    }
+    ConditionalExpression {
+        test: ExpressionNode,
+        consequent: ExpressionNode,
+        alternate: ExpressionNode,
+    }
+    NewExpression {
+        type_name: TypeNameNode,
+    }
+    TypeExpression {
+        type_name: TypeNameNode,
+    }
+}
+
+// Type names
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum TypeName {
+    ElementaryTypeName(ElementaryTypeName),
+    UserDefinedTypeName(Identifier),
+    Mapping(Mapping),
+    ArrayTypeName(ArrayTypeName),
+    FunctionTypeName,
+}
+
+impl<'ast> IntoOwned for crate::TypeName<'ast> {
+    type Owned = TypeName;
+
+    fn into_owned(self) -> TypeName {
+        match self {
+            crate::TypeName::ElementaryTypeName(value) => {
+                TypeName::ElementaryTypeName(value.into_owned())
+            }
+            crate::TypeName::UserDefinedTypeName(value) => {
+                TypeName::UserDefinedTypeName(value.into_owned())
+            }
+            crate::TypeName::Mapping(value) => TypeName::Mapping(value.into_owned()),
+            crate::TypeName::ArrayTypeName(value) => TypeName::ArrayTypeName(value.into_owned()),
+            crate::TypeName::FunctionTypeName => TypeName::FunctionTypeName,
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for TypeName {
+    type Arena = crate::TypeName<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::TypeName<'ast> {
+        match self {
+            TypeName::ElementaryTypeName(value) => {
+                crate::TypeName::ElementaryTypeName(value.to_arena(arena))
+            }
+            TypeName::UserDefinedTypeName(value) => {
+                crate::TypeName::UserDefinedTypeName(value.to_arena(arena))
+            }
+            TypeName::Mapping(value) => crate::TypeName::Mapping(value.to_arena(arena)),
+            TypeName::ArrayTypeName(value) => crate::TypeName::ArrayTypeName(value.to_arena(arena)),
+            TypeName::FunctionTypeName => crate::TypeName::FunctionTypeName,
+        }
+    }
+}
+
+owned_structs! {
+    VariableDeclaration {
+        type_name: TypeNameNode,
+        location: Option<Node<StorageLocation>>,
+        id: IdentifierNode,
+    }
+    Mapping {
+        from: ElementaryTypeNameNode,
+        to: TypeNameNode,
+    }
+    ArrayTypeName {
+        base: TypeNameNode,
+        length: Option<ExpressionNode>,
+    }
+}
+
+// Assembly
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AssemblyItem {
+    InlineAssemblyBlock(InlineAssemblyBlock),
+    AssemblyLocalBinding(AssemblyLocalBinding),
+    AssemblyAssignment(AssemblyAssignment),
+    AssemblyIf(AssemblyIf),
+    AssemblySwitch(AssemblySwitch),
+    AssemblyFor(AssemblyFor),
+    AssemblyFunctionDefinition(AssemblyFunctionDefinition),
+    FunctionalAssemblyExpression(FunctionalAssemblyExpression),
+    Leave,
+    Break,
+    Continue,
+}
+
+impl<'ast> IntoOwned for crate::AssemblyItem<'ast> {
+    type Owned = AssemblyItem;
+
+    fn into_owned(self) -> AssemblyItem {
+        match self {
+            crate::AssemblyItem::InlineAssemblyBlock(value) => {
+                AssemblyItem::InlineAssemblyBlock(value.into_owned())
+            }
+            crate::AssemblyItem::AssemblyLocalBinding(value) => {
+                AssemblyItem::AssemblyLocalBinding(value.into_owned())
+            }
+            crate::AssemblyItem::AssemblyAssignment(value) => {
+                AssemblyItem::AssemblyAssignment(value.into_owned())
+            }
+            crate::AssemblyItem::AssemblyIf(value) => AssemblyItem::AssemblyIf(value.into_owned()),
+            crate::AssemblyItem::AssemblySwitch(value) => {
+                AssemblyItem::AssemblySwitch(value.into_owned())
+            }
+            crate::AssemblyItem::AssemblyFor(value) => {
+                AssemblyItem::AssemblyFor(value.into_owned())
+            }
+            crate::AssemblyItem::AssemblyFunctionDefinition(value) => {
+                AssemblyItem::AssemblyFunctionDefinition(value.into_owned())
+            }
+            crate::AssemblyItem::FunctionalAssemblyExpression(value) => {
+                AssemblyItem::FunctionalAssemblyExpression(value.into_owned())
+            }
+            crate::AssemblyItem::Leave => AssemblyItem::Leave,
+            crate::AssemblyItem::Break => AssemblyItem::Break,
+            crate::AssemblyItem::Continue => AssemblyItem::Continue,
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for AssemblyItem {
+    type Arena = crate::AssemblyItem<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::AssemblyItem<'ast> {
+        match self {
+            AssemblyItem::InlineAssemblyBlock(value) => {
+                crate::AssemblyItem::InlineAssemblyBlock(value.to_arena(arena))
+            }
+            AssemblyItem::AssemblyLocalBinding(value) => {
+                crate::AssemblyItem::AssemblyLocalBinding(value.to_arena(arena))
+            }
+            AssemblyItem::AssemblyAssignment(value) => {
+                crate::AssemblyItem::AssemblyAssignment(value.to_arena(arena))
+            }
+            AssemblyItem::AssemblyIf(value) => {
+                crate::AssemblyItem::AssemblyIf(value.to_arena(arena))
+            }
+            AssemblyItem::AssemblySwitch(value) => {
+                crate::AssemblyItem::AssemblySwitch(value.to_arena(arena))
+            }
+            AssemblyItem::AssemblyFor(value) => {
+                crate::AssemblyItem::AssemblyFor(value.to_arena(arena))
+            }
+            AssemblyItem::AssemblyFunctionDefinition(value) => {
+                crate::AssemblyItem::AssemblyFunctionDefinition(value.to_arena(arena))
+            }
+            AssemblyItem::FunctionalAssemblyExpression(value) => {
+                crate::AssemblyItem::FunctionalAssemblyExpression(value.to_arena(arena))
+            }
+            AssemblyItem::Leave => crate::AssemblyItem::Leave,
+            AssemblyItem::Break => crate::AssemblyItem::Break,
+            AssemblyItem::Continue => crate::AssemblyItem::Continue,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AssemblyExpression {
+    Identifier(Identifier),
+    Literal(Primitive),
+    FunctionalAssemblyExpression(FunctionalAssemblyExpression),
+}
+
+impl<'ast> IntoOwned for crate::AssemblyExpression<'ast> {
+    type Owned = AssemblyExpression;
+
+    fn into_owned(self) -> AssemblyExpression {
+        match self {
+            crate::AssemblyExpression::Identifier(value) => {
+                AssemblyExpression::Identifier(value.into_owned())
+            }
+            crate::AssemblyExpression::Literal(value) => {
+                AssemblyExpression::Literal(value.into_owned())
+            }
+            crate::AssemblyExpression::FunctionalAssemblyExpression(value) => {
+                AssemblyExpression::FunctionalAssemblyExpression(value.into_owned())
+            }
+        }
+    }
+}
+
+impl<'ast> ToArena<'ast> for AssemblyExpression {
+    type Arena = crate::AssemblyExpression<'ast>;
+
+    fn to_arena(&self, arena: &'ast Arena) -> crate::AssemblyExpression<'ast> {
+        match self {
+            AssemblyExpression::Identifier(value) => {
+                crate::AssemblyExpression::Identifier(value.to_arena(arena))
+            }
+            AssemblyExpression::Literal(value) => {
+                crate::AssemblyExpression::Literal(value.to_arena(arena))
+            }
+            AssemblyExpression::FunctionalAssemblyExpression(value) => {
+                crate::AssemblyExpression::FunctionalAssemblyExpression(value.to_arena(arena))
+            }
+        }
+    }
+}
+
+owned_structs! {
+    InlineAssemblyBlock {
+        items: AssemblyItemList,
+    }
+    AssemblyLocalBinding {
+        ids: IdentifierList,
+        init: Option<AssemblyExpressionNode>,
+    }
+    AssemblyAssignment {
+        ids: IdentifierList,
+        init: AssemblyExpressionNode,
+    }
+    AssemblyIf {
+        test: AssemblyExpressionNode,
+        body: InlineAssemblyBlockNode,
+    }
+    AssemblySwitch {
+        test: AssemblyExpressionNode,
+        cases: AssemblyCaseList,
+        default: Option<InlineAssemblyBlockNode>,
+    }
+    AssemblyCase {
+        value: Node<Primitive>,
+        body: InlineAssemblyBlockNode,
+    }
+    AssemblyFor {
+        init: InlineAssemblyBlockNode,
+        test: AssemblyExpressionNode,
+        update: InlineAssemblyBlockNode,
+        body: InlineAssemblyBlockNode,
+    }
+    AssemblyFunctionDefinition {
+        name: IdentifierNode,
+        params: IdentifierList,
+        returns: IdentifierList,
+        body: InlineAssemblyBlockNode,
+    }
+    FunctionalAssemblyExpression {
+        id: IdentifierNode,
+        arguments: AssemblyExpressionList,
+    }
+}
+
+// Recovered source
+
+owned_structs! {
+    Unknown {
+        raw: String,
+    }
+}
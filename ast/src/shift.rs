@@ -0,0 +1,466 @@
+use toolshed::list::{GrowableList, List};
+use toolshed::Arena;
+
+use crate::*;
+
+/// Copy of a node with the spans of it and all its descendants moved by `by` bytes,
+/// allocated on `arena`. Used to keep the nodes that follow an edit of the source
+/// without parsing them again. Synthesized nodes keep their lack of a span, and
+/// values without a span, like the text of identifiers, are shared with the original.
+pub trait Shift<'ast>: Sized {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self;
+}
+
+#[inline]
+fn shift_offset(offset: u32, by: i64) -> u32 {
+    (offset as i64 + by) as u32
+}
+
+impl<'ast, T> Shift<'ast> for Node<'ast, T>
+where
+    T: 'ast + Copy + Shift<'ast>,
+{
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        let value = self.value.shifted(arena, by);
+        let inner = match self.is_synthesized() {
+            true => NodeInner::synthesized(value),
+            false => NodeInner::new(
+                shift_offset(self.start, by),
+                shift_offset(self.end, by),
+                value,
+            ),
+        };
+
+        Node::new(arena.alloc(inner))
+    }
+}
+
+impl<'ast, T> Shift<'ast> for List<'ast, T>
+where
+    T: 'ast + Copy + Shift<'ast>,
+{
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        let builder = GrowableList::new();
+
+        for item in self.iter() {
+            builder.push(arena, item.shifted(arena, by));
+        }
+
+        builder.as_list()
+    }
+}
+
+impl<'ast, T: Shift<'ast>> Shift<'ast> for Option<T> {
+    #[inline]
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        self.as_ref().map(|value| value.shifted(arena, by))
+    }
+}
+
+/// Types without nodes are copied as they are.
+macro_rules! impl_leaves {
+    ($( $type:ty ),* $(,)?) => {$(
+        impl<'ast> Shift<'ast> for $type {
+            #[inline]
+            fn shifted(&self, _: &'ast Arena, _: i64) -> Self {
+                *self
+            }
+        }
+    )*};
+}
+
+impl_leaves! {
+    &'ast str,
+    bool,
+    Flag,
+    VersionComparator,
+    StateVariableVisibility,
+    UserDefinableOperator,
+    FunctionVisibility,
+    StateMutability,
+    NumberUnit,
+    PrefixOperator,
+    PostfixOperator,
+    BinaryOperator,
+    AssignmentOperator,
+    StorageLocation,
+    ElementaryTypeName,
+}
+
+/// Structs shift field by field.
+macro_rules! impl_structs {
+    ($( $name:ident { $( $field:ident ),* } )*) => {$(
+        impl<'ast> Shift<'ast> for $name<'ast> {
+            fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+                $name {
+                    $( $field: self.$field.shifted(arena, by), )*
+                }
+            }
+        }
+    )*};
+}
+
+impl<'ast> Shift<'ast> for SourceUnit<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            SourceUnit::PragmaDirective(value) => {
+                SourceUnit::PragmaDirective(value.shifted(arena, by))
+            }
+            SourceUnit::ImportDirective(value) => {
+                SourceUnit::ImportDirective(value.shifted(arena, by))
+            }
+            SourceUnit::ContractDefinition(value) => {
+                SourceUnit::ContractDefinition(value.shifted(arena, by))
+            }
+            SourceUnit::StructDefinition(value) => {
+                SourceUnit::StructDefinition(value.shifted(arena, by))
+            }
+            SourceUnit::EnumDefinition(value) => {
+                SourceUnit::EnumDefinition(value.shifted(arena, by))
+            }
+            SourceUnit::FunctionDefinition(value) => {
+                SourceUnit::FunctionDefinition(value.shifted(arena, by))
+            }
+            SourceUnit::ErrorDefinition(value) => {
+                SourceUnit::ErrorDefinition(value.shifted(arena, by))
+            }
+            SourceUnit::ConstantDeclaration(value) => {
+                SourceUnit::ConstantDeclaration(value.shifted(arena, by))
+            }
+            SourceUnit::UserDefinedValueTypeDefinition(value) => {
+                SourceUnit::UserDefinedValueTypeDefinition(value.shifted(arena, by))
+            }
+            SourceUnit::UsingForDeclaration(value) => {
+                SourceUnit::UsingForDeclaration(value.shifted(arena, by))
+            }
+            SourceUnit::Unknown(value) => SourceUnit::Unknown(value.shifted(arena, by)),
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for PragmaDirective<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            PragmaDirective::Solidity { ranges } => PragmaDirective::Solidity {
+                ranges: ranges.shifted(arena, by),
+            },
+            PragmaDirective::Abicoder { version } => PragmaDirective::Abicoder {
+                version: version.shifted(arena, by),
+            },
+            PragmaDirective::Experimental { feature } => PragmaDirective::Experimental {
+                feature: feature.shifted(arena, by),
+            },
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for ImportDirective<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            ImportDirective::Global { source, alias } => ImportDirective::Global {
+                source: source.shifted(arena, by),
+                alias: alias.shifted(arena, by),
+            },
+            ImportDirective::From {
+                symbol,
+                alias,
+                source,
+            } => ImportDirective::From {
+                symbol: symbol.shifted(arena, by),
+                alias: alias.shifted(arena, by),
+                source: source.shifted(arena, by),
+            },
+            ImportDirective::ManyFrom { imports, source } => ImportDirective::ManyFrom {
+                imports: imports.shifted(arena, by),
+                source: source.shifted(arena, by),
+            },
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for ContractPart<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            ContractPart::StateVariableDeclaration(value) => {
+                ContractPart::StateVariableDeclaration(value.shifted(arena, by))
+            }
+            ContractPart::UsingForDeclaration(value) => {
+                ContractPart::UsingForDeclaration(value.shifted(arena, by))
+            }
+            ContractPart::StructDefinition(value) => {
+                ContractPart::StructDefinition(value.shifted(arena, by))
+            }
+            ContractPart::ModifierDefinition(value) => {
+                ContractPart::ModifierDefinition(value.shifted(arena, by))
+            }
+            ContractPart::FunctionDefinition(value) => {
+                ContractPart::FunctionDefinition(value.shifted(arena, by))
+            }
+            ContractPart::EventDefinition(value) => {
+                ContractPart::EventDefinition(value.shifted(arena, by))
+            }
+            ContractPart::EnumDefinition(value) => {
+                ContractPart::EnumDefinition(value.shifted(arena, by))
+            }
+            ContractPart::ErrorDefinition(value) => {
+                ContractPart::ErrorDefinition(value.shifted(arena, by))
+            }
+            ContractPart::UserDefinedValueTypeDefinition(value) => {
+                ContractPart::UserDefinedValueTypeDefinition(value.shifted(arena, by))
+            }
+            ContractPart::Unknown(value) => ContractPart::Unknown(value.shifted(arena, by)),
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for Statement<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            Statement::Placeholder => Statement::Placeholder,
+            Statement::IfStatement(value) => Statement::IfStatement(value.shifted(arena, by)),
+            Statement::WhileStatement(value) => Statement::WhileStatement(value.shifted(arena, by)),
+            Statement::ForStatement(value) => Statement::ForStatement(value.shifted(arena, by)),
+            Statement::BlockStatement(value) => Statement::BlockStatement(value.shifted(arena, by)),
+            Statement::InlineAssemblyStatement(value) => {
+                Statement::InlineAssemblyStatement(value.shifted(arena, by))
+            }
+            Statement::DoWhileStatement(value) => {
+                Statement::DoWhileStatement(value.shifted(arena, by))
+            }
+            Statement::ContinueStatement => Statement::ContinueStatement,
+            Statement::BreakStatement => Statement::BreakStatement,
+            Statement::ReturnStatement(value) => {
+                Statement::ReturnStatement(value.shifted(arena, by))
+            }
+            Statement::ThrowStatement => Statement::ThrowStatement,
+            Statement::EmitStatement(value) => Statement::EmitStatement(value.shifted(arena, by)),
+            Statement::RevertStatement(value) => {
+                Statement::RevertStatement(value.shifted(arena, by))
+            }
+            Statement::UncheckedBlock(value) => Statement::UncheckedBlock(value.shifted(arena, by)),
+            Statement::TryStatement(value) => Statement::TryStatement(value.shifted(arena, by)),
+            Statement::VariableDefinitionStatement(value) => {
+                Statement::VariableDefinitionStatement(value.shifted(arena, by))
+            }
+            Statement::InferredDefinitionStatement(value) => {
+                Statement::InferredDefinitionStatement(value.shifted(arena, by))
+            }
+            Statement::ExpressionStatement(value) => {
+                Statement::ExpressionStatement(value.shifted(arena, by))
+            }
+            Statement::Unknown(value) => Statement::Unknown(value.shifted(arena, by)),
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for SimpleStatement<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            SimpleStatement::VariableDefinitionStatement(value) => {
+                SimpleStatement::VariableDefinitionStatement(value.shifted(arena, by))
+            }
+            SimpleStatement::InferredDefinitionStatement(value) => {
+                SimpleStatement::InferredDefinitionStatement(value.shifted(arena, by))
+            }
+            SimpleStatement::ExpressionStatement(value) => {
+                SimpleStatement::ExpressionStatement(value.shifted(arena, by))
+            }
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for Expression<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            Expression::ThisExpression => Expression::ThisExpression,
+            Expression::IdentifierExpression(value) => {
+                Expression::IdentifierExpression(value.shifted(arena, by))
+            }
+            Expression::PrimitiveExpression(value) => {
+                Expression::PrimitiveExpression(value.shifted(arena, by))
+            }
+            Expression::PrefixExpression(value) => {
+                Expression::PrefixExpression(value.shifted(arena, by))
+            }
+            Expression::PostfixExpression(value) => {
+                Expression::PostfixExpression(value.shifted(arena, by))
+            }
+            Expression::BinaryExpression(value) => {
+                Expression::BinaryExpression(value.shifted(arena, by))
+            }
+            Expression::AssignmentExpression(value) => {
+                Expression::AssignmentExpression(value.shifted(arena, by))
+            }
+            Expression::TupleExpression(value) => {
+                Expression::TupleExpression(value.shifted(arena, by))
+            }
+            Expression::CallExpression(value) => {
+                Expression::CallExpression(value.shifted(arena, by))
+            }
+            Expression::CallOptionsExpression(value) => {
+                Expression::CallOptionsExpression(value.shifted(arena, by))
+            }
+            Expression::MemberAccessExpression(value) => {
+                Expression::MemberAccessExpression(value.shifted(arena, by))
+            }
+            Expression::IndexAccessExpression(value) => {
+                Expression::IndexAccessExpression(value.shifted(arena, by))
+            }
+            Expression::ConditionalExpression(value) => {
+                Expression::ConditionalExpression(value.shifted(arena, by))
+            }
+            Expression::ElementaryTypeExpression(value) => {
+                Expression::ElementaryTypeExpression(value.shifted(arena, by))
+            }
+            Expression::NewExpression(value) => Expression::NewExpression(value.shifted(arena, by)),
+            Expression::TypeExpression(value) => {
+                Expression::TypeExpression(value.shifted(arena, by))
+            }
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for Primitive<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            Primitive::Bool(value) => Primitive::Bool(value.shifted(arena, by)),
+            Primitive::HexNumber(value) => Primitive::HexNumber(value.shifted(arena, by)),
+            Primitive::IntegerNumber(value, unit) => Primitive::IntegerNumber(value, unit),
+            Primitive::RationalNumber(value) => Primitive::RationalNumber(value.shifted(arena, by)),
+            Primitive::String(value) => Primitive::String(value.shifted(arena, by)),
+            Primitive::HexString(value) => Primitive::HexString(value.shifted(arena, by)),
+            Primitive::UnicodeString(value) => Primitive::UnicodeString(value.shifted(arena, by)),
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for CallArguments<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            CallArguments::Positional(value) => CallArguments::Positional(value.shifted(arena, by)),
+            CallArguments::Named(value) => CallArguments::Named(value.shifted(arena, by)),
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for TypeName<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            TypeName::ElementaryTypeName(value) => {
+                TypeName::ElementaryTypeName(value.shifted(arena, by))
+            }
+            TypeName::UserDefinedTypeName(value) => {
+                TypeName::UserDefinedTypeName(value.shifted(arena, by))
+            }
+            TypeName::Mapping(value) => TypeName::Mapping(value.shifted(arena, by)),
+            TypeName::ArrayTypeName(value) => TypeName::ArrayTypeName(value.shifted(arena, by)),
+            TypeName::FunctionTypeName => TypeName::FunctionTypeName,
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for AssemblyItem<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            AssemblyItem::InlineAssemblyBlock(value) => {
+                AssemblyItem::InlineAssemblyBlock(value.shifted(arena, by))
+            }
+            AssemblyItem::AssemblyLocalBinding(value) => {
+                AssemblyItem::AssemblyLocalBinding(value.shifted(arena, by))
+            }
+            AssemblyItem::AssemblyAssignment(value) => {
+                AssemblyItem::AssemblyAssignment(value.shifted(arena, by))
+            }
+            AssemblyItem::AssemblyIf(value) => AssemblyItem::AssemblyIf(value.shifted(arena, by)),
+            AssemblyItem::AssemblySwitch(value) => {
+                AssemblyItem::AssemblySwitch(value.shifted(arena, by))
+            }
+            AssemblyItem::AssemblyFor(value) => AssemblyItem::AssemblyFor(value.shifted(arena, by)),
+            AssemblyItem::AssemblyFunctionDefinition(value) => {
+                AssemblyItem::AssemblyFunctionDefinition(value.shifted(arena, by))
+            }
+            AssemblyItem::FunctionalAssemblyExpression(value) => {
+                AssemblyItem::FunctionalAssemblyExpression(value.shifted(arena, by))
+            }
+            AssemblyItem::Leave => AssemblyItem::Leave,
+            AssemblyItem::Break => AssemblyItem::Break,
+            AssemblyItem::Continue => AssemblyItem::Continue,
+        }
+    }
+}
+
+impl<'ast> Shift<'ast> for AssemblyExpression<'ast> {
+    fn shifted(&self, arena: &'ast Arena, by: i64) -> Self {
+        match *self {
+            AssemblyExpression::Identifier(value) => {
+                AssemblyExpression::Identifier(value.shifted(arena, by))
+            }
+            AssemblyExpression::Literal(value) => {
+                AssemblyExpression::Literal(value.shifted(arena, by))
+            }
+            AssemblyExpression::FunctionalAssemblyExpression(value) => {
+                AssemblyExpression::FunctionalAssemblyExpression(value.shifted(arena, by))
+            }
+        }
+    }
+}
+
+impl_structs! {
+    VersionRange { comparators }
+    ConstantDeclaration { type_name, name, init }
+    Import { symbol, alias }
+    ContractDefinition { name, inherits, body }
+    StateVariableDeclaration { type_name, visibility, constant, name, init }
+    UsingForDeclaration { id, functions, type_name, global }
+    UsingForFunction { id, operator }
+    StructDefinition { name, body }
+    ModifierDefinition { name, params, block }
+    EventDefinition { anonymous, name, params }
+    IndexedParameter { type_name, indexed, name }
+    EnumDefinition { name, variants }
+    UserDefinedValueTypeDefinition { name, underlying }
+    ErrorDefinition { name, params }
+    FunctionDefinition { name, params, visibility, mutability, modifiers, returns, block }
+    ModifierInvocation { id, arguments }
+    Parameter { type_name, location, name }
+    IfStatement { test, consequent, alternate }
+    WhileStatement { test, body }
+    ForStatement { init, test, update, body }
+    Block { body }
+    InlineAssemblyStatement { string, flags, block }
+    DoWhileStatement { body, test }
+    ReturnStatement { value }
+    EmitStatement { event }
+    RevertStatement { error }
+    UncheckedBlock { block }
+    TryStatement { call, returns, body, clauses }
+    CatchClause { id, params, body }
+    VariableDefinitionStatement { declaration, init }
+    InferredDefinitionStatement { ids, init }
+    PrefixExpression { operator, operand }
+    PostfixExpression { operand, operator }
+    BinaryExpression { left, operator, right }
+    AssignmentExpression { left, operator, right }
+    TupleExpression { expressions }
+    CallExpression { callee, arguments }
+    CallOptionsExpression { callee, options }
+    NamedArgument { name, value }
+    MemberAccessExpression { object, member }
+    IndexAccessExpression { array, index }
+    ConditionalExpression { test, consequent, alternate }
+    NewExpression { type_name }
+    TypeExpression { type_name }
+    VariableDeclaration { type_name, location, id }
+    Mapping { from, to }
+    ArrayTypeName { base, length }
+    InlineAssemblyBlock { items }
+    AssemblyLocalBinding { ids, init }
+    AssemblyAssignment { ids, init }
+    AssemblyIf { test, body }
+    AssemblySwitch { test, cases, default }
+    AssemblyCase { value, body }
+    AssemblyFor { init, test, update, body }
+    AssemblyFunctionDefinition { name, params, returns, body }
+    FunctionalAssemblyExpression { id, arguments }
+    Unknown { raw }
+}
@@ -1,25 +1,95 @@
-use crate::{ContractDefinition, IdentifierNode, Node, NodeList, StringLiteral};
+use crate::{
+    ContractDefinition, EnumDefinition, ErrorDefinition, ExpressionNode, FunctionDefinition,
+    IdentifierNode, Node, NodeList, StringLiteral, StructDefinition, TypeNameNode, Unknown,
+    UserDefinedValueTypeDefinition, UsingForDeclaration,
+};
 
 /// A `SourceUnit` is the top level construct of the grammar. It represents a single solidity file.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SourceUnit<'ast> {
     PragmaDirective(PragmaDirective<'ast>),
     ImportDirective(ImportDirective<'ast>),
     ContractDefinition(ContractDefinition<'ast>),
+    StructDefinition(StructDefinition<'ast>),
+    EnumDefinition(EnumDefinition<'ast>),
+    FunctionDefinition(FunctionDefinition<'ast>),
+    ErrorDefinition(ErrorDefinition<'ast>),
+    ConstantDeclaration(ConstantDeclaration<'ast>),
+    UserDefinedValueTypeDefinition(UserDefinedValueTypeDefinition<'ast>),
+    UsingForDeclaration(UsingForDeclaration<'ast>),
+    Unknown(Unknown<'ast>),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-pub struct PragmaDirective<'ast> {
-    pub version: &'ast str,
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PragmaDirective<'ast> {
+    /// 'pragma' 'solidity' VersionRange ('||' VersionRange)* ';'
+    Solidity { ranges: VersionRangeList<'ast> },
+
+    /// 'pragma' 'abicoder' Identifier ';'
+    Abicoder { version: IdentifierNode<'ast> },
+
+    /// 'pragma' 'experimental' Identifier ';'
+    Experimental { feature: IdentifierNode<'ast> },
+}
+
+/// Comparators that all have to match, like `>=0.6 <0.9`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VersionRange<'ast> {
+    pub comparators: NodeList<'ast, VersionComparator>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct VersionComparator {
+    pub operator: VersionOperator,
+    pub version: PragmaVersion,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum VersionOperator {
+    /// No operator, or `=`
+    Exact,
+    Caret,
+    Tilde,
+    Greater,
+    GreaterEquals,
+    Lesser,
+    LesserEquals,
+}
+
+/// A version such as `0.8.19`, omitted or wildcard (`x`, `*`) parts are `None`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PragmaVersion {
+    pub major: u32,
+    pub minor: Option<u32>,
+    pub patch: Option<u32>,
+}
+
+pub type VersionRangeList<'ast> = NodeList<'ast, VersionRange<'ast>>;
+
+/// File level `uint256 constant X = 1;`, the value is mandatory
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ConstantDeclaration<'ast> {
+    pub type_name: TypeNameNode<'ast>,
+    pub name: IdentifierNode<'ast>,
+    pub init: ExpressionNode<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Import<'ast> {
     pub symbol: IdentifierNode<'ast>,
     pub alias: Option<IdentifierNode<'ast>>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ImportDirective<'ast> {
     /// 'import' StringLiteral ('as' Identifier)? ';'
     Global {
@@ -45,4 +115,12 @@ impl_from! {
     PragmaDirective => SourceUnit::PragmaDirective,
     ImportDirective => SourceUnit::ImportDirective,
     ContractDefinition => SourceUnit::ContractDefinition,
+    StructDefinition => SourceUnit::StructDefinition,
+    EnumDefinition => SourceUnit::EnumDefinition,
+    FunctionDefinition => SourceUnit::FunctionDefinition,
+    ErrorDefinition => SourceUnit::ErrorDefinition,
+    ConstantDeclaration => SourceUnit::ConstantDeclaration,
+    UserDefinedValueTypeDefinition => SourceUnit::UserDefinedValueTypeDefinition,
+    UsingForDeclaration => SourceUnit::UsingForDeclaration,
+    Unknown => SourceUnit::Unknown,
 }
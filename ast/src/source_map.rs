@@ -0,0 +1,187 @@
+use crate::NodeInner;
+
+/// Identifies a source file, like the index of the file within a project.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FileId(pub usize);
+
+/// Zero based line and column, as used by the LSP.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LineCol {
+    pub line: u32,
+    pub col: u32,
+}
+
+/// Source text of a file along with the offsets at which its lines start, to turn
+/// the byte offsets of nodes into lines and columns.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SourceMap {
+    source: String,
+    /// Offset of the first byte of every line, the first one being `0`
+    line_starts: Vec<u32>,
+}
+
+impl SourceMap {
+    pub fn new(source: impl Into<String>) -> Self {
+        let source = source.into();
+        let line_starts = std::iter::once(0)
+            .chain(
+                source
+                    .match_indices('\n')
+                    .map(|(index, _)| index as u32 + 1),
+            )
+            .collect();
+
+        SourceMap {
+            source,
+            line_starts,
+        }
+    }
+
+    #[inline]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Number of lines, a trailing newline starts an empty last line.
+    #[inline]
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Text of the zero based `line`, without the line break.
+    pub fn line(&self, line: u32) -> Option<&str> {
+        let (start, end) = self.line_bounds(line)?;
+        let text = &self.source[start as usize..end as usize];
+
+        Some(text.strip_suffix('\r').unwrap_or(text))
+    }
+
+    /// Line and column of a byte `offset`, with the column counted in bytes.
+    /// Offsets past the end of the source are clamped to it.
+    pub fn line_col(&self, offset: u32) -> LineCol {
+        let offset = offset.min(self.source.len() as u32);
+        let line = self.line_of(offset);
+
+        LineCol {
+            line,
+            col: offset - self.line_starts[line as usize],
+        }
+    }
+
+    /// Like `line_col`, with the column counted in UTF-16 code units like the LSP
+    /// does by default. An offset within a character counts from its start.
+    pub fn line_col_utf16(&self, offset: u32) -> LineCol {
+        let LineCol { line, col } = self.line_col(offset);
+        let start = self.line_starts[line as usize] as usize;
+
+        let col = self.source[start..]
+            .char_indices()
+            .take_while(|&(index, char)| index + char.len_utf8() <= col as usize)
+            .map(|(_, char)| char.len_utf16() as u32)
+            .sum();
+
+        LineCol { line, col }
+    }
+
+    /// Byte offset of a line and byte column, `None` if the line doesn't exist.
+    /// Columns past the end of the line are clamped to it.
+    pub fn offset(&self, position: LineCol) -> Option<u32> {
+        let (start, end) = self.line_bounds(position.line)?;
+
+        Some((start + position.col).min(end))
+    }
+
+    /// Byte offset of a line and UTF-16 column, the reverse of `line_col_utf16`.
+    pub fn offset_utf16(&self, position: LineCol) -> Option<u32> {
+        let (start, end) = self.line_bounds(position.line)?;
+        let mut units = 0;
+
+        for (index, char) in self.source[start as usize..end as usize].char_indices() {
+            if units >= position.col {
+                return Some(start + index as u32);
+            }
+
+            units += char.len_utf16() as u32;
+        }
+
+        Some(end)
+    }
+
+    /// Source text of a node, `None` for nodes that weren't parsed from it.
+    pub fn slice<T>(&self, node: &NodeInner<T>) -> Option<&str> {
+        match node.is_synthesized() {
+            true => None,
+            false => self.source.get(node.start as usize..node.end as usize),
+        }
+    }
+
+    fn line_of(&self, offset: u32) -> u32 {
+        (self.line_starts.partition_point(|&start| start <= offset) - 1) as u32
+    }
+
+    /// Start of the line and the end of its text, before the `\n`.
+    fn line_bounds(&self, line: u32) -> Option<(u32, u32)> {
+        let start = *self.line_starts.get(line as usize)?;
+        let end = match self.line_starts.get(line as usize + 1) {
+            Some(next) => next - 1,
+            None => self.source.len() as u32,
+        };
+
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(line: u32, col: u32) -> LineCol {
+        LineCol { line, col }
+    }
+
+    #[test]
+    fn lines_and_columns() {
+        let map = SourceMap::new("contract A {\r\n    uint x;\n}\n");
+
+        assert_eq!(map.line_count(), 4);
+        assert_eq!(map.line(0), Some("contract A {"));
+        assert_eq!(map.line(1), Some("    uint x;"));
+        assert_eq!(map.line(3), Some(""));
+        assert_eq!(map.line(4), None);
+
+        assert_eq!(map.line_col(0), at(0, 0));
+        assert_eq!(map.line_col(13), at(0, 13));
+        assert_eq!(map.line_col(18), at(1, 4));
+        assert_eq!(map.line_col(26), at(2, 0));
+        assert_eq!(map.line_col(99), at(3, 0));
+
+        assert_eq!(map.offset(at(1, 4)), Some(18));
+        assert_eq!(map.offset(at(1, 99)), Some(25));
+        assert_eq!(map.offset(at(4, 0)), None);
+
+        let name = NodeInner::new(9, 10, "A");
+
+        assert_eq!(map.slice(&name), Some("A"));
+        assert_eq!(map.slice(&NodeInner::synthesized("A")), None);
+    }
+
+    #[test]
+    fn utf16_columns() {
+        // `é` is 2 bytes and 1 UTF-16 unit, `𝔸` is 4 bytes and 2 units
+        let map = SourceMap::new("x\nstring s = \"é𝔸\"; y");
+
+        assert_eq!(map.line_col(22), at(1, 20));
+        assert_eq!(map.line_col_utf16(22), at(1, 17));
+        assert_eq!(map.line_col_utf16(16), at(1, 13));
+        // Within `𝔸`, counted from its start
+        assert_eq!(map.line_col_utf16(18), at(1, 13));
+
+        assert_eq!(map.offset_utf16(at(1, 17)), Some(22));
+        assert_eq!(map.offset_utf16(at(1, 13)), Some(16));
+        assert_eq!(map.offset_utf16(at(1, 99)), Some(24));
+        assert_eq!(map.offset_utf16(at(0, 0)), Some(0));
+    }
+}
@@ -1,6 +1,7 @@
 use toolshed::list::List;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Statement<'ast> {
     /// Only available in modifiers
     Placeholder,
@@ -14,13 +15,19 @@ pub enum Statement<'ast> {
     BreakStatement,
     ReturnStatement(ReturnStatement<'ast>),
     ThrowStatement,
+    EmitStatement(EmitStatement<'ast>),
+    RevertStatement(RevertStatement<'ast>),
+    UncheckedBlock(UncheckedBlock<'ast>),
+    TryStatement(TryStatement<'ast>),
     VariableDefinitionStatement(VariableDefinitionStatement<'ast>),
     InferredDefinitionStatement(InferredDefinitionStatement<'ast>),
     ExpressionStatement(ExpressionNode<'ast>),
+    Unknown(Unknown<'ast>),
 }
 
 /// Used in the `for` loop initialization.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SimpleStatement<'ast> {
     VariableDefinitionStatement(VariableDefinitionStatement<'ast>),
     InferredDefinitionStatement(InferredDefinitionStatement<'ast>),
@@ -28,6 +35,7 @@ pub enum SimpleStatement<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IfStatement<'ast> {
     pub test: ExpressionNode<'ast>,
     pub consequent: StatementNode<'ast>,
@@ -35,12 +43,14 @@ pub struct IfStatement<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct WhileStatement<'ast> {
     pub test: ExpressionNode<'ast>,
     pub body: StatementNode<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ForStatement<'ast> {
     pub init: Option<SimpleStatementNode<'ast>>,
     pub test: Option<ExpressionNode<'ast>>,
@@ -49,29 +59,76 @@ pub struct ForStatement<'ast> {
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Block<'ast> {
     pub body: StatementList<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct InlineAssemblyStatement<'ast> {
     pub string: Option<StringLiteralNode<'ast>>,
+    /// `("memory-safe")`
+    pub flags: StringLiteralList<'ast>,
     pub block: InlineAssemblyBlockNode<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DoWhileStatement<'ast> {
     pub body: StatementNode<'ast>,
     pub test: ExpressionNode<'ast>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ReturnStatement<'ast> {
     pub value: Option<ExpressionNode<'ast>>,
 }
 
+/// `emit Event(...);`, the call to the event is kept as a whole
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EmitStatement<'ast> {
+    pub event: ExpressionNode<'ast>,
+}
+
+/// `revert CustomError(...);`, the call to the error is kept as a whole
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct RevertStatement<'ast> {
+    pub error: ExpressionNode<'ast>,
+}
+
+/// `unchecked { ... }`, arithmetic within the block wraps instead of reverting
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UncheckedBlock<'ast> {
+    pub block: BlockNode<'ast>,
+}
+
+/// `try call() returns (...) { ... } catch { ... }`, the call is always external
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct TryStatement<'ast> {
+    pub call: ExpressionNode<'ast>,
+    pub returns: ParameterList<'ast>,
+    pub body: BlockNode<'ast>,
+    pub clauses: CatchClauseList<'ast>,
+}
+
+/// `catch Error(string memory reason) { ... }`, both the identifier and parameters are optional
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CatchClause<'ast> {
+    pub id: Option<IdentifierNode<'ast>>,
+    pub params: ParameterList<'ast>,
+    pub body: BlockNode<'ast>,
+}
+
 /// explicitly typed, can have storage flag, init is optional
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VariableDefinitionStatement<'ast> {
     pub declaration: VariableDeclarationNode<'ast>,
     pub init: Option<ExpressionNode<'ast>>,
@@ -79,12 +136,13 @@ pub struct VariableDefinitionStatement<'ast> {
 
 /// type inferred via `var`, cannot have storage flag, init is mandatory
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct InferredDefinitionStatement<'ast> {
     pub ids: List<'ast, Option<IdentifierNode<'ast>>>,
     pub init: ExpressionNode<'ast>,
 }
 
-use crate::{ExpressionNode, IdentifierNode, InlineAssemblyBlockNode, Node, NodeList, StringLiteralNode, VariableDeclarationNode};
+use crate::{ExpressionNode, IdentifierNode, InlineAssemblyBlockNode, Node, NodeList, ParameterList, StringLiteralList, StringLiteralNode, Unknown, VariableDeclarationNode};
 
 pub use self::Statement::{BreakStatement, ContinueStatement, Placeholder, ThrowStatement};
 
@@ -92,6 +150,8 @@ pub type StatementNode<'ast> = Node<'ast, Statement<'ast>>;
 pub type StatementList<'ast> = NodeList<'ast, Statement<'ast>>;
 pub type SimpleStatementNode<'ast> = Node<'ast, SimpleStatement<'ast>>;
 pub type BlockNode<'ast> = Node<'ast, Block<'ast>>;
+pub type CatchClauseNode<'ast> = Node<'ast, CatchClause<'ast>>;
+pub type CatchClauseList<'ast> = NodeList<'ast, CatchClause<'ast>>;
 
 impl_from! {
     IfStatement => Statement::IfStatement,
@@ -99,12 +159,17 @@ impl_from! {
     ForStatement => Statement::ForStatement,
     DoWhileStatement => Statement::DoWhileStatement,
     ReturnStatement => Statement::ReturnStatement,
+    EmitStatement => Statement::EmitStatement,
+    RevertStatement => Statement::RevertStatement,
+    UncheckedBlock => Statement::UncheckedBlock,
+    TryStatement => Statement::TryStatement,
     VariableDefinitionStatement => Statement::VariableDefinitionStatement,
     VariableDefinitionStatement => SimpleStatement::VariableDefinitionStatement,
     InferredDefinitionStatement => Statement::InferredDefinitionStatement,
     InferredDefinitionStatement => SimpleStatement::InferredDefinitionStatement,
     ExpressionNode => Statement::ExpressionStatement,
     ExpressionNode => SimpleStatement::ExpressionStatement,
+    Unknown => Statement::Unknown,
     Block => Statement::BlockStatement,
     InlineAssemblyStatement => Statement::InlineAssemblyStatement,
 }
@@ -0,0 +1,1111 @@
+use std::hash::Hasher;
+
+use toolshed::list::List;
+
+use crate::*;
+
+/// Equality of the code a node stands for, ignoring the spans of the nodes. Unlike
+/// `PartialEq`, `a + b` parsed from two differently formatted files is equal.
+pub trait SyntaxEq {
+    fn syntax_eq(&self, other: &Self) -> bool;
+}
+
+/// Hashing consistent with `SyntaxEq`, so spans don't change the hash either.
+pub trait SyntaxHash {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H);
+}
+
+/// Hash of the code a node stands for, computed with `SyntaxHasher` so that it can
+/// key caches across runs.
+pub fn syntax_hash<T: SyntaxHash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = SyntaxHasher::default();
+
+    value.syntax_hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 64 bit FNV-1a. Unlike the `DefaultHasher` of std, it isn't seeded and its output
+/// doesn't change between Rust releases or with the endianness of the machine.
+#[derive(Clone, Copy, Debug)]
+pub struct SyntaxHasher(u64);
+
+impl Default for SyntaxHasher {
+    #[inline]
+    fn default() -> Self {
+        SyntaxHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for SyntaxHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    #[inline]
+    fn write_u16(&mut self, value: u16) {
+        self.write(&value.to_le_bytes())
+    }
+
+    #[inline]
+    fn write_u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes())
+    }
+
+    #[inline]
+    fn write_u64(&mut self, value: u64) {
+        self.write(&value.to_le_bytes())
+    }
+
+    #[inline]
+    fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64)
+    }
+}
+
+impl<'ast, T: SyntaxEq> SyntaxEq for Node<'ast, T> {
+    #[inline]
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self.value.syntax_eq(&other.value)
+    }
+}
+
+impl<'ast, T: SyntaxHash> SyntaxHash for Node<'ast, T> {
+    #[inline]
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.value.syntax_hash(state)
+    }
+}
+
+impl<'ast, T: SyntaxEq> SyntaxEq for List<'ast, T> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        let mut left = self.iter();
+        let mut right = other.iter();
+
+        loop {
+            match (left.next(), right.next()) {
+                (Some(left), Some(right)) if left.syntax_eq(right) => {}
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl<'ast, T: SyntaxHash> SyntaxHash for List<'ast, T> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        let mut len = 0u64;
+
+        for item in self.iter() {
+            item.syntax_hash(state);
+            len += 1;
+        }
+
+        // Keeps `[a, b], [c]` apart from `[a], [b, c]`
+        state.write_u64(len);
+    }
+}
+
+impl<T: SyntaxEq> SyntaxEq for Option<T> {
+    #[inline]
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Some(left), Some(right)) => left.syntax_eq(right),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<T: SyntaxHash> SyntaxHash for Option<T> {
+    #[inline]
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Some(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            None => state.write_u8(0),
+        }
+    }
+}
+
+impl SyntaxEq for &str {
+    #[inline]
+    fn syntax_eq(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+impl SyntaxHash for &str {
+    #[inline]
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        state.write(self.as_bytes());
+        state.write_u8(0xff);
+    }
+}
+
+/// Types without nodes compare as they are.
+macro_rules! impl_leaves {
+    ($( $type:ty ),* $(,)?) => {$(
+        impl SyntaxEq for $type {
+            #[inline]
+            fn syntax_eq(&self, other: &Self) -> bool {
+                self == other
+            }
+        }
+    )*};
+}
+
+impl_leaves! {
+    bool,
+    u8,
+    u32,
+    Flag,
+    VersionOperator,
+    PragmaVersion,
+    VersionComparator,
+    StateVariableVisibility,
+    UserDefinableOperator,
+    FunctionVisibility,
+    StateMutability,
+    EtherUnit,
+    TimeUnit,
+    NumberUnit,
+    PrefixOperator,
+    PostfixOperator,
+    BinaryOperator,
+    AssignmentOperator,
+    StorageLocation,
+    ElementaryTypeName,
+}
+
+/// Fieldless enums hash the index of their variant, which only changes with the
+/// order of the variants, unlike what `#[derive(Hash)]` writes.
+macro_rules! impl_variant_hashes {
+    ($( $type:ty ),* $(,)?) => {$(
+        impl SyntaxHash for $type {
+            #[inline]
+            fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+                state.write_u8(*self as u8)
+            }
+        }
+    )*};
+}
+
+impl_variant_hashes! {
+    bool,
+    VersionOperator,
+    StateVariableVisibility,
+    UserDefinableOperator,
+    FunctionVisibility,
+    StateMutability,
+    EtherUnit,
+    TimeUnit,
+    PrefixOperator,
+    PostfixOperator,
+    BinaryOperator,
+    AssignmentOperator,
+    StorageLocation,
+}
+
+impl SyntaxHash for u8 {
+    #[inline]
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u8(*self)
+    }
+}
+
+impl SyntaxHash for u32 {
+    #[inline]
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(*self)
+    }
+}
+
+impl SyntaxHash for Flag {
+    #[inline]
+    fn syntax_hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+impl SyntaxHash for PragmaVersion {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.major.syntax_hash(state);
+        self.minor.syntax_hash(state);
+        self.patch.syntax_hash(state);
+    }
+}
+
+impl SyntaxHash for VersionComparator {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        self.operator.syntax_hash(state);
+        self.version.syntax_hash(state);
+    }
+}
+
+impl SyntaxHash for NumberUnit {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            NumberUnit::None => state.write_u8(0),
+            NumberUnit::Ether(unit) => {
+                state.write_u8(1);
+                unit.syntax_hash(state);
+            }
+            NumberUnit::Time(unit) => {
+                state.write_u8(2);
+                unit.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl SyntaxHash for ElementaryTypeName {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match *self {
+            ElementaryTypeName::Address => state.write_u8(0),
+            ElementaryTypeName::AddressPayable => state.write_u8(1),
+            ElementaryTypeName::Bool => state.write_u8(2),
+            ElementaryTypeName::String => state.write_u8(3),
+            ElementaryTypeName::Bytes => state.write_u8(4),
+            ElementaryTypeName::Int(size) => state.write(&[5, size]),
+            ElementaryTypeName::Uint(size) => state.write(&[6, size]),
+            ElementaryTypeName::Byte(size) => state.write(&[7, size]),
+            ElementaryTypeName::Fixed(size, decimals) => state.write(&[8, size, decimals]),
+            ElementaryTypeName::Ufixed(size, decimals) => state.write(&[9, size, decimals]),
+        }
+    }
+}
+
+/// Structs compare and hash field by field.
+macro_rules! impl_structs {
+    ($( $name:ident { $( $field:ident ),* } )*) => {$(
+        impl<'ast> SyntaxEq for $name<'ast> {
+            fn syntax_eq(&self, other: &Self) -> bool {
+                true $( && self.$field.syntax_eq(&other.$field) )*
+            }
+        }
+
+        impl<'ast> SyntaxHash for $name<'ast> {
+            fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+                $( self.$field.syntax_hash(state); )*
+            }
+        }
+    )*};
+}
+
+// Enums compare their variants, and hash the index of the variant before its fields.
+
+impl<'ast> SyntaxEq for SourceUnit<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (SourceUnit::PragmaDirective(a), SourceUnit::PragmaDirective(b)) => a.syntax_eq(b),
+            (SourceUnit::ImportDirective(a), SourceUnit::ImportDirective(b)) => a.syntax_eq(b),
+            (SourceUnit::ContractDefinition(a), SourceUnit::ContractDefinition(b)) => {
+                a.syntax_eq(b)
+            }
+            (SourceUnit::StructDefinition(a), SourceUnit::StructDefinition(b)) => a.syntax_eq(b),
+            (SourceUnit::EnumDefinition(a), SourceUnit::EnumDefinition(b)) => a.syntax_eq(b),
+            (SourceUnit::FunctionDefinition(a), SourceUnit::FunctionDefinition(b)) => {
+                a.syntax_eq(b)
+            }
+            (SourceUnit::ErrorDefinition(a), SourceUnit::ErrorDefinition(b)) => a.syntax_eq(b),
+            (SourceUnit::ConstantDeclaration(a), SourceUnit::ConstantDeclaration(b)) => {
+                a.syntax_eq(b)
+            }
+            (
+                SourceUnit::UserDefinedValueTypeDefinition(a),
+                SourceUnit::UserDefinedValueTypeDefinition(b),
+            ) => a.syntax_eq(b),
+            (SourceUnit::UsingForDeclaration(a), SourceUnit::UsingForDeclaration(b)) => {
+                a.syntax_eq(b)
+            }
+            (SourceUnit::Unknown(a), SourceUnit::Unknown(b)) => a.syntax_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for SourceUnit<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            SourceUnit::PragmaDirective(value) => {
+                state.write_u8(0);
+                value.syntax_hash(state);
+            }
+            SourceUnit::ImportDirective(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            SourceUnit::ContractDefinition(value) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+            }
+            SourceUnit::StructDefinition(value) => {
+                state.write_u8(3);
+                value.syntax_hash(state);
+            }
+            SourceUnit::EnumDefinition(value) => {
+                state.write_u8(4);
+                value.syntax_hash(state);
+            }
+            SourceUnit::FunctionDefinition(value) => {
+                state.write_u8(5);
+                value.syntax_hash(state);
+            }
+            SourceUnit::ErrorDefinition(value) => {
+                state.write_u8(6);
+                value.syntax_hash(state);
+            }
+            SourceUnit::ConstantDeclaration(value) => {
+                state.write_u8(7);
+                value.syntax_hash(state);
+            }
+            SourceUnit::UserDefinedValueTypeDefinition(value) => {
+                state.write_u8(8);
+                value.syntax_hash(state);
+            }
+            SourceUnit::UsingForDeclaration(value) => {
+                state.write_u8(9);
+                value.syntax_hash(state);
+            }
+            SourceUnit::Unknown(value) => {
+                state.write_u8(10);
+                value.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for PragmaDirective<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                PragmaDirective::Solidity { ranges },
+                PragmaDirective::Solidity {
+                    ranges: other_ranges,
+                },
+            ) => ranges.syntax_eq(other_ranges),
+            (
+                PragmaDirective::Abicoder { version },
+                PragmaDirective::Abicoder {
+                    version: other_version,
+                },
+            ) => version.syntax_eq(other_version),
+            (
+                PragmaDirective::Experimental { feature },
+                PragmaDirective::Experimental {
+                    feature: other_feature,
+                },
+            ) => feature.syntax_eq(other_feature),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for PragmaDirective<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            PragmaDirective::Solidity { ranges } => {
+                state.write_u8(0);
+                ranges.syntax_hash(state);
+            }
+            PragmaDirective::Abicoder { version } => {
+                state.write_u8(1);
+                version.syntax_hash(state);
+            }
+            PragmaDirective::Experimental { feature } => {
+                state.write_u8(2);
+                feature.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for ImportDirective<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                ImportDirective::Global { source, alias },
+                ImportDirective::Global {
+                    source: other_source,
+                    alias: other_alias,
+                },
+            ) => source.syntax_eq(other_source) && alias.syntax_eq(other_alias),
+            (
+                ImportDirective::From {
+                    symbol,
+                    alias,
+                    source,
+                },
+                ImportDirective::From {
+                    symbol: other_symbol,
+                    alias: other_alias,
+                    source: other_source,
+                },
+            ) => {
+                symbol.syntax_eq(other_symbol)
+                    && alias.syntax_eq(other_alias)
+                    && source.syntax_eq(other_source)
+            }
+            (
+                ImportDirective::ManyFrom { imports, source },
+                ImportDirective::ManyFrom {
+                    imports: other_imports,
+                    source: other_source,
+                },
+            ) => imports.syntax_eq(other_imports) && source.syntax_eq(other_source),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for ImportDirective<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            ImportDirective::Global { source, alias } => {
+                state.write_u8(0);
+                source.syntax_hash(state);
+                alias.syntax_hash(state);
+            }
+            ImportDirective::From {
+                symbol,
+                alias,
+                source,
+            } => {
+                state.write_u8(1);
+                symbol.syntax_hash(state);
+                alias.syntax_hash(state);
+                source.syntax_hash(state);
+            }
+            ImportDirective::ManyFrom { imports, source } => {
+                state.write_u8(2);
+                imports.syntax_hash(state);
+                source.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for ContractPart<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                ContractPart::StateVariableDeclaration(a),
+                ContractPart::StateVariableDeclaration(b),
+            ) => a.syntax_eq(b),
+            (ContractPart::UsingForDeclaration(a), ContractPart::UsingForDeclaration(b)) => {
+                a.syntax_eq(b)
+            }
+            (ContractPart::StructDefinition(a), ContractPart::StructDefinition(b)) => {
+                a.syntax_eq(b)
+            }
+            (ContractPart::ModifierDefinition(a), ContractPart::ModifierDefinition(b)) => {
+                a.syntax_eq(b)
+            }
+            (ContractPart::FunctionDefinition(a), ContractPart::FunctionDefinition(b)) => {
+                a.syntax_eq(b)
+            }
+            (ContractPart::EventDefinition(a), ContractPart::EventDefinition(b)) => a.syntax_eq(b),
+            (ContractPart::EnumDefinition(a), ContractPart::EnumDefinition(b)) => a.syntax_eq(b),
+            (ContractPart::ErrorDefinition(a), ContractPart::ErrorDefinition(b)) => a.syntax_eq(b),
+            (
+                ContractPart::UserDefinedValueTypeDefinition(a),
+                ContractPart::UserDefinedValueTypeDefinition(b),
+            ) => a.syntax_eq(b),
+            (ContractPart::Unknown(a), ContractPart::Unknown(b)) => a.syntax_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for ContractPart<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            ContractPart::StateVariableDeclaration(value) => {
+                state.write_u8(0);
+                value.syntax_hash(state);
+            }
+            ContractPart::UsingForDeclaration(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            ContractPart::StructDefinition(value) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+            }
+            ContractPart::ModifierDefinition(value) => {
+                state.write_u8(3);
+                value.syntax_hash(state);
+            }
+            ContractPart::FunctionDefinition(value) => {
+                state.write_u8(4);
+                value.syntax_hash(state);
+            }
+            ContractPart::EventDefinition(value) => {
+                state.write_u8(5);
+                value.syntax_hash(state);
+            }
+            ContractPart::EnumDefinition(value) => {
+                state.write_u8(6);
+                value.syntax_hash(state);
+            }
+            ContractPart::ErrorDefinition(value) => {
+                state.write_u8(7);
+                value.syntax_hash(state);
+            }
+            ContractPart::UserDefinedValueTypeDefinition(value) => {
+                state.write_u8(8);
+                value.syntax_hash(state);
+            }
+            ContractPart::Unknown(value) => {
+                state.write_u8(9);
+                value.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for Statement<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Statement::Placeholder, Statement::Placeholder) => true,
+            (Statement::IfStatement(a), Statement::IfStatement(b)) => a.syntax_eq(b),
+            (Statement::WhileStatement(a), Statement::WhileStatement(b)) => a.syntax_eq(b),
+            (Statement::ForStatement(a), Statement::ForStatement(b)) => a.syntax_eq(b),
+            (Statement::BlockStatement(a), Statement::BlockStatement(b)) => a.syntax_eq(b),
+            (Statement::InlineAssemblyStatement(a), Statement::InlineAssemblyStatement(b)) => {
+                a.syntax_eq(b)
+            }
+            (Statement::DoWhileStatement(a), Statement::DoWhileStatement(b)) => a.syntax_eq(b),
+            (Statement::ContinueStatement, Statement::ContinueStatement) => true,
+            (Statement::BreakStatement, Statement::BreakStatement) => true,
+            (Statement::ReturnStatement(a), Statement::ReturnStatement(b)) => a.syntax_eq(b),
+            (Statement::ThrowStatement, Statement::ThrowStatement) => true,
+            (Statement::EmitStatement(a), Statement::EmitStatement(b)) => a.syntax_eq(b),
+            (Statement::RevertStatement(a), Statement::RevertStatement(b)) => a.syntax_eq(b),
+            (Statement::UncheckedBlock(a), Statement::UncheckedBlock(b)) => a.syntax_eq(b),
+            (Statement::TryStatement(a), Statement::TryStatement(b)) => a.syntax_eq(b),
+            (
+                Statement::VariableDefinitionStatement(a),
+                Statement::VariableDefinitionStatement(b),
+            ) => a.syntax_eq(b),
+            (
+                Statement::InferredDefinitionStatement(a),
+                Statement::InferredDefinitionStatement(b),
+            ) => a.syntax_eq(b),
+            (Statement::ExpressionStatement(a), Statement::ExpressionStatement(b)) => {
+                a.syntax_eq(b)
+            }
+            (Statement::Unknown(a), Statement::Unknown(b)) => a.syntax_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for Statement<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Statement::Placeholder => state.write_u8(0),
+            Statement::IfStatement(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            Statement::WhileStatement(value) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+            }
+            Statement::ForStatement(value) => {
+                state.write_u8(3);
+                value.syntax_hash(state);
+            }
+            Statement::BlockStatement(value) => {
+                state.write_u8(4);
+                value.syntax_hash(state);
+            }
+            Statement::InlineAssemblyStatement(value) => {
+                state.write_u8(5);
+                value.syntax_hash(state);
+            }
+            Statement::DoWhileStatement(value) => {
+                state.write_u8(6);
+                value.syntax_hash(state);
+            }
+            Statement::ContinueStatement => state.write_u8(7),
+            Statement::BreakStatement => state.write_u8(8),
+            Statement::ReturnStatement(value) => {
+                state.write_u8(9);
+                value.syntax_hash(state);
+            }
+            Statement::ThrowStatement => state.write_u8(10),
+            Statement::EmitStatement(value) => {
+                state.write_u8(11);
+                value.syntax_hash(state);
+            }
+            Statement::RevertStatement(value) => {
+                state.write_u8(12);
+                value.syntax_hash(state);
+            }
+            Statement::UncheckedBlock(value) => {
+                state.write_u8(13);
+                value.syntax_hash(state);
+            }
+            Statement::TryStatement(value) => {
+                state.write_u8(14);
+                value.syntax_hash(state);
+            }
+            Statement::VariableDefinitionStatement(value) => {
+                state.write_u8(15);
+                value.syntax_hash(state);
+            }
+            Statement::InferredDefinitionStatement(value) => {
+                state.write_u8(16);
+                value.syntax_hash(state);
+            }
+            Statement::ExpressionStatement(value) => {
+                state.write_u8(17);
+                value.syntax_hash(state);
+            }
+            Statement::Unknown(value) => {
+                state.write_u8(18);
+                value.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for SimpleStatement<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                SimpleStatement::VariableDefinitionStatement(a),
+                SimpleStatement::VariableDefinitionStatement(b),
+            ) => a.syntax_eq(b),
+            (
+                SimpleStatement::InferredDefinitionStatement(a),
+                SimpleStatement::InferredDefinitionStatement(b),
+            ) => a.syntax_eq(b),
+            (SimpleStatement::ExpressionStatement(a), SimpleStatement::ExpressionStatement(b)) => {
+                a.syntax_eq(b)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for SimpleStatement<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            SimpleStatement::VariableDefinitionStatement(value) => {
+                state.write_u8(0);
+                value.syntax_hash(state);
+            }
+            SimpleStatement::InferredDefinitionStatement(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            SimpleStatement::ExpressionStatement(value) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for Expression<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Expression::ThisExpression, Expression::ThisExpression) => true,
+            (Expression::IdentifierExpression(a), Expression::IdentifierExpression(b)) => {
+                a.syntax_eq(b)
+            }
+            (Expression::PrimitiveExpression(a), Expression::PrimitiveExpression(b)) => {
+                a.syntax_eq(b)
+            }
+            (Expression::PrefixExpression(a), Expression::PrefixExpression(b)) => a.syntax_eq(b),
+            (Expression::PostfixExpression(a), Expression::PostfixExpression(b)) => a.syntax_eq(b),
+            (Expression::BinaryExpression(a), Expression::BinaryExpression(b)) => a.syntax_eq(b),
+            (Expression::AssignmentExpression(a), Expression::AssignmentExpression(b)) => {
+                a.syntax_eq(b)
+            }
+            (Expression::TupleExpression(a), Expression::TupleExpression(b)) => a.syntax_eq(b),
+            (Expression::CallExpression(a), Expression::CallExpression(b)) => a.syntax_eq(b),
+            (Expression::CallOptionsExpression(a), Expression::CallOptionsExpression(b)) => {
+                a.syntax_eq(b)
+            }
+            (Expression::MemberAccessExpression(a), Expression::MemberAccessExpression(b)) => {
+                a.syntax_eq(b)
+            }
+            (Expression::IndexAccessExpression(a), Expression::IndexAccessExpression(b)) => {
+                a.syntax_eq(b)
+            }
+            (Expression::ConditionalExpression(a), Expression::ConditionalExpression(b)) => {
+                a.syntax_eq(b)
+            }
+            (Expression::ElementaryTypeExpression(a), Expression::ElementaryTypeExpression(b)) => {
+                a.syntax_eq(b)
+            }
+            (Expression::NewExpression(a), Expression::NewExpression(b)) => a.syntax_eq(b),
+            (Expression::TypeExpression(a), Expression::TypeExpression(b)) => a.syntax_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for Expression<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Expression::ThisExpression => state.write_u8(0),
+            Expression::IdentifierExpression(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            Expression::PrimitiveExpression(value) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+            }
+            Expression::PrefixExpression(value) => {
+                state.write_u8(3);
+                value.syntax_hash(state);
+            }
+            Expression::PostfixExpression(value) => {
+                state.write_u8(4);
+                value.syntax_hash(state);
+            }
+            Expression::BinaryExpression(value) => {
+                state.write_u8(5);
+                value.syntax_hash(state);
+            }
+            Expression::AssignmentExpression(value) => {
+                state.write_u8(6);
+                value.syntax_hash(state);
+            }
+            Expression::TupleExpression(value) => {
+                state.write_u8(7);
+                value.syntax_hash(state);
+            }
+            Expression::CallExpression(value) => {
+                state.write_u8(8);
+                value.syntax_hash(state);
+            }
+            Expression::CallOptionsExpression(value) => {
+                state.write_u8(9);
+                value.syntax_hash(state);
+            }
+            Expression::MemberAccessExpression(value) => {
+                state.write_u8(10);
+                value.syntax_hash(state);
+            }
+            Expression::IndexAccessExpression(value) => {
+                state.write_u8(11);
+                value.syntax_hash(state);
+            }
+            Expression::ConditionalExpression(value) => {
+                state.write_u8(12);
+                value.syntax_hash(state);
+            }
+            Expression::ElementaryTypeExpression(value) => {
+                state.write_u8(13);
+                value.syntax_hash(state);
+            }
+            Expression::NewExpression(value) => {
+                state.write_u8(14);
+                value.syntax_hash(state);
+            }
+            Expression::TypeExpression(value) => {
+                state.write_u8(15);
+                value.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for Primitive<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Primitive::Bool(a), Primitive::Bool(b)) => a.syntax_eq(b),
+            (Primitive::HexNumber(a), Primitive::HexNumber(b)) => a.syntax_eq(b),
+            (Primitive::IntegerNumber(a, a_unit), Primitive::IntegerNumber(b, b_unit)) => {
+                a.syntax_eq(b) && a_unit.syntax_eq(b_unit)
+            }
+            (Primitive::RationalNumber(a), Primitive::RationalNumber(b)) => a.syntax_eq(b),
+            (Primitive::String(a), Primitive::String(b)) => a.syntax_eq(b),
+            (Primitive::HexString(a), Primitive::HexString(b)) => a.syntax_eq(b),
+            (Primitive::UnicodeString(a), Primitive::UnicodeString(b)) => a.syntax_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for Primitive<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Primitive::Bool(value) => {
+                state.write_u8(0);
+                value.syntax_hash(state);
+            }
+            Primitive::HexNumber(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            Primitive::IntegerNumber(value, unit) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+                unit.syntax_hash(state);
+            }
+            Primitive::RationalNumber(value) => {
+                state.write_u8(3);
+                value.syntax_hash(state);
+            }
+            Primitive::String(value) => {
+                state.write_u8(4);
+                value.syntax_hash(state);
+            }
+            Primitive::HexString(value) => {
+                state.write_u8(5);
+                value.syntax_hash(state);
+            }
+            Primitive::UnicodeString(value) => {
+                state.write_u8(6);
+                value.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for CallArguments<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (CallArguments::Positional(a), CallArguments::Positional(b)) => a.syntax_eq(b),
+            (CallArguments::Named(a), CallArguments::Named(b)) => a.syntax_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for CallArguments<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            CallArguments::Positional(value) => {
+                state.write_u8(0);
+                value.syntax_hash(state);
+            }
+            CallArguments::Named(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for TypeName<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TypeName::ElementaryTypeName(a), TypeName::ElementaryTypeName(b)) => a.syntax_eq(b),
+            (TypeName::UserDefinedTypeName(a), TypeName::UserDefinedTypeName(b)) => a.syntax_eq(b),
+            (TypeName::Mapping(a), TypeName::Mapping(b)) => a.syntax_eq(b),
+            (TypeName::ArrayTypeName(a), TypeName::ArrayTypeName(b)) => a.syntax_eq(b),
+            (TypeName::FunctionTypeName, TypeName::FunctionTypeName) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for TypeName<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            TypeName::ElementaryTypeName(value) => {
+                state.write_u8(0);
+                value.syntax_hash(state);
+            }
+            TypeName::UserDefinedTypeName(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            TypeName::Mapping(value) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+            }
+            TypeName::ArrayTypeName(value) => {
+                state.write_u8(3);
+                value.syntax_hash(state);
+            }
+            TypeName::FunctionTypeName => state.write_u8(4),
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for AssemblyItem<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (AssemblyItem::InlineAssemblyBlock(a), AssemblyItem::InlineAssemblyBlock(b)) => {
+                a.syntax_eq(b)
+            }
+            (AssemblyItem::AssemblyLocalBinding(a), AssemblyItem::AssemblyLocalBinding(b)) => {
+                a.syntax_eq(b)
+            }
+            (AssemblyItem::AssemblyAssignment(a), AssemblyItem::AssemblyAssignment(b)) => {
+                a.syntax_eq(b)
+            }
+            (AssemblyItem::AssemblyIf(a), AssemblyItem::AssemblyIf(b)) => a.syntax_eq(b),
+            (AssemblyItem::AssemblySwitch(a), AssemblyItem::AssemblySwitch(b)) => a.syntax_eq(b),
+            (AssemblyItem::AssemblyFor(a), AssemblyItem::AssemblyFor(b)) => a.syntax_eq(b),
+            (
+                AssemblyItem::AssemblyFunctionDefinition(a),
+                AssemblyItem::AssemblyFunctionDefinition(b),
+            ) => a.syntax_eq(b),
+            (
+                AssemblyItem::FunctionalAssemblyExpression(a),
+                AssemblyItem::FunctionalAssemblyExpression(b),
+            ) => a.syntax_eq(b),
+            (AssemblyItem::Leave, AssemblyItem::Leave) => true,
+            (AssemblyItem::Break, AssemblyItem::Break) => true,
+            (AssemblyItem::Continue, AssemblyItem::Continue) => true,
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for AssemblyItem<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            AssemblyItem::InlineAssemblyBlock(value) => {
+                state.write_u8(0);
+                value.syntax_hash(state);
+            }
+            AssemblyItem::AssemblyLocalBinding(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            AssemblyItem::AssemblyAssignment(value) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+            }
+            AssemblyItem::AssemblyIf(value) => {
+                state.write_u8(3);
+                value.syntax_hash(state);
+            }
+            AssemblyItem::AssemblySwitch(value) => {
+                state.write_u8(4);
+                value.syntax_hash(state);
+            }
+            AssemblyItem::AssemblyFor(value) => {
+                state.write_u8(5);
+                value.syntax_hash(state);
+            }
+            AssemblyItem::AssemblyFunctionDefinition(value) => {
+                state.write_u8(6);
+                value.syntax_hash(state);
+            }
+            AssemblyItem::FunctionalAssemblyExpression(value) => {
+                state.write_u8(7);
+                value.syntax_hash(state);
+            }
+            AssemblyItem::Leave => state.write_u8(8),
+            AssemblyItem::Break => state.write_u8(9),
+            AssemblyItem::Continue => state.write_u8(10),
+        }
+    }
+}
+
+impl<'ast> SyntaxEq for AssemblyExpression<'ast> {
+    fn syntax_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (AssemblyExpression::Identifier(a), AssemblyExpression::Identifier(b)) => {
+                a.syntax_eq(b)
+            }
+            (AssemblyExpression::Literal(a), AssemblyExpression::Literal(b)) => a.syntax_eq(b),
+            (
+                AssemblyExpression::FunctionalAssemblyExpression(a),
+                AssemblyExpression::FunctionalAssemblyExpression(b),
+            ) => a.syntax_eq(b),
+            _ => false,
+        }
+    }
+}
+
+impl<'ast> SyntaxHash for AssemblyExpression<'ast> {
+    fn syntax_hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            AssemblyExpression::Identifier(value) => {
+                state.write_u8(0);
+                value.syntax_hash(state);
+            }
+            AssemblyExpression::Literal(value) => {
+                state.write_u8(1);
+                value.syntax_hash(state);
+            }
+            AssemblyExpression::FunctionalAssemblyExpression(value) => {
+                state.write_u8(2);
+                value.syntax_hash(state);
+            }
+        }
+    }
+}
+
+impl_structs! {
+    VersionRange { comparators }
+    ConstantDeclaration { type_name, name, init }
+    Import { symbol, alias }
+    ContractDefinition { name, inherits, body }
+    StateVariableDeclaration { type_name, visibility, constant, name, init }
+    UsingForDeclaration { id, functions, type_name, global }
+    UsingForFunction { id, operator }
+    StructDefinition { name, body }
+    ModifierDefinition { name, params, block }
+    EventDefinition { anonymous, name, params }
+    IndexedParameter { type_name, indexed, name }
+    EnumDefinition { name, variants }
+    UserDefinedValueTypeDefinition { name, underlying }
+    ErrorDefinition { name, params }
+    FunctionDefinition { name, params, visibility, mutability, modifiers, returns, block }
+    ModifierInvocation { id, arguments }
+    Parameter { type_name, location, name }
+    IfStatement { test, consequent, alternate }
+    WhileStatement { test, body }
+    ForStatement { init, test, update, body }
+    Block { body }
+    InlineAssemblyStatement { string, flags, block }
+    DoWhileStatement { body, test }
+    ReturnStatement { value }
+    EmitStatement { event }
+    RevertStatement { error }
+    UncheckedBlock { block }
+    TryStatement { call, returns, body, clauses }
+    CatchClause { id, params, body }
+    VariableDefinitionStatement { declaration, init }
+    InferredDefinitionStatement { ids, init }
+    PrefixExpression { operator, operand }
+    PostfixExpression { operand, operator }
+    BinaryExpression { left, operator, right }
+    AssignmentExpression { left, operator, right }
+    TupleExpression { expressions }
+    CallExpression { callee, arguments }
+    CallOptionsExpression { callee, options }
+    NamedArgument { name, value }
+    MemberAccessExpression { object, member }
+    IndexAccessExpression { array, index }
+    ConditionalExpression { test, consequent, alternate }
+    NewExpression { type_name }
+    TypeExpression { type_name }
+    VariableDeclaration { type_name, location, id }
+    Mapping { from, to }
+    ArrayTypeName { base, length }
+    InlineAssemblyBlock { items }
+    AssemblyLocalBinding { ids, init }
+    AssemblyAssignment { ids, init }
+    AssemblyIf { test, body }
+    AssemblySwitch { test, cases, default }
+    AssemblyCase { value, body }
+    AssemblyFor { init, test, update, body }
+    AssemblyFunctionDefinition { name, params, returns, body }
+    FunctionalAssemblyExpression { id, arguments }
+    Unknown { raw }
+}
@@ -1,30 +1,36 @@
-use crate::{Identifier, IdentifierNode, Node, NodeList};
+use crate::{ExpressionNode, Identifier, IdentifierNode, Node, NodeList};
 
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TypeName<'ast> {
     ElementaryTypeName(ElementaryTypeName),
     UserDefinedTypeName(Identifier<'ast>),
     Mapping(Mapping<'ast>),
-    ArrayTypeName,
+    ArrayTypeName(ArrayTypeName<'ast>),
     FunctionTypeName,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VariableDeclaration<'ast> {
     pub type_name: TypeNameNode<'ast>,
     pub location: Option<Node<'ast, StorageLocation>>,
     pub id: IdentifierNode<'ast>,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StorageLocation {
     Memory,
     Storage,
+    Calldata,
 }
 
-#[derive(Clone, Copy, PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ElementaryTypeName {
     Address,
+    AddressPayable,
     Bool,
     String,
     Bytes,
@@ -36,11 +42,20 @@ pub enum ElementaryTypeName {
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Mapping<'ast> {
     pub from: ElementaryTypeNameNode<'ast>,
     pub to: TypeNameNode<'ast>,
 }
 
+/// `T[]` or `T[length]`
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ArrayTypeName<'ast> {
+    pub base: TypeNameNode<'ast>,
+    pub length: Option<ExpressionNode<'ast>>,
+}
+
 pub type TypeNameNode<'ast> = Node<'ast, TypeName<'ast>>;
 pub type ElementaryTypeNameNode<'ast> = Node<'ast, ElementaryTypeName>;
 pub type VariableDeclarationNode<'ast> = Node<'ast, VariableDeclaration<'ast>>;
@@ -58,4 +73,5 @@ impl<'ast> From<ElementaryTypeName> for TypeName<'ast> {
 impl_from! {
     Identifier => TypeName::UserDefinedTypeName,
     Mapping => TypeName::Mapping,
+    ArrayTypeName => TypeName::ArrayTypeName,
 }
@@ -0,0 +1,1069 @@
+use crate::*;
+
+/// Read only traversal of the AST.
+///
+/// Every `visit_*` method defaults to the matching `walk_*` function, which visits the
+/// children of the node in source order. Overriding a method and calling the `walk_*`
+/// function from it keeps descending, leaving it out skips the children.
+pub trait Visitor<'ast>: Sized {
+    fn visit_source_unit(&mut self, unit: SourceUnitNode<'ast>) {
+        walk_source_unit(self, unit)
+    }
+
+    fn visit_pragma_directive(&mut self, pragma: &PragmaDirective<'ast>) {
+        walk_pragma_directive(self, pragma)
+    }
+
+    fn visit_import_directive(&mut self, import: &ImportDirective<'ast>) {
+        walk_import_directive(self, import)
+    }
+
+    fn visit_constant_declaration(&mut self, constant: &ConstantDeclaration<'ast>) {
+        walk_constant_declaration(self, constant)
+    }
+
+    fn visit_contract_definition(&mut self, contract: &ContractDefinition<'ast>) {
+        walk_contract_definition(self, contract)
+    }
+
+    fn visit_contract_part(&mut self, part: ContractPartNode<'ast>) {
+        walk_contract_part(self, part)
+    }
+
+    fn visit_state_variable_declaration(&mut self, variable: &StateVariableDeclaration<'ast>) {
+        walk_state_variable_declaration(self, variable)
+    }
+
+    fn visit_using_for_declaration(&mut self, using: &UsingForDeclaration<'ast>) {
+        walk_using_for_declaration(self, using)
+    }
+
+    fn visit_using_for_function(&mut self, function: Node<'ast, UsingForFunction<'ast>>) {
+        walk_using_for_function(self, function)
+    }
+
+    fn visit_struct_definition(&mut self, definition: &StructDefinition<'ast>) {
+        walk_struct_definition(self, definition)
+    }
+
+    fn visit_modifier_definition(&mut self, modifier: &ModifierDefinition<'ast>) {
+        walk_modifier_definition(self, modifier)
+    }
+
+    fn visit_event_definition(&mut self, event: &EventDefinition<'ast>) {
+        walk_event_definition(self, event)
+    }
+
+    fn visit_indexed_parameter(&mut self, parameter: Node<'ast, IndexedParameter<'ast>>) {
+        walk_indexed_parameter(self, parameter)
+    }
+
+    fn visit_enum_definition(&mut self, definition: &EnumDefinition<'ast>) {
+        walk_enum_definition(self, definition)
+    }
+
+    fn visit_error_definition(&mut self, definition: &ErrorDefinition<'ast>) {
+        walk_error_definition(self, definition)
+    }
+
+    fn visit_user_defined_value_type_definition(
+        &mut self,
+        definition: &UserDefinedValueTypeDefinition<'ast>,
+    ) {
+        walk_user_defined_value_type_definition(self, definition)
+    }
+
+    fn visit_function_definition(&mut self, function: &FunctionDefinition<'ast>) {
+        walk_function_definition(self, function)
+    }
+
+    fn visit_modifier_invocation(&mut self, invocation: Node<'ast, ModifierInvocation<'ast>>) {
+        walk_modifier_invocation(self, invocation)
+    }
+
+    fn visit_parameter(&mut self, parameter: Node<'ast, Parameter<'ast>>) {
+        walk_parameter(self, parameter)
+    }
+
+    fn visit_statement(&mut self, statement: StatementNode<'ast>) {
+        walk_statement(self, statement)
+    }
+
+    fn visit_simple_statement(&mut self, statement: SimpleStatementNode<'ast>) {
+        walk_simple_statement(self, statement)
+    }
+
+    fn visit_block(&mut self, block: &Block<'ast>) {
+        walk_block(self, block)
+    }
+
+    fn visit_if_statement(&mut self, statement: &IfStatement<'ast>) {
+        walk_if_statement(self, statement)
+    }
+
+    fn visit_while_statement(&mut self, statement: &WhileStatement<'ast>) {
+        walk_while_statement(self, statement)
+    }
+
+    fn visit_for_statement(&mut self, statement: &ForStatement<'ast>) {
+        walk_for_statement(self, statement)
+    }
+
+    fn visit_do_while_statement(&mut self, statement: &DoWhileStatement<'ast>) {
+        walk_do_while_statement(self, statement)
+    }
+
+    fn visit_return_statement(&mut self, statement: &ReturnStatement<'ast>) {
+        walk_return_statement(self, statement)
+    }
+
+    fn visit_emit_statement(&mut self, statement: &EmitStatement<'ast>) {
+        walk_emit_statement(self, statement)
+    }
+
+    fn visit_revert_statement(&mut self, statement: &RevertStatement<'ast>) {
+        walk_revert_statement(self, statement)
+    }
+
+    fn visit_unchecked_block(&mut self, unchecked: &UncheckedBlock<'ast>) {
+        walk_unchecked_block(self, unchecked)
+    }
+
+    fn visit_try_statement(&mut self, statement: &TryStatement<'ast>) {
+        walk_try_statement(self, statement)
+    }
+
+    fn visit_catch_clause(&mut self, clause: CatchClauseNode<'ast>) {
+        walk_catch_clause(self, clause)
+    }
+
+    fn visit_variable_definition_statement(
+        &mut self,
+        statement: &VariableDefinitionStatement<'ast>,
+    ) {
+        walk_variable_definition_statement(self, statement)
+    }
+
+    fn visit_inferred_definition_statement(
+        &mut self,
+        statement: &InferredDefinitionStatement<'ast>,
+    ) {
+        walk_inferred_definition_statement(self, statement)
+    }
+
+    fn visit_inline_assembly_statement(&mut self, statement: &InlineAssemblyStatement<'ast>) {
+        walk_inline_assembly_statement(self, statement)
+    }
+
+    fn visit_type_name(&mut self, type_name: TypeNameNode<'ast>) {
+        walk_type_name(self, type_name)
+    }
+
+    fn visit_variable_declaration(&mut self, declaration: VariableDeclarationNode<'ast>) {
+        walk_variable_declaration(self, declaration)
+    }
+
+    fn visit_mapping(&mut self, mapping: &Mapping<'ast>) {
+        walk_mapping(self, mapping)
+    }
+
+    fn visit_array_type_name(&mut self, array: &ArrayTypeName<'ast>) {
+        walk_array_type_name(self, array)
+    }
+
+    fn visit_expression(&mut self, expression: ExpressionNode<'ast>) {
+        walk_expression(self, expression)
+    }
+
+    fn visit_prefix_expression(&mut self, prefix: &PrefixExpression<'ast>) {
+        walk_prefix_expression(self, prefix)
+    }
+
+    fn visit_postfix_expression(&mut self, postfix: &PostfixExpression<'ast>) {
+        walk_postfix_expression(self, postfix)
+    }
+
+    fn visit_binary_expression(&mut self, binary: &BinaryExpression<'ast>) {
+        walk_binary_expression(self, binary)
+    }
+
+    fn visit_assignment_expression(&mut self, assignment: &AssignmentExpression<'ast>) {
+        walk_assignment_expression(self, assignment)
+    }
+
+    fn visit_tuple_expression(&mut self, tuple: &TupleExpression<'ast>) {
+        walk_tuple_expression(self, tuple)
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'ast>) {
+        walk_call_expression(self, call)
+    }
+
+    fn visit_call_options_expression(&mut self, call: &CallOptionsExpression<'ast>) {
+        walk_call_options_expression(self, call)
+    }
+
+    fn visit_named_argument(&mut self, argument: NamedArgumentNode<'ast>) {
+        walk_named_argument(self, argument)
+    }
+
+    fn visit_member_access_expression(&mut self, access: &MemberAccessExpression<'ast>) {
+        walk_member_access_expression(self, access)
+    }
+
+    fn visit_index_access_expression(&mut self, access: &IndexAccessExpression<'ast>) {
+        walk_index_access_expression(self, access)
+    }
+
+    fn visit_conditional_expression(&mut self, conditional: &ConditionalExpression<'ast>) {
+        walk_conditional_expression(self, conditional)
+    }
+
+    fn visit_new_expression(&mut self, new: &NewExpression<'ast>) {
+        walk_new_expression(self, new)
+    }
+
+    fn visit_type_expression(&mut self, expression: &TypeExpression<'ast>) {
+        walk_type_expression(self, expression)
+    }
+
+    fn visit_inline_assembly_block(&mut self, block: &InlineAssemblyBlock<'ast>) {
+        walk_inline_assembly_block(self, block)
+    }
+
+    fn visit_assembly_item(&mut self, item: AssemblyItemNode<'ast>) {
+        walk_assembly_item(self, item)
+    }
+
+    fn visit_assembly_expression(&mut self, expression: AssemblyExpressionNode<'ast>) {
+        walk_assembly_expression(self, expression)
+    }
+
+    fn visit_assembly_local_binding(&mut self, binding: &AssemblyLocalBinding<'ast>) {
+        walk_assembly_local_binding(self, binding)
+    }
+
+    fn visit_assembly_assignment(&mut self, assignment: &AssemblyAssignment<'ast>) {
+        walk_assembly_assignment(self, assignment)
+    }
+
+    fn visit_assembly_if(&mut self, statement: &AssemblyIf<'ast>) {
+        walk_assembly_if(self, statement)
+    }
+
+    fn visit_assembly_switch(&mut self, switch: &AssemblySwitch<'ast>) {
+        walk_assembly_switch(self, switch)
+    }
+
+    fn visit_assembly_case(&mut self, case: AssemblyCaseNode<'ast>) {
+        walk_assembly_case(self, case)
+    }
+
+    fn visit_assembly_for(&mut self, statement: &AssemblyFor<'ast>) {
+        walk_assembly_for(self, statement)
+    }
+
+    fn visit_assembly_function_definition(&mut self, function: &AssemblyFunctionDefinition<'ast>) {
+        walk_assembly_function_definition(self, function)
+    }
+
+    fn visit_functional_assembly_expression(
+        &mut self,
+        expression: &FunctionalAssemblyExpression<'ast>,
+    ) {
+        walk_functional_assembly_expression(self, expression)
+    }
+
+    /// Identifiers that are nodes of their own, like names of declarations and
+    /// members. Identifiers used as expressions are visited as expressions.
+    fn visit_identifier(&mut self, _identifier: IdentifierNode<'ast>) {}
+
+    fn visit_primitive(&mut self, _primitive: &Primitive<'ast>) {}
+
+    fn visit_unknown(&mut self, _unknown: &Unknown<'ast>) {}
+}
+
+pub fn walk_program<'ast, V: Visitor<'ast>>(visitor: &mut V, program: &Program<'ast>) {
+    for unit in program.body().iter() {
+        visitor.visit_source_unit(*unit);
+    }
+}
+
+pub fn walk_source_unit<'ast, V: Visitor<'ast>>(visitor: &mut V, unit: SourceUnitNode<'ast>) {
+    match unit.value {
+        SourceUnit::PragmaDirective(pragma) => visitor.visit_pragma_directive(&pragma),
+        SourceUnit::ImportDirective(import) => visitor.visit_import_directive(&import),
+        SourceUnit::ContractDefinition(contract) => visitor.visit_contract_definition(&contract),
+        SourceUnit::StructDefinition(definition) => visitor.visit_struct_definition(&definition),
+        SourceUnit::EnumDefinition(definition) => visitor.visit_enum_definition(&definition),
+        SourceUnit::FunctionDefinition(function) => visitor.visit_function_definition(&function),
+        SourceUnit::ErrorDefinition(definition) => visitor.visit_error_definition(&definition),
+        SourceUnit::ConstantDeclaration(constant) => visitor.visit_constant_declaration(&constant),
+        SourceUnit::UserDefinedValueTypeDefinition(definition) => {
+            visitor.visit_user_defined_value_type_definition(&definition)
+        }
+        SourceUnit::UsingForDeclaration(using) => visitor.visit_using_for_declaration(&using),
+        SourceUnit::Unknown(unknown) => visitor.visit_unknown(&unknown),
+    }
+}
+
+pub fn walk_pragma_directive<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    pragma: &PragmaDirective<'ast>,
+) {
+    match *pragma {
+        PragmaDirective::Solidity { .. } => {}
+        PragmaDirective::Abicoder { version } => visitor.visit_identifier(version),
+        PragmaDirective::Experimental { feature } => visitor.visit_identifier(feature),
+    }
+}
+
+pub fn walk_import_directive<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    import: &ImportDirective<'ast>,
+) {
+    match *import {
+        ImportDirective::Global { alias, .. } => walk_identifiers(visitor, alias),
+        ImportDirective::From { symbol, alias, .. } => {
+            walk_identifiers(visitor, symbol);
+            walk_identifiers(visitor, alias);
+        }
+        ImportDirective::ManyFrom { imports, .. } => {
+            for import in imports.iter() {
+                visitor.visit_identifier(import.value.symbol);
+                walk_identifiers(visitor, import.value.alias);
+            }
+        }
+    }
+}
+
+pub fn walk_constant_declaration<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    constant: &ConstantDeclaration<'ast>,
+) {
+    visitor.visit_type_name(constant.type_name);
+    visitor.visit_identifier(constant.name);
+    visitor.visit_expression(constant.init);
+}
+
+pub fn walk_contract_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    contract: &ContractDefinition<'ast>,
+) {
+    visitor.visit_identifier(contract.name);
+    walk_identifiers(visitor, contract.inherits.iter().copied());
+
+    for part in contract.body.iter() {
+        visitor.visit_contract_part(*part);
+    }
+}
+
+pub fn walk_contract_part<'ast, V: Visitor<'ast>>(visitor: &mut V, part: ContractPartNode<'ast>) {
+    match part.value {
+        ContractPart::StateVariableDeclaration(variable) => {
+            visitor.visit_state_variable_declaration(&variable)
+        }
+        ContractPart::UsingForDeclaration(using) => visitor.visit_using_for_declaration(&using),
+        ContractPart::StructDefinition(definition) => visitor.visit_struct_definition(&definition),
+        ContractPart::ModifierDefinition(modifier) => visitor.visit_modifier_definition(&modifier),
+        ContractPart::FunctionDefinition(function) => visitor.visit_function_definition(&function),
+        ContractPart::EventDefinition(event) => visitor.visit_event_definition(&event),
+        ContractPart::EnumDefinition(definition) => visitor.visit_enum_definition(&definition),
+        ContractPart::ErrorDefinition(definition) => visitor.visit_error_definition(&definition),
+        ContractPart::UserDefinedValueTypeDefinition(definition) => {
+            visitor.visit_user_defined_value_type_definition(&definition)
+        }
+        ContractPart::Unknown(unknown) => visitor.visit_unknown(&unknown),
+    }
+}
+
+pub fn walk_state_variable_declaration<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    variable: &StateVariableDeclaration<'ast>,
+) {
+    visitor.visit_type_name(variable.type_name);
+    visitor.visit_identifier(variable.name);
+    walk_expressions(visitor, variable.init);
+}
+
+pub fn walk_using_for_declaration<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    using: &UsingForDeclaration<'ast>,
+) {
+    walk_identifiers(visitor, using.id);
+
+    for function in using.functions.iter() {
+        visitor.visit_using_for_function(*function);
+    }
+
+    if let Some(type_name) = using.type_name {
+        visitor.visit_type_name(type_name);
+    }
+}
+
+pub fn walk_using_for_function<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    function: Node<'ast, UsingForFunction<'ast>>,
+) {
+    visitor.visit_identifier(function.value.id);
+}
+
+pub fn walk_struct_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    definition: &StructDefinition<'ast>,
+) {
+    visitor.visit_identifier(definition.name);
+
+    for declaration in definition.body.iter() {
+        visitor.visit_variable_declaration(*declaration);
+    }
+}
+
+pub fn walk_modifier_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    modifier: &ModifierDefinition<'ast>,
+) {
+    visitor.visit_identifier(modifier.name);
+    walk_parameters(visitor, modifier.params);
+    visitor.visit_block(&modifier.block.value);
+}
+
+pub fn walk_event_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    event: &EventDefinition<'ast>,
+) {
+    visitor.visit_identifier(event.name);
+
+    for parameter in event.params.iter() {
+        visitor.visit_indexed_parameter(*parameter);
+    }
+}
+
+pub fn walk_indexed_parameter<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    parameter: Node<'ast, IndexedParameter<'ast>>,
+) {
+    visitor.visit_type_name(parameter.value.type_name);
+    walk_identifiers(visitor, parameter.value.name);
+}
+
+pub fn walk_enum_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    definition: &EnumDefinition<'ast>,
+) {
+    visitor.visit_identifier(definition.name);
+    walk_identifiers(visitor, definition.variants.iter().copied());
+}
+
+pub fn walk_error_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    definition: &ErrorDefinition<'ast>,
+) {
+    visitor.visit_identifier(definition.name);
+    walk_parameters(visitor, definition.params);
+}
+
+pub fn walk_user_defined_value_type_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    definition: &UserDefinedValueTypeDefinition<'ast>,
+) {
+    visitor.visit_identifier(definition.name);
+}
+
+pub fn walk_function_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    function: &FunctionDefinition<'ast>,
+) {
+    walk_identifiers(visitor, function.name);
+    walk_parameters(visitor, function.params);
+
+    for invocation in function.modifiers.iter() {
+        visitor.visit_modifier_invocation(*invocation);
+    }
+
+    walk_parameters(visitor, function.returns);
+
+    if let Some(block) = function.block {
+        visitor.visit_block(&block.value);
+    }
+}
+
+pub fn walk_modifier_invocation<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    invocation: Node<'ast, ModifierInvocation<'ast>>,
+) {
+    visitor.visit_identifier(invocation.value.id);
+    walk_expressions(visitor, invocation.value.arguments.iter().copied());
+}
+
+pub fn walk_parameter<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    parameter: Node<'ast, Parameter<'ast>>,
+) {
+    visitor.visit_type_name(parameter.value.type_name);
+    walk_identifiers(visitor, parameter.value.name);
+}
+
+pub fn walk_statement<'ast, V: Visitor<'ast>>(visitor: &mut V, statement: StatementNode<'ast>) {
+    match statement.value {
+        Statement::IfStatement(statement) => visitor.visit_if_statement(&statement),
+        Statement::WhileStatement(statement) => visitor.visit_while_statement(&statement),
+        Statement::ForStatement(statement) => visitor.visit_for_statement(&statement),
+        Statement::BlockStatement(block) => visitor.visit_block(&block),
+        Statement::InlineAssemblyStatement(statement) => {
+            visitor.visit_inline_assembly_statement(&statement)
+        }
+        Statement::DoWhileStatement(statement) => visitor.visit_do_while_statement(&statement),
+        Statement::ReturnStatement(statement) => visitor.visit_return_statement(&statement),
+        Statement::EmitStatement(statement) => visitor.visit_emit_statement(&statement),
+        Statement::RevertStatement(statement) => visitor.visit_revert_statement(&statement),
+        Statement::UncheckedBlock(unchecked) => visitor.visit_unchecked_block(&unchecked),
+        Statement::TryStatement(statement) => visitor.visit_try_statement(&statement),
+        Statement::VariableDefinitionStatement(statement) => {
+            visitor.visit_variable_definition_statement(&statement)
+        }
+        Statement::InferredDefinitionStatement(statement) => {
+            visitor.visit_inferred_definition_statement(&statement)
+        }
+        Statement::ExpressionStatement(expression) => visitor.visit_expression(expression),
+        Statement::Unknown(unknown) => visitor.visit_unknown(&unknown),
+        Statement::Placeholder
+        | Statement::ContinueStatement
+        | Statement::BreakStatement
+        | Statement::ThrowStatement => {}
+    }
+}
+
+pub fn walk_simple_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: SimpleStatementNode<'ast>,
+) {
+    match statement.value {
+        SimpleStatement::VariableDefinitionStatement(statement) => {
+            visitor.visit_variable_definition_statement(&statement)
+        }
+        SimpleStatement::InferredDefinitionStatement(statement) => {
+            visitor.visit_inferred_definition_statement(&statement)
+        }
+        SimpleStatement::ExpressionStatement(expression) => visitor.visit_expression(expression),
+    }
+}
+
+pub fn walk_block<'ast, V: Visitor<'ast>>(visitor: &mut V, block: &Block<'ast>) {
+    for statement in block.body.iter() {
+        visitor.visit_statement(*statement);
+    }
+}
+
+pub fn walk_if_statement<'ast, V: Visitor<'ast>>(visitor: &mut V, statement: &IfStatement<'ast>) {
+    visitor.visit_expression(statement.test);
+    visitor.visit_statement(statement.consequent);
+
+    if let Some(alternate) = statement.alternate {
+        visitor.visit_statement(alternate);
+    }
+}
+
+pub fn walk_while_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: &WhileStatement<'ast>,
+) {
+    visitor.visit_expression(statement.test);
+    visitor.visit_statement(statement.body);
+}
+
+pub fn walk_for_statement<'ast, V: Visitor<'ast>>(visitor: &mut V, statement: &ForStatement<'ast>) {
+    if let Some(init) = statement.init {
+        visitor.visit_simple_statement(init);
+    }
+
+    walk_expressions(visitor, statement.test);
+    walk_expressions(visitor, statement.update);
+    visitor.visit_statement(statement.body);
+}
+
+pub fn walk_do_while_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: &DoWhileStatement<'ast>,
+) {
+    visitor.visit_statement(statement.body);
+    visitor.visit_expression(statement.test);
+}
+
+pub fn walk_return_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: &ReturnStatement<'ast>,
+) {
+    walk_expressions(visitor, statement.value);
+}
+
+pub fn walk_emit_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: &EmitStatement<'ast>,
+) {
+    visitor.visit_expression(statement.event);
+}
+
+pub fn walk_revert_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: &RevertStatement<'ast>,
+) {
+    visitor.visit_expression(statement.error);
+}
+
+pub fn walk_unchecked_block<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    unchecked: &UncheckedBlock<'ast>,
+) {
+    visitor.visit_block(&unchecked.block.value);
+}
+
+pub fn walk_try_statement<'ast, V: Visitor<'ast>>(visitor: &mut V, statement: &TryStatement<'ast>) {
+    visitor.visit_expression(statement.call);
+    walk_parameters(visitor, statement.returns);
+    visitor.visit_block(&statement.body.value);
+
+    for clause in statement.clauses.iter() {
+        visitor.visit_catch_clause(*clause);
+    }
+}
+
+pub fn walk_catch_clause<'ast, V: Visitor<'ast>>(visitor: &mut V, clause: CatchClauseNode<'ast>) {
+    walk_identifiers(visitor, clause.value.id);
+    walk_parameters(visitor, clause.value.params);
+    visitor.visit_block(&clause.value.body.value);
+}
+
+pub fn walk_variable_definition_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: &VariableDefinitionStatement<'ast>,
+) {
+    visitor.visit_variable_declaration(statement.declaration);
+    walk_expressions(visitor, statement.init);
+}
+
+pub fn walk_inferred_definition_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: &InferredDefinitionStatement<'ast>,
+) {
+    walk_identifiers(visitor, statement.ids.iter().flatten().copied());
+    visitor.visit_expression(statement.init);
+}
+
+pub fn walk_inline_assembly_statement<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    statement: &InlineAssemblyStatement<'ast>,
+) {
+    visitor.visit_inline_assembly_block(&statement.block.value);
+}
+
+pub fn walk_type_name<'ast, V: Visitor<'ast>>(visitor: &mut V, type_name: TypeNameNode<'ast>) {
+    match type_name.value {
+        TypeName::Mapping(mapping) => visitor.visit_mapping(&mapping),
+        TypeName::ArrayTypeName(array) => visitor.visit_array_type_name(&array),
+        TypeName::ElementaryTypeName(_)
+        | TypeName::UserDefinedTypeName(_)
+        | TypeName::FunctionTypeName => {}
+    }
+}
+
+pub fn walk_variable_declaration<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    declaration: VariableDeclarationNode<'ast>,
+) {
+    visitor.visit_type_name(declaration.value.type_name);
+    visitor.visit_identifier(declaration.value.id);
+}
+
+pub fn walk_mapping<'ast, V: Visitor<'ast>>(visitor: &mut V, mapping: &Mapping<'ast>) {
+    visitor.visit_type_name(mapping.to);
+}
+
+pub fn walk_array_type_name<'ast, V: Visitor<'ast>>(visitor: &mut V, array: &ArrayTypeName<'ast>) {
+    visitor.visit_type_name(array.base);
+    walk_expressions(visitor, array.length);
+}
+
+pub fn walk_expression<'ast, V: Visitor<'ast>>(visitor: &mut V, expression: ExpressionNode<'ast>) {
+    match expression.value {
+        Expression::PrimitiveExpression(primitive) => visitor.visit_primitive(&primitive),
+        Expression::PrefixExpression(prefix) => visitor.visit_prefix_expression(&prefix),
+        Expression::PostfixExpression(postfix) => visitor.visit_postfix_expression(&postfix),
+        Expression::BinaryExpression(binary) => visitor.visit_binary_expression(&binary),
+        Expression::AssignmentExpression(assignment) => {
+            visitor.visit_assignment_expression(&assignment)
+        }
+        Expression::TupleExpression(tuple) => visitor.visit_tuple_expression(&tuple),
+        Expression::CallExpression(call) => visitor.visit_call_expression(&call),
+        Expression::CallOptionsExpression(call) => visitor.visit_call_options_expression(&call),
+        Expression::MemberAccessExpression(access) => {
+            visitor.visit_member_access_expression(&access)
+        }
+        Expression::IndexAccessExpression(access) => visitor.visit_index_access_expression(&access),
+        Expression::ConditionalExpression(conditional) => {
+            visitor.visit_conditional_expression(&conditional)
+        }
+        Expression::NewExpression(new) => visitor.visit_new_expression(&new),
+        Expression::TypeExpression(expression) => visitor.visit_type_expression(&expression),
+        Expression::ThisExpression
+        | Expression::IdentifierExpression(_)
+        | Expression::ElementaryTypeExpression(_) => {}
+    }
+}
+
+pub fn walk_prefix_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    prefix: &PrefixExpression<'ast>,
+) {
+    visitor.visit_expression(prefix.operand);
+}
+
+pub fn walk_postfix_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    postfix: &PostfixExpression<'ast>,
+) {
+    visitor.visit_expression(postfix.operand);
+}
+
+pub fn walk_binary_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    binary: &BinaryExpression<'ast>,
+) {
+    visitor.visit_expression(binary.left);
+    visitor.visit_expression(binary.right);
+}
+
+pub fn walk_assignment_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    assignment: &AssignmentExpression<'ast>,
+) {
+    visitor.visit_expression(assignment.left);
+    visitor.visit_expression(assignment.right);
+}
+
+pub fn walk_tuple_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    tuple: &TupleExpression<'ast>,
+) {
+    walk_expressions(visitor, tuple.expressions.iter().copied());
+}
+
+pub fn walk_call_expression<'ast, V: Visitor<'ast>>(visitor: &mut V, call: &CallExpression<'ast>) {
+    visitor.visit_expression(call.callee);
+
+    match call.arguments {
+        CallArguments::Positional(arguments) => {
+            walk_expressions(visitor, arguments.iter().copied())
+        }
+        CallArguments::Named(arguments) => {
+            for argument in arguments.iter() {
+                visitor.visit_named_argument(*argument);
+            }
+        }
+    }
+}
+
+pub fn walk_call_options_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    call: &CallOptionsExpression<'ast>,
+) {
+    visitor.visit_expression(call.callee);
+
+    for option in call.options.iter() {
+        visitor.visit_named_argument(*option);
+    }
+}
+
+pub fn walk_named_argument<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    argument: NamedArgumentNode<'ast>,
+) {
+    visitor.visit_identifier(argument.value.name);
+    visitor.visit_expression(argument.value.value);
+}
+
+pub fn walk_member_access_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    access: &MemberAccessExpression<'ast>,
+) {
+    visitor.visit_expression(access.object);
+    visitor.visit_identifier(access.member);
+}
+
+pub fn walk_index_access_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    access: &IndexAccessExpression<'ast>,
+) {
+    visitor.visit_expression(access.array);
+    walk_expressions(visitor, access.index);
+}
+
+pub fn walk_conditional_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    conditional: &ConditionalExpression<'ast>,
+) {
+    visitor.visit_expression(conditional.test);
+    visitor.visit_expression(conditional.consequent);
+    visitor.visit_expression(conditional.alternate);
+}
+
+pub fn walk_new_expression<'ast, V: Visitor<'ast>>(visitor: &mut V, new: &NewExpression<'ast>) {
+    visitor.visit_type_name(new.type_name);
+}
+
+pub fn walk_type_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    expression: &TypeExpression<'ast>,
+) {
+    visitor.visit_type_name(expression.type_name);
+}
+
+pub fn walk_inline_assembly_block<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    block: &InlineAssemblyBlock<'ast>,
+) {
+    for item in block.items.iter() {
+        visitor.visit_assembly_item(*item);
+    }
+}
+
+pub fn walk_assembly_item<'ast, V: Visitor<'ast>>(visitor: &mut V, item: AssemblyItemNode<'ast>) {
+    match item.value {
+        AssemblyItem::InlineAssemblyBlock(block) => visitor.visit_inline_assembly_block(&block),
+        AssemblyItem::AssemblyLocalBinding(binding) => {
+            visitor.visit_assembly_local_binding(&binding)
+        }
+        AssemblyItem::AssemblyAssignment(assignment) => {
+            visitor.visit_assembly_assignment(&assignment)
+        }
+        AssemblyItem::AssemblyIf(statement) => visitor.visit_assembly_if(&statement),
+        AssemblyItem::AssemblySwitch(switch) => visitor.visit_assembly_switch(&switch),
+        AssemblyItem::AssemblyFor(statement) => visitor.visit_assembly_for(&statement),
+        AssemblyItem::AssemblyFunctionDefinition(function) => {
+            visitor.visit_assembly_function_definition(&function)
+        }
+        AssemblyItem::FunctionalAssemblyExpression(expression) => {
+            visitor.visit_functional_assembly_expression(&expression)
+        }
+        AssemblyItem::Leave | AssemblyItem::Break | AssemblyItem::Continue => {}
+    }
+}
+
+pub fn walk_assembly_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    expression: AssemblyExpressionNode<'ast>,
+) {
+    match expression.value {
+        AssemblyExpression::Identifier(_) => {}
+        AssemblyExpression::Literal(primitive) => visitor.visit_primitive(&primitive),
+        AssemblyExpression::FunctionalAssemblyExpression(expression) => {
+            visitor.visit_functional_assembly_expression(&expression)
+        }
+    }
+}
+
+pub fn walk_assembly_local_binding<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    binding: &AssemblyLocalBinding<'ast>,
+) {
+    walk_identifiers(visitor, binding.ids.iter().copied());
+
+    if let Some(init) = binding.init {
+        visitor.visit_assembly_expression(init);
+    }
+}
+
+pub fn walk_assembly_assignment<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    assignment: &AssemblyAssignment<'ast>,
+) {
+    walk_identifiers(visitor, assignment.ids.iter().copied());
+    visitor.visit_assembly_expression(assignment.init);
+}
+
+pub fn walk_assembly_if<'ast, V: Visitor<'ast>>(visitor: &mut V, statement: &AssemblyIf<'ast>) {
+    visitor.visit_assembly_expression(statement.test);
+    visitor.visit_inline_assembly_block(&statement.body.value);
+}
+
+pub fn walk_assembly_switch<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    switch: &AssemblySwitch<'ast>,
+) {
+    visitor.visit_assembly_expression(switch.test);
+
+    for case in switch.cases.iter() {
+        visitor.visit_assembly_case(*case);
+    }
+
+    if let Some(default) = switch.default {
+        visitor.visit_inline_assembly_block(&default.value);
+    }
+}
+
+pub fn walk_assembly_case<'ast, V: Visitor<'ast>>(visitor: &mut V, case: AssemblyCaseNode<'ast>) {
+    visitor.visit_primitive(&case.value.value.value);
+    visitor.visit_inline_assembly_block(&case.value.body.value);
+}
+
+pub fn walk_assembly_for<'ast, V: Visitor<'ast>>(visitor: &mut V, statement: &AssemblyFor<'ast>) {
+    visitor.visit_inline_assembly_block(&statement.init.value);
+    visitor.visit_assembly_expression(statement.test);
+    visitor.visit_inline_assembly_block(&statement.update.value);
+    visitor.visit_inline_assembly_block(&statement.body.value);
+}
+
+pub fn walk_assembly_function_definition<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    function: &AssemblyFunctionDefinition<'ast>,
+) {
+    visitor.visit_identifier(function.name);
+    walk_identifiers(visitor, function.params.iter().copied());
+    walk_identifiers(visitor, function.returns.iter().copied());
+    visitor.visit_inline_assembly_block(&function.body.value);
+}
+
+pub fn walk_functional_assembly_expression<'ast, V: Visitor<'ast>>(
+    visitor: &mut V,
+    expression: &FunctionalAssemblyExpression<'ast>,
+) {
+    visitor.visit_identifier(expression.id);
+
+    for argument in expression.arguments.iter() {
+        visitor.visit_assembly_expression(*argument);
+    }
+}
+
+fn walk_identifiers<'ast, V, I>(visitor: &mut V, identifiers: I)
+where
+    V: Visitor<'ast>,
+    I: IntoIterator<Item = IdentifierNode<'ast>>,
+{
+    for identifier in identifiers {
+        visitor.visit_identifier(identifier);
+    }
+}
+
+fn walk_expressions<'ast, V, I>(visitor: &mut V, expressions: I)
+where
+    V: Visitor<'ast>,
+    I: IntoIterator<Item = ExpressionNode<'ast>>,
+{
+    for expression in expressions {
+        visitor.visit_expression(expression);
+    }
+}
+
+fn walk_parameters<'ast, V: Visitor<'ast>>(visitor: &mut V, parameters: ParameterList<'ast>) {
+    for parameter in parameters.iter() {
+        visitor.visit_parameter(*parameter);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use toolshed::Arena;
+
+    fn node<'ast, T: Copy>(arena: &'ast Arena, value: T) -> Node<'ast, T> {
+        Node::new(arena.alloc(NodeInner::new(0, 0, value)))
+    }
+
+    #[derive(Default)]
+    struct Names<'ast> {
+        identifiers: Vec<&'ast str>,
+        expressions: usize,
+    }
+
+    impl<'ast> Visitor<'ast> for Names<'ast> {
+        fn visit_identifier(&mut self, identifier: IdentifierNode<'ast>) {
+            self.identifiers.push(identifier.value);
+        }
+
+        fn visit_expression(&mut self, expression: ExpressionNode<'ast>) {
+            self.expressions += 1;
+
+            walk_expression(self, expression);
+        }
+
+        // Skips the bodies of modifiers
+        fn visit_modifier_definition(&mut self, modifier: &ModifierDefinition<'ast>) {
+            self.visit_identifier(modifier.name);
+        }
+    }
+
+    #[test]
+    fn visits_nested_nodes_in_source_order() {
+        let arena = Arena::new();
+
+        // function foo() { bar.baz(1 + x); }
+        let call = CallExpression {
+            callee: node(
+                &arena,
+                MemberAccessExpression {
+                    object: node(&arena, Expression::IdentifierExpression("bar")),
+                    member: node(&arena, "baz"),
+                }
+                .into(),
+            ),
+            arguments: CallArguments::Positional(NodeList::from(
+                &arena,
+                node(
+                    &arena,
+                    BinaryExpression {
+                        left: node(
+                            &arena,
+                            Primitive::IntegerNumber("1", NumberUnit::None).into(),
+                        ),
+                        operator: node(&arena, BinaryOperator::Addition),
+                        right: node(&arena, Expression::IdentifierExpression("x")),
+                    }
+                    .into(),
+                ),
+            )),
+        };
+
+        let function = FunctionDefinition {
+            name: Some(node(&arena, "foo")),
+            params: NodeList::empty(),
+            visibility: None,
+            mutability: None,
+            modifiers: NodeList::empty(),
+            returns: NodeList::empty(),
+            block: Some(node(
+                &arena,
+                Block {
+                    body: NodeList::from(
+                        &arena,
+                        node(
+                            &arena,
+                            Statement::ExpressionStatement(node(&arena, call.into())),
+                        ),
+                    ),
+                },
+            )),
+        };
+
+        let modifier = ModifierDefinition {
+            name: node(&arena, "onlyOwner"),
+            params: NodeList::empty(),
+            block: function.block.unwrap(),
+        };
+
+        let contract = ContractDefinition {
+            name: node(&arena, "Foo"),
+            inherits: NodeList::from(&arena, node(&arena, "Bar")),
+            body: NodeList::from_iter(
+                &arena,
+                vec![node(&arena, function.into()), node(&arena, modifier.into())],
+            ),
+        };
+
+        let mut names = Names::default();
+
+        names.visit_source_unit(node(&arena, contract.into()));
+
+        assert_eq!(names.identifiers, ["Foo", "Bar", "foo", "baz", "onlyOwner"]);
+        assert_eq!(names.expressions, 6);
+    }
+}
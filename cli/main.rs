@@ -1,14 +1,347 @@
-use optimizoor_parser::{parse, Parser};
+use std::collections::HashSet;
+use std::fmt::{Display, Write};
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::{env, fs, process, thread};
+
+use optimizoor_ast::{Program, SourceMap};
+use optimizoor_optimizers::{pinned_structs, Pipeline, Report};
+use optimizoor_parser::parse;
+use optimizoor_project::Layout;
 
 fn main() {
-    let source = include_str!("../examples/unoptimized_contracts/struct_packing.sol");
+    #[cfg(feature = "tracing")]
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(tracing_subscriber::filter::LevelFilter::DEBUG)
+        .init();
+
+    let (flags, args): (Vec<_>, Vec<_>) =
+        env::args().skip(1).partition(|arg| arg.starts_with("--"));
+    let mut format = Format::Debug;
+    // Threads projects are analyzed on, one per core unless `--jobs=N` says otherwise
+    let mut jobs = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+
+    for flag in &flags {
+        match flag.as_str() {
+            "--json" => format = Format::Json,
+            "--json=compact" => format = Format::CompactJson,
+            flag => match flag.strip_prefix("--jobs=").map(str::parse) {
+                Some(Ok(count)) if count > 0 => jobs = count,
+                Some(_) => fail("--jobs expects a number of threads, like --jobs=4"),
+                None => fail(format!("unknown flag {}", flag)),
+            },
+        }
+    }
+
+    let path = args
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| "examples/unoptimized_contracts/struct_packing.sol".to_string());
 
-    //     let tokens = tokenize(struct_contract_source);
-    //     let output = generate_output(tokens);
-    //     println!("{}", output);
-    // }
+    if Path::new(&path).is_dir() {
+        return load_project(&path, jobs);
+    }
+
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => SourceMap::new(source),
+        Err(err) => {
+            eprintln!("error: can't read {}: {}", path, err);
+            process::exit(1);
+        }
+    };
 
     // parse the source code
-    let mut ast = parse(source).unwrap();
-    println!("{:#?}", ast.body());
+    match parse(source.source()) {
+        Ok(ast) => print_ast(&ast, format),
+        Err(errors) => {
+            for error in &errors {
+                eprintln!("{}", error.render(&path, &source));
+            }
+
+            eprintln!("error: could not parse {} ({} errors)", path, errors.len());
+            process::exit(1);
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Format {
+    Debug,
+    /// The AST as it is in Rust
+    Json,
+    /// Resembling the `compactAST` of solc
+    CompactJson,
+}
+
+fn print_ast(ast: &Program, format: Format) {
+    match format {
+        Format::Debug => println!("{:#?}", ast.body()),
+        #[cfg(feature = "serde")]
+        Format::Json => println!("{}", serde_json::to_string_pretty(ast).unwrap()),
+        #[cfg(feature = "serde")]
+        Format::CompactJson => {
+            let json = optimizoor_ast::compact_ast(ast.body(), 0);
+
+            println!("{}", serde_json::to_string_pretty(&json).unwrap())
+        }
+        #[cfg(not(feature = "serde"))]
+        Format::Json | Format::CompactJson => {
+            fail("JSON output needs the cli to be built with the `serde` feature")
+        }
+    }
+}
+
+/// Analyzes a Foundry or Hardhat project on `jobs` threads and lists the findings
+/// for each of its files.
+fn load_project(root: &str, jobs: usize) {
+    let (findings, errors) = analyze_project(root, jobs).unwrap_or_else(|err| fail(err));
+
+    print!("{}", findings);
+
+    for error in &errors {
+        eprintln!("{}", error);
+    }
+
+    if !errors.is_empty() {
+        eprintln!("error: could not analyze {} ({} errors)", root, errors.len());
+        process::exit(1);
+    }
+}
+
+/// Runs every pass over the files of the project, returning their findings in the
+/// order of their ids, which doesn't depend on `jobs`, and the diagnostics: rendered
+/// syntax errors and imports that don't resolve.
+fn analyze_project(root: &str, jobs: usize) -> Result<(String, Vec<String>), String> {
+    let layout = Layout::detect(root).map_err(|err| err.to_string())?;
+    let entries = layout.entries().map_err(|err| err.to_string())?;
+    let project = layout.project().with_jobs(jobs);
+
+    // Files are rewritten one at a time, so structs that any file of the project
+    // constructs or exposes keep their order, whichever file declares them
+    let pinned: HashSet<String> = project
+        .analyze(&entries, |program| {
+            let pinned = pinned_structs(program);
+
+            pinned.into_iter().map(str::to_string).collect::<Vec<_>>()
+        })
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .flat_map(|analysis| analysis.findings)
+        .collect();
+
+    let pipeline = Pipeline::pinning_structs(pinned);
+    let analyses = project
+        .analyze(&entries, |program| {
+            (pipeline.run(program), program.source_map().clone())
+        })
+        .map_err(|err| err.to_string())?;
+
+    let mut findings = String::new();
+    let mut errors = Vec::new();
+
+    // Writing into a `String` can't fail
+    for analysis in analyses {
+        let path = analysis.path.display().to_string();
+        let (output, source) = analysis.findings;
+
+        errors.extend(
+            analysis
+                .errors
+                .iter()
+                .map(|error| error.render(&path, &source)),
+        );
+        errors.extend(analysis.unresolved.iter().map(|(import, error)| {
+            format!("error: can't resolve import \"{}\" in {}: {}", import, path, error)
+        }));
+
+        // Libraries and tests are parsed so that their symbols resolve, never rewritten
+        if analysis.excluded {
+            let _ = writeln!(
+                findings,
+                "{} {:<10} {}",
+                analysis.file.0, "parse only", path
+            );
+            continue;
+        }
+
+        let _ = writeln!(findings, "{} {:<10} {}", analysis.file.0, "rewrite", path);
+
+        for report in &output.reports {
+            let _ = writeln!(findings, "    {}", describe(report, &source));
+        }
+
+        for pass in &output.skipped {
+            let _ = writeln!(
+                findings,
+                "    {:<16} skipped, the pragma allows older compilers",
+                pass
+            );
+        }
+    }
+
+    Ok((findings, errors))
+}
+
+/// One line summary of a finding, like `struct-packing   Vault.Account: 3 -> 2 slots`.
+fn describe(report: &Report, source: &SourceMap) -> String {
+    match report {
+        Report::CustomErrors(report) => format!(
+            "{:<16} {}: \"{}\" -> {}() ({} uses, ~{} bytes)",
+            "custom-errors",
+            report.contract,
+            report.message,
+            report.error,
+            report.occurrences,
+            report.estimated_savings,
+        ),
+        Report::UncheckedLoop(report) => format!(
+            "{:<16} {}.{}: `{}` on line {}",
+            "unchecked-loops",
+            report.contract,
+            report.function.as_deref().unwrap_or("<unnamed>"),
+            report.counter,
+            source.line_col(report.start).line + 1,
+        ),
+        Report::StructPacking(report) => format!(
+            "{:<16} {}{}: {} -> {} slots",
+            "struct-packing",
+            report
+                .contract
+                .as_ref()
+                .map_or(String::new(), |contract| format!("{}.", contract)),
+            report.name,
+            report.slots_before,
+            report.slots_after,
+        ),
+    }
+}
+
+fn fail(err: impl Display) -> ! {
+    eprintln!("error: {}", err);
+    process::exit(1);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Writes a Foundry project with the `files` into a fresh temporary directory.
+    fn foundry_project(name: &str, files: &[(String, String)]) -> String {
+        let root = env::temp_dir().join(format!("optimizoor-cli-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("foundry.toml"), "[profile.default]\n").unwrap();
+
+        for (path, source) in files {
+            fs::write(root.join(path), source).unwrap();
+        }
+
+        root.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn findings_do_not_depend_on_jobs() {
+        let mut files = Vec::new();
+
+        for index in 0..16 {
+            let source = format!(
+                r#"pragma solidity ^0.8.4;
+                import "./Vault{next}.sol";
+
+                contract Vault{index} {{
+                    struct Account {{ uint128 a; uint256 b; uint128 c; }}
+
+                    mapping(address => Account) accounts;
+
+                    function sum(uint256 n) internal returns (uint256 total) {{
+                        require(n > 0, "empty");
+
+                        for (uint256 i = 0; i < n; i++) {{
+                            total += i;
+                        }}
+                    }}
+                }}
+                "#,
+                index = index,
+                next = (index + 1) % 16,
+            );
+
+            files.push((format!("src/Vault{}.sol", index), source));
+        }
+
+        let root = foundry_project("jobs", &files);
+        let (sequential, errors) = analyze_project(&root, 1).unwrap();
+
+        assert!(errors.is_empty());
+        assert_eq!(sequential.matches("custom-errors").count(), 16);
+        assert_eq!(sequential.matches("unchecked-loops").count(), 16);
+        assert_eq!(sequential.matches("3 -> 2 slots").count(), 16);
+
+        assert_eq!(analyze_project(&root, 4).unwrap(), (sequential, errors));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keeps_structs_other_files_construct() {
+        let files = [
+            (
+                "src/Types.sol",
+                r#"
+                struct Order { uint128 amount; address maker; uint128 price; }
+                struct Fill { uint128 amount; address taker; uint128 price; }
+                "#,
+            ),
+            (
+                "src/Book.sol",
+                r#"
+                import "./Types.sol";
+
+                contract Book {
+                    function place(uint128 a, uint128 p) external returns (Order memory order) {
+                        order = Order(a, msg.sender, p);
+                    }
+                }
+                "#,
+            ),
+        ];
+        let files: Vec<_> = files
+            .iter()
+            .map(|(path, source)| (path.to_string(), source.to_string()))
+            .collect();
+
+        let root = foundry_project("pinned", &files);
+        let (findings, _) = analyze_project(&root, 2).unwrap();
+
+        assert!(!findings.contains("Order"));
+        assert!(findings.contains("struct-packing   Fill: 3 -> 2 slots"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn reports_unresolved_imports() {
+        let files = [(
+            "src/Vault.sol".to_string(),
+            r#"
+            import "forge-std/Test.sol";
+
+            contract Vault {
+                struct Account { uint128 a; uint256 b; uint128 c; }
+            }
+            "#
+            .to_string(),
+        )];
+
+        let root = foundry_project("unresolved", &files);
+        let (findings, errors) = analyze_project(&root, 1).unwrap();
+
+        assert!(findings.contains("struct-packing   Vault.Account: 3 -> 2 slots"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("error: can't resolve import \"forge-std/Test.sol\" in "));
+
+        fs::remove_dir_all(root).unwrap();
+    }
 }
@@ -3,7 +3,7 @@ extern crate logos;
 mod token;
 
 pub use self::token::Token;
-pub use logos::{lookup, Logos};
+pub use logos::{lookup, Logos, Source};
 pub type Lexer<S> = logos::Lexer<Token, S>;
 
 // /// Tokenizes the given Solidity source code.
@@ -121,7 +121,7 @@ mod test {
     fn identifiers() {
         assert_lex(
             "
-                foo _foo $foo $_foo _ $ $$ fooBar BarFoo foo10 $1
+                foo _foo $foo $_foo _ $ $$ fooBar BarFoo foo10 $1 hex unicode
             ",
             &[
                 (Identifier, "foo"),
@@ -135,6 +135,8 @@ mod test {
                 (Identifier, "BarFoo"),
                 (Identifier, "foo10"),
                 (Identifier, "$1"),
+                (Identifier, "hex"),
+                (Identifier, "unicode"),
             ][..],
         );
     }
@@ -167,6 +169,8 @@ mod test {
             r#"
                 true false 0 42 0xDEAD 0Xdead 3.14 3.14E+2 .12345
                 5.1e2 42e-3 500E-1 500.1 10.000 'foo bar' "doge to the moon"
+                1_000_000 0xdead_beef 1e18 2.5e-1 1_000e-3 501E-1 0.0e-5
+                hex"deadbeef" hex'00_ff' hex"" unicode"Hello 😃" unicode'\'quoted\''
             "#,
             &[
                 (LiteralTrue, "true"),
@@ -185,6 +189,18 @@ mod test {
                 (LiteralInteger, "10.000"),
                 (LiteralString, "'foo bar'"),
                 (LiteralString, "\"doge to the moon\""),
+                (LiteralInteger, "1_000_000"),
+                (LiteralHex, "0xdead_beef"),
+                (LiteralInteger, "1e18"),
+                (LiteralRational, "2.5e-1"),
+                (LiteralInteger, "1_000e-3"),
+                (LiteralRational, "501E-1"),
+                (LiteralInteger, "0.0e-5"),
+                (LiteralHexString, "hex\"deadbeef\""),
+                (LiteralHexString, "hex'00_ff'"),
+                (LiteralHexString, "hex\"\""),
+                (LiteralUnicodeString, "unicode\"Hello 😃\""),
+                (LiteralUnicodeString, "unicode'\\'quoted\\''"),
             ][..],
         );
     }
@@ -210,24 +226,26 @@ mod test {
     fn keywords() {
         assert_lex(
             "
-                anonymous as assembly break constant continue do
-                delete else external for hex if indexed internal import
+                anonymous as assembly break calldata catch constant continue do
+                delete else emit external for if indexed internal import
                 is mapping memory new payable public pragma private pure
-                return returns storage super this throw using view while
+                return returns storage super this throw try type unchecked using view while
             ",
             &[
                 (KeywordAnonymous, "anonymous"),
                 (KeywordAs, "as"),
                 (KeywordAssembly, "assembly"),
                 (KeywordBreak, "break"),
+                (KeywordCalldata, "calldata"),
+                (KeywordCatch, "catch"),
                 (KeywordConstant, "constant"),
                 (KeywordContinue, "continue"),
                 (KeywordDo, "do"),
                 (KeywordDelete, "delete"),
                 (KeywordElse, "else"),
+                (KeywordEmit, "emit"),
                 (KeywordExternal, "external"),
                 (KeywordFor, "for"),
-                (KeywordHex, "hex"),
                 (KeywordIf, "if"),
                 (KeywordIndexed, "indexed"),
                 (KeywordInternal, "internal"),
@@ -247,6 +265,9 @@ mod test {
                 (KeywordSuper, "super"),
                 (KeywordThis, "this"),
                 (KeywordThrow, "throw"),
+                (KeywordTry, "try"),
+                (KeywordType, "type"),
+                (KeywordUnchecked, "unchecked"),
                 (KeywordUsing, "using"),
                 (KeywordView, "view"),
                 (KeywordWhile, "while"),
@@ -279,11 +300,12 @@ mod test {
     fn units() {
         assert_lex(
             "
-                wei szabo finney ether
+                wei gwei szabo finney ether
                 seconds minutes hours days weeks years
             ",
             &[
                 (UnitWei, "wei"),
+                (UnitGwei, "gwei"),
                 (UnitSzabo, "szabo"),
                 (UnitFinney, "finney"),
                 (UnitEther, "ether"),
@@ -301,15 +323,14 @@ mod test {
     fn reserved_words() {
         assert_lex(
             "
-                abstract after case catch default final in
+                abstract after case default final in
                 inline let match null of relocatable static
-                switch try type typeof
+                switch typeof
             ",
             &[
                 (ReservedWord, "abstract"),
                 (ReservedWord, "after"),
                 (ReservedWord, "case"),
-                (ReservedWord, "catch"),
                 (ReservedWord, "default"),
                 (ReservedWord, "final"),
                 (ReservedWord, "in"),
@@ -321,8 +342,6 @@ mod test {
                 (ReservedWord, "relocatable"),
                 (ReservedWord, "static"),
                 (ReservedWord, "switch"),
-                (ReservedWord, "try"),
-                (ReservedWord, "type"),
                 (ReservedWord, "typeof"),
             ][..],
         );
@@ -410,6 +429,22 @@ mod test {
         );
     }
 
+    #[test]
+    fn assembly_operators() {
+        assert_lex(
+            ":= =: -> x-->y",
+            &[
+                (AssemblyBind, ":="),
+                (AssemblyAssign, "=:"),
+                (AssemblyArrow, "->"),
+                (Identifier, "x"),
+                (OperatorDecrement, "--"),
+                (OperatorGreater, ">"),
+                (Identifier, "y"),
+            ][..],
+        );
+    }
+
     #[test]
     fn types_easy() {
         assert_lex(
@@ -5,15 +5,15 @@
 //!  ```text
 //!  EOF    ;      :      ,      .      (      )      {      }      [      ]      =>
 //!  IDENT  BLTIN  CONTR  LIB    IFACE  ENUM   STRUCT MODIF  EVENT  FUNCT  VAR    ANON
-//!  AS     ASM    BREAK  CONST  CONTIN DO     DELETE ELSE   EXTERN FOR    HEX    IF
-//!  INDEX  INTERN IMPORT IS     MAP    MEM    NEW    PAY    PULIC  PRAGMA PRIV   PURE
-//!  RET    RETNS  STORAG SUPER  THIS   THROW  USING  VIEW   WHILE  RESERV T_BOOL T_ADDR
-//!  T_STR  T_BYT  T_BYTS T_INT  T_UINT T_FIX  T_UFIX L_TRUE L_FALS L_HEX  L_INT  L_RAT
-//!  L_STR  E_ETH  E_FINN E_SZAB E_WEI  T_YEAR T_WEEK T_DAYS T_HOUR T_MIN  T_SEC  :=
-//!  =:     ++     --     !      ~      *      /      %      **     +      -      <<
-//!  >>     <      <=     >      >=     ==     !=     &      ^      |      &&     ||
-//!  ?      =      +=     -=     *=     /=     %=     <<=    >>=    &=     ^=     |=
-//!  ERRTOK ERREOF
+//!  AS     ASM    BREAK  CALLD  CATCH  CONST  CONTIN DO     DELETE ELSE   EMIT   EXTERN
+//!  FOR    IF     INDEX  INTERN IMPORT IS     MAP    MEM    NEW    PAY    PULIC  PRAGMA
+//!  PRIV   PURE   RET    RETNS  STORAG SUPER  THIS   THROW  TRY    TYPE   UNCHK  USING
+//!  VIEW   WHILE  RESERV T_BOOL T_ADDR T_STR  T_BYT  T_BYTS T_INT  T_UINT T_FIX  T_UFIX
+//!  L_TRUE L_FALS L_HEX  L_INT  L_RAT  L_STR  L_HSTR L_USTR E_ETH  E_FINN E_SZAB E_GWEI
+//!  E_WEI  T_YEAR T_WEEK T_DAYS T_HOUR T_MIN  T_SEC  :=     =:     ->     ++     --
+//!  !      ~      *      /      %      **     +      -      <<     >>     <      <=
+//!  >      >=     ==     !=     &      ^      |      &&     ||     ?      =      +=
+//!  -=     *=     /=     %=     <<=    >>=    &=     ^=     |=     ERRTOK ERREOF
 //!  ```
 //!
 
@@ -126,6 +126,12 @@ pub enum Token {
     #[token = "break"]
     KeywordBreak,
 
+    #[token = "calldata"]
+    KeywordCalldata,
+
+    #[token = "catch"]
+    KeywordCatch,
+
     #[token = "constant"]
     KeywordConstant,
 
@@ -141,16 +147,15 @@ pub enum Token {
     #[token = "else"]
     KeywordElse,
 
+    #[token = "emit"]
+    KeywordEmit,
+
     #[token = "external"]
     KeywordExternal,
 
     #[token = "for"]
     KeywordFor,
 
-    // FIXME: Should able to handle hex literals on lexer-level!
-    #[token = "hex"]
-    KeywordHex,
-
     #[token = "if"]
     KeywordIf,
 
@@ -208,6 +213,15 @@ pub enum Token {
     #[token = "throw"]
     KeywordThrow,
 
+    #[token = "try"]
+    KeywordTry,
+
+    #[token = "type"]
+    KeywordType,
+
+    #[token = "unchecked"]
+    KeywordUnchecked,
+
     #[token = "using"]
     KeywordUsing,
 
@@ -217,9 +231,9 @@ pub enum Token {
     #[token = "while"]
     KeywordWhile,
 
-    #[regex = "abstract|after|case|catch|default|final|in"]
+    #[regex = "abstract|after|case|default|final|in"]
     #[regex = "inline|let|match|null|of|relocatable|static"]
-    #[regex = "switch|try|type|typeof"]
+    #[regex = "switch|typeof"]
     ReservedWord,
 
     #[token = "bool"]
@@ -270,13 +284,14 @@ pub enum Token {
     #[token = "false"]
     LiteralFalse,
 
-    #[regex = "0[xX][0-9a-fA-F]+"]
+    #[regex = "0[xX][0-9a-fA-F]+(_[0-9a-fA-F]+)*"]
     LiteralHex,
 
-    #[regex = "[0-9]+"]
+    #[regex = "[0-9]+(_[0-9]+)*"]
     LiteralInteger,
 
-    #[regex = "[0-9]*\\.[0-9]+([eE][+-]?[0-9]+)?|[0-9]+[eE][+-]?[0-9]+"]
+    #[regex = "([0-9]+(_[0-9]+)*)?\\.[0-9]+(_[0-9]+)*([eE][+-]?[0-9]+)?"]
+    #[regex = "[0-9]+(_[0-9]+)*[eE][+-]?[0-9]+"]
     #[callback = "rational_to_integer"]
     LiteralRational,
 
@@ -284,6 +299,14 @@ pub enum Token {
     #[regex = "'([^'\\\\]|\\\\.)*'"]
     LiteralString,
 
+    #[regex = "hex\"([0-9a-fA-F]+(_[0-9a-fA-F]+)*)?\""]
+    #[regex = "hex'([0-9a-fA-F]+(_[0-9a-fA-F]+)*)?'"]
+    LiteralHexString,
+
+    #[regex = "unicode\"([^\"\\\\]|\\\\.)*\""]
+    #[regex = "unicode'([^'\\\\]|\\\\.)*'"]
+    LiteralUnicodeString,
+
     #[token = "ether"]
     UnitEther,
 
@@ -293,6 +316,9 @@ pub enum Token {
     #[token = "szabo"]
     UnitSzabo,
 
+    #[token = "gwei"]
+    UnitGwei,
+
     #[token = "wei"]
     UnitWei,
 
@@ -320,6 +346,9 @@ pub enum Token {
     #[token = "=:"]
     AssemblyAssign,
 
+    #[token = "->"]
+    AssemblyArrow,
+
     #[token = "++"]
     OperatorIncrement,
 
@@ -440,7 +469,9 @@ fn ignore_comments<'source, Src: Source<'source>>(lex: &mut Lexer<Token, Src>) {
         loop {
             match lex.read() {
                 0 => {
-                    println!("Unexpected end of program ASSDADASDADAS");
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("unterminated block comment");
+
                     return lex.token = Token::UnexpectedEndOfProgram;
                 }
                 b'*' => {
@@ -531,29 +562,28 @@ fn validate_fixed<'source, Src: Source<'source>>(lex: &mut Lexer<Token, Src>) {
 }
 
 fn rational_to_integer<'source, Src: Source<'source>>(lex: &mut Lexer<Token, Src>) {
-    let mut floating = 0i32;
     let mut iter = lex.slice().as_bytes().iter();
 
-    'outer: while let Some(&byte) = iter.next() {
+    // Digits after the dot, and trailing zeroes of all the digits
+    let mut fraction = 0i32;
+    let mut zeroes = 0i32;
+    let mut is_fraction = false;
+    let mut is_zero = true;
+
+    for &byte in iter.by_ref() {
         match byte {
-            b'e' | b'E' => break 'outer,
-            b'0' => floating += 1,
-            b'.' => {
-                floating = 0;
-                let mut zeroes = 0;
-
-                while let Some(&byte) = iter.next() {
-                    match byte {
-                        b'e' | b'E' => break 'outer,
-                        b'0' => zeroes += 1,
-                        _ => {
-                            floating -= 1 + zeroes;
-                            zeroes = 0;
-                        }
-                    }
-                }
+            b'e' | b'E' => break,
+            b'.' => is_fraction = true,
+            b'_' => continue,
+            b'0' => zeroes += 1,
+            _ => {
+                zeroes = 0;
+                is_zero = false;
             }
-            _ => {}
+        }
+
+        if is_fraction && byte != b'.' {
+            fraction += 1;
         }
     }
 
@@ -568,7 +598,7 @@ fn rational_to_integer<'source, Src: Source<'source>>(lex: &mut Lexer<Token, Src
         }
     }
 
-    if floating + e * neg >= 0 {
+    if is_zero || e * neg - fraction + zeroes >= 0 {
         lex.token = Token::LiteralInteger;
     }
 }
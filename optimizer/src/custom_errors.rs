@@ -0,0 +1,595 @@
+//! Custom Errors Optimisation
+
+use optimizoor_ast::{
+    CallArguments, CallExpression, ContractDefinition, ContractPart, ErrorDefinition, Expression,
+    ExpressionNode, IfStatement, ImportDirective, Node, NodeInner, NodeList, PrefixExpression,
+    PrefixOperator, Primitive, Program, RevertStatement, SourceUnit, Statement, StatementList,
+    StatementNode, TupleExpression,
+};
+use toolshed::list::{GrowableList, List};
+use toolshed::Arena;
+
+use crate::pragma::{pragma_allows, Version};
+
+/// Custom errors were introduced in solidity 0.8.4.
+pub const CUSTOM_ERRORS_MIN_VERSION: Version = (0, 8, 4);
+
+/// Bytecode used to ABI-encode `Error(string)`: the selector, offset and length words.
+const STRING_REVERT_OVERHEAD: usize = 64;
+
+/// Every 32 byte word of a message is pushed with `PUSH32` and stored with `MSTORE`.
+const BYTES_PER_MESSAGE_WORD: usize = 34;
+
+/// `PUSH4 selector, PUSH1 0, MSTORE, PUSH1 4, PUSH1 28, REVERT`.
+const CUSTOM_ERROR_REVERT: usize = 12;
+
+/// Savings for a single unique `require` message of a contract.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CustomErrorReport {
+    pub contract: String,
+    pub message: String,
+    pub error: String,
+    pub occurrences: usize,
+    /// Estimated size reduction of the deployed bytecode, in bytes.
+    pub estimated_savings: usize,
+}
+
+/// A `require(cond, "message");` statement that is about to be rewritten.
+struct Require<'ast> {
+    statement: &'ast StatementNode<'ast>,
+    condition: ExpressionNode<'ast>,
+    message: &'ast str,
+}
+
+/// Replaces every `require(cond, "message")` with `if (!cond) revert Error();`,
+/// declaring one custom error per unique message in the enclosing contract.
+/// Programs that can be compiled with solidity older than 0.8.4 are left untouched.
+pub fn replace_require_strings<'ast>(program: &'ast Program<'ast>) -> Vec<CustomErrorReport> {
+    if !pragma_allows(program, CUSTOM_ERRORS_MIN_VERSION) {
+        return Vec::new();
+    }
+
+    let arena = program.arena();
+    let mut reports = Vec::new();
+    let mut names = declared_names(program);
+
+    for unit in program.body().iter() {
+        if let SourceUnit::ContractDefinition(contract) = &unit.value {
+            if let Some(contract) = replace_in_contract(arena, contract, &mut names, &mut reports) {
+                unit.set(arena.alloc(NodeInner::new(unit.start, unit.end, contract.into())));
+            }
+        }
+    }
+
+    reports
+}
+
+fn replace_in_contract<'ast>(
+    arena: &'ast Arena,
+    contract: &'ast ContractDefinition<'ast>,
+    names: &mut Vec<String>,
+    reports: &mut Vec<CustomErrorReport>,
+) -> Option<ContractDefinition<'ast>> {
+    let mut requires = Vec::new();
+
+    for part in contract.body.iter() {
+        let block = match &part.value {
+            ContractPart::FunctionDefinition(function) => function.block.as_ref(),
+            ContractPart::ModifierDefinition(modifier) => Some(&modifier.block),
+            _ => None,
+        };
+
+        if let Some(block) = block {
+            collect_block(&block.value.body, &mut requires);
+        }
+    }
+
+    if requires.is_empty() {
+        return None;
+    }
+
+    // Unique messages in order of appearance, with the name of their error
+    let mut messages: Vec<(&'ast str, &'ast str, usize)> = Vec::new();
+
+    for require in &requires {
+        let name = match messages
+            .iter_mut()
+            .find(|(message, ..)| *message == require.message)
+        {
+            Some((_, name, occurrences)) => {
+                *occurrences += 1;
+                *name
+            }
+            None => {
+                let name = arena.alloc_string(error_name(require.message, names));
+
+                names.push(name.to_string());
+                messages.push((require.message, name, 1));
+                name
+            }
+        };
+
+        rewrite_require(arena, require, name);
+    }
+
+    let body = GrowableList::new();
+
+    for &(_, name, _) in &messages {
+        let (start, end) = (contract.name.start, contract.name.end);
+        let error = ErrorDefinition {
+            name: alloc(arena, start, end, name),
+            params: NodeList::empty(),
+        };
+
+        body.push(
+            arena,
+            alloc::<ContractPart>(arena, start, end, error.into()),
+        );
+    }
+
+    for part in contract.body.iter() {
+        body.push(arena, *part);
+    }
+
+    for (message, error, occurrences) in messages {
+        reports.push(CustomErrorReport {
+            contract: contract.name.value.to_string(),
+            message: message.to_string(),
+            error: error.to_string(),
+            occurrences,
+            estimated_savings: occurrences * estimate_savings(message),
+        });
+    }
+
+    Some(ContractDefinition {
+        body: body.as_list(),
+        ..*contract
+    })
+}
+
+/// Every name declared at file level or within any of the contracts of the file, which
+/// covers the declarations a contract inherits from the same file. Errors are named
+/// so that they don't shadow or clash with any of them.
+fn declared_names(program: &Program) -> Vec<String> {
+    let mut names = Vec::new();
+
+    for unit in program.body().iter() {
+        let name = match unit.value {
+            SourceUnit::ContractDefinition(contract) => {
+                names.extend(
+                    contract
+                        .body
+                        .iter()
+                        .filter_map(|part| part_name(part.value)),
+                );
+                Some(contract.name.value)
+            }
+            SourceUnit::ImportDirective(import) => {
+                names.extend(import_names(import));
+                None
+            }
+            SourceUnit::StructDefinition(definition) => Some(definition.name.value),
+            SourceUnit::EnumDefinition(definition) => Some(definition.name.value),
+            SourceUnit::FunctionDefinition(function) => function.name.map(|name| name.value),
+            SourceUnit::ErrorDefinition(error) => Some(error.name.value),
+            SourceUnit::ConstantDeclaration(constant) => Some(constant.name.value),
+            SourceUnit::UserDefinedValueTypeDefinition(definition) => Some(definition.name.value),
+            SourceUnit::PragmaDirective(_)
+            | SourceUnit::UsingForDeclaration(_)
+            | SourceUnit::Unknown(_) => None,
+        };
+
+        names.extend(name);
+    }
+
+    names.into_iter().map(str::to_string).collect()
+}
+
+fn part_name<'ast>(part: ContractPart<'ast>) -> Option<&'ast str> {
+    match part {
+        ContractPart::StateVariableDeclaration(variable) => Some(variable.name.value),
+        ContractPart::StructDefinition(definition) => Some(definition.name.value),
+        ContractPart::ModifierDefinition(modifier) => Some(modifier.name.value),
+        ContractPart::FunctionDefinition(function) => function.name.map(|name| name.value),
+        ContractPart::EventDefinition(event) => Some(event.name.value),
+        ContractPart::EnumDefinition(definition) => Some(definition.name.value),
+        ContractPart::ErrorDefinition(error) => Some(error.name.value),
+        ContractPart::UserDefinedValueTypeDefinition(definition) => Some(definition.name.value),
+        ContractPart::UsingForDeclaration(_) | ContractPart::Unknown(_) => None,
+    }
+}
+
+/// Names an import brings into the scope of the file.
+fn import_names<'ast>(import: ImportDirective<'ast>) -> Vec<&'ast str> {
+    match import {
+        ImportDirective::Global { alias, .. } => {
+            alias.map(|alias| alias.value).into_iter().collect()
+        }
+        ImportDirective::From { symbol, alias, .. } => alias
+            .or(symbol)
+            .map(|name| name.value)
+            .into_iter()
+            .collect(),
+        ImportDirective::ManyFrom { imports, .. } => imports
+            .iter()
+            .map(|import| import.value.alias.unwrap_or(import.value.symbol).value)
+            .collect(),
+    }
+}
+
+fn collect_block<'ast>(body: &'ast StatementList<'ast>, requires: &mut Vec<Require<'ast>>) {
+    for statement in body.iter() {
+        collect_statement(statement, requires);
+    }
+}
+
+/// Statements are collected by reference so they can be replaced within their parent.
+fn collect_statement<'ast>(
+    statement: &'ast StatementNode<'ast>,
+    requires: &mut Vec<Require<'ast>>,
+) {
+    match &statement.value {
+        Statement::ExpressionStatement(expression) => {
+            if let Some((condition, message)) = require_with_message(*expression) {
+                requires.push(Require {
+                    statement,
+                    condition,
+                    message,
+                });
+            }
+        }
+        Statement::BlockStatement(block) => collect_block(&block.body, requires),
+        Statement::UncheckedBlock(unchecked) => {
+            collect_block(&unchecked.block.value.body, requires)
+        }
+        Statement::IfStatement(statement) => {
+            collect_statement(&statement.consequent, requires);
+
+            if let Some(alternate) = &statement.alternate {
+                collect_statement(alternate, requires);
+            }
+        }
+        Statement::WhileStatement(statement) => collect_statement(&statement.body, requires),
+        Statement::ForStatement(statement) => collect_statement(&statement.body, requires),
+        Statement::DoWhileStatement(statement) => collect_statement(&statement.body, requires),
+        Statement::TryStatement(statement) => {
+            collect_block(&statement.body.value.body, requires);
+
+            for clause in statement.clauses.iter() {
+                collect_block(&clause.value.body.value.body, requires);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Matches `require(condition, "message")`, returning the condition and the unquoted message.
+fn require_with_message<'ast>(
+    expression: ExpressionNode<'ast>,
+) -> Option<(ExpressionNode<'ast>, &'ast str)> {
+    let call = match expression.value {
+        Expression::CallExpression(call) => call,
+        _ => return None,
+    };
+
+    if call.callee.value != Expression::IdentifierExpression("require") {
+        return None;
+    }
+
+    let mut arguments = match call.arguments {
+        CallArguments::Positional(arguments) => arguments.iter(),
+        CallArguments::Named(_) => return None,
+    };
+    let condition = *arguments.next()?;
+    let message = match arguments.next()?.value {
+        Expression::PrimitiveExpression(Primitive::String(message)) => message,
+        _ => return None,
+    };
+
+    if arguments.next().is_some() {
+        return None;
+    }
+
+    Some((condition, &message[1..message.len() - 1]))
+}
+
+/// Turns `require(cond, "message");` into `if (!cond) revert Error();` in place.
+fn rewrite_require<'ast>(arena: &'ast Arena, require: &Require<'ast>, error: &'ast str) {
+    let (start, end) = (require.statement.start, require.statement.end);
+    let condition = require.condition;
+
+    let test = match condition.value {
+        // `require(!cond)` becomes `if (cond)` rather than `if (!!cond)`
+        Expression::PrefixExpression(PrefixExpression { operator, operand })
+            if operator.value == PrefixOperator::LogicalNot =>
+        {
+            operand
+        }
+        _ => {
+            let operand = match condition.value {
+                Expression::IdentifierExpression(_)
+                | Expression::PrimitiveExpression(_)
+                | Expression::CallExpression(_)
+                | Expression::MemberAccessExpression(_)
+                | Expression::IndexAccessExpression(_)
+                | Expression::TupleExpression(_) => condition,
+                _ => alloc(
+                    arena,
+                    condition.start,
+                    condition.end,
+                    TupleExpression {
+                        expressions: List::from(arena, condition),
+                    }
+                    .into(),
+                ),
+            };
+
+            let operator = PrefixOperator::LogicalNot;
+            let operator = alloc(arena, start, start, operator);
+
+            alloc(
+                arena,
+                start,
+                end,
+                PrefixExpression { operator, operand }.into(),
+            )
+        }
+    };
+
+    let callee = alloc(arena, start, end, Expression::IdentifierExpression(error));
+    let call = CallExpression {
+        callee,
+        arguments: CallArguments::Positional(NodeList::empty()),
+    };
+    let revert = RevertStatement {
+        error: alloc(arena, start, end, call.into()),
+    };
+    let consequent = alloc(arena, start, end, revert.into());
+
+    let statement = IfStatement {
+        test,
+        consequent,
+        alternate: None,
+    };
+
+    require
+        .statement
+        .set(arena.alloc(NodeInner::new(start, end, statement.into())));
+}
+
+/// Builds a PascalCase error name out of the message, e.g. `"Ownable: caller is
+/// not the owner"` becomes `OwnableCallerIsNotTheOwner`, avoiding names in `taken`.
+fn error_name(message: &str, taken: &[String]) -> String {
+    let mut name: String = message
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().map(|c| c.to_ascii_uppercase());
+
+            first.into_iter().chain(chars).collect::<String>()
+        })
+        .collect();
+
+    if name.is_empty() || name == "Error" || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "Custom");
+    }
+
+    if !taken.contains(&name) {
+        return name;
+    }
+
+    (2..)
+        .map(|n| format!("{}{}", name, n))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("Names are unbounded; qed")
+}
+
+/// Estimated bytecode saved by a single `require` call site with the given message.
+fn estimate_savings(message: &str) -> usize {
+    let words = message.len().div_ceil(32);
+
+    STRING_REVERT_OVERHEAD + words * BYTES_PER_MESSAGE_WORD - CUSTOM_ERROR_REVERT
+}
+
+#[inline]
+fn alloc<'ast, T: Copy>(arena: &'ast Arena, start: u32, end: u32, value: T) -> Node<'ast, T> {
+    Node::new(arena.alloc(NodeInner::new(start, end, value)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use optimizoor_ast::{ContractPartList, ContractPartNode};
+    use optimizoor_parser::parse;
+
+    fn contract_body<'ast>(program: &Program<'ast>) -> ContractPartList<'ast> {
+        program
+            .body()
+            .iter()
+            .find_map(|unit| match unit.value {
+                SourceUnit::ContractDefinition(contract) => Some(contract.body),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    fn function_statements<'ast>(part: ContractPartNode<'ast>) -> Vec<Statement<'ast>> {
+        match part.value {
+            ContractPart::FunctionDefinition(function) => function
+                .block
+                .unwrap()
+                .value
+                .body
+                .iter()
+                .map(|statement| statement.value)
+                .collect(),
+            _ => panic!("Expected a function"),
+        }
+    }
+
+    #[test]
+    fn rewrites_require_with_message() {
+        let program = parse(
+            r#"
+            pragma solidity ^0.8.4;
+
+            contract Vault {
+                function withdraw(uint amount) {
+                    require(amount > 0, "Vault: zero amount");
+                    require(!paused, "paused");
+                    require(amount < limit, "Vault: zero amount");
+                    require(ok);
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let reports = replace_require_strings(&program);
+
+        assert_eq!(
+            reports,
+            vec![
+                CustomErrorReport {
+                    contract: "Vault".into(),
+                    message: "Vault: zero amount".into(),
+                    error: "VaultZeroAmount".into(),
+                    occurrences: 2,
+                    estimated_savings: 2 * (64 + 34 - 12),
+                },
+                CustomErrorReport {
+                    contract: "Vault".into(),
+                    message: "paused".into(),
+                    error: "Paused".into(),
+                    occurrences: 1,
+                    estimated_savings: 64 + 34 - 12,
+                },
+            ]
+        );
+
+        let body: Vec<_> = contract_body(&program).iter().cloned().collect();
+
+        assert_eq!(body.len(), 3);
+
+        for (part, expected) in body.iter().zip(["VaultZeroAmount", "Paused"]) {
+            match part.value {
+                ContractPart::ErrorDefinition(error) => assert_eq!(error.name.value, expected),
+                _ => panic!("Expected an error definition"),
+            }
+        }
+
+        let statements = function_statements(body[2]);
+
+        let reverts: Vec<_> = statements
+            .iter()
+            .filter_map(|statement| match statement {
+                Statement::IfStatement(statement) => Some(statement),
+                _ => None,
+            })
+            .map(|statement| match statement.consequent.value {
+                Statement::RevertStatement(revert) => match revert.error.value {
+                    Expression::CallExpression(call) => call.callee.value,
+                    _ => panic!("Expected a call"),
+                },
+                _ => panic!("Expected a revert"),
+            })
+            .collect();
+
+        assert_eq!(
+            reverts,
+            [
+                Expression::IdentifierExpression("VaultZeroAmount"),
+                Expression::IdentifierExpression("Paused"),
+                Expression::IdentifierExpression("VaultZeroAmount"),
+            ]
+        );
+
+        match statements[1] {
+            Statement::IfStatement(statement) => {
+                assert_eq!(
+                    statement.test.value,
+                    Expression::IdentifierExpression("paused")
+                )
+            }
+            _ => panic!("Expected an if statement"),
+        }
+
+        match statements[3] {
+            Statement::ExpressionStatement(_) => {}
+            _ => panic!("`require` without a message should be kept"),
+        }
+    }
+
+    #[test]
+    fn requires_solidity_0_8_4() {
+        let source = r#"
+            pragma solidity ^0.8.0;
+
+            contract Vault {
+                function withdraw() {
+                    require(false, "nope");
+                }
+            }
+        "#;
+
+        let program = parse(source).unwrap();
+
+        assert_eq!(replace_require_strings(&program), vec![]);
+        assert_eq!(contract_body(&program).iter().count(), 1);
+    }
+
+    #[test]
+    fn avoids_declared_names() {
+        let program = parse(
+            r#"
+            pragma solidity ^0.8.4;
+
+            import {Ownable as NotOwner} from "./Ownable.sol";
+
+            error Unauthorized();
+
+            contract Pausable {
+                bool paused;
+
+                function zeroAmount() public {}
+            }
+
+            contract Vault is Pausable {
+                event Paused(address account);
+
+                function withdraw(uint amount) {
+                    require(!paused, "paused");
+                    require(amount > 0, "zero amount");
+                    require(msg.sender == owner, "unauthorized");
+                    require(msg.sender == owner, "not owner");
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let errors: Vec<_> = replace_require_strings(&program)
+            .into_iter()
+            .map(|report| report.error)
+            .collect();
+
+        assert_eq!(
+            errors,
+            ["Paused2", "ZeroAmount", "Unauthorized2", "NotOwner2"]
+        );
+    }
+
+    #[test]
+    fn error_names() {
+        let taken = vec!["Paused".to_string(), "Paused2".to_string()];
+
+        assert_eq!(
+            error_name("Ownable: caller is not the owner", &taken),
+            "OwnableCallerIsNotTheOwner"
+        );
+        assert_eq!(error_name("paused", &taken), "Paused3");
+        assert_eq!(error_name("404", &taken), "Custom404");
+        assert_eq!(error_name("!!", &taken), "Custom");
+    }
+}
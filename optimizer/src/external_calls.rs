@@ -0,0 +1,216 @@
+//! External Call Analysis
+
+use optimizoor_ast::{
+    walk_contract_definition, walk_expression, walk_program, ContractDefinition, ContractPart,
+    ContractPartNode, ElementaryTypeName, Expression, ExpressionNode, FunctionDefinition, Program,
+    SourceUnit, SourceUnitNode, TryStatement, Visitor,
+};
+
+/// How a call was determined to leave the current contract.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExternalCallKind {
+    /// The call of a `try` statement, which solidity requires to be external.
+    Try,
+    /// `this.f(...)`, calling the contract itself through a message call.
+    This,
+    /// Sends ether, either through the `value` call option or with `transfer` or `send`
+    /// on a `payable(...)` conversion.
+    Value,
+}
+
+/// A call that is known to be external. Storage read before it can change by the time
+/// it returns, so it can't be cached across the call.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExternalCall {
+    pub contract: String,
+    pub function: Option<String>,
+    pub kind: ExternalCallKind,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Lists the calls within function bodies that are known to be external.
+///
+/// Member calls like `token.transfer(...)` are usually external as well, but can't be told
+/// apart from library calls without type information, so they aren't reported.
+pub fn external_calls(program: &Program) -> Vec<ExternalCall> {
+    let mut collector = Collector {
+        contract: "",
+        function: None,
+        calls: Vec::new(),
+    };
+
+    walk_program(&mut collector, program);
+
+    collector.calls
+}
+
+struct Collector<'ast> {
+    contract: &'ast str,
+    function: Option<&'ast str>,
+    calls: Vec<ExternalCall>,
+}
+
+impl<'ast> Collector<'ast> {
+    fn push(&mut self, kind: ExternalCallKind, call: ExpressionNode<'ast>) {
+        self.calls.push(ExternalCall {
+            contract: self.contract.to_string(),
+            function: self.function.map(str::to_string),
+            kind,
+            start: call.start,
+            end: call.end,
+        });
+    }
+}
+
+impl<'ast> Visitor<'ast> for Collector<'ast> {
+    // Only functions of contracts are analyzed
+    fn visit_source_unit(&mut self, unit: SourceUnitNode<'ast>) {
+        if let SourceUnit::ContractDefinition(contract) = unit.value {
+            self.visit_contract_definition(&contract);
+        }
+    }
+
+    fn visit_contract_definition(&mut self, contract: &ContractDefinition<'ast>) {
+        self.contract = contract.name.value;
+
+        walk_contract_definition(self, contract);
+    }
+
+    fn visit_contract_part(&mut self, part: ContractPartNode<'ast>) {
+        if let ContractPart::FunctionDefinition(function) = part.value {
+            self.visit_function_definition(&function);
+        }
+    }
+
+    fn visit_function_definition(&mut self, function: &FunctionDefinition<'ast>) {
+        self.function = function.name.map(|name| name.value);
+
+        if let Some(block) = function.block {
+            self.visit_block(&block.value);
+        }
+    }
+
+    fn visit_try_statement(&mut self, statement: &TryStatement<'ast>) {
+        self.push(ExternalCallKind::Try, statement.call);
+
+        // The callee and arguments are evaluated before the call is made, walking
+        // the call directly keeps it from being reported twice
+        walk_expression(self, statement.call);
+        self.visit_block(&statement.body.value);
+
+        for clause in statement.clauses.iter() {
+            self.visit_catch_clause(*clause);
+        }
+    }
+
+    fn visit_expression(&mut self, expression: ExpressionNode<'ast>) {
+        if let Expression::CallExpression(call) = expression.value {
+            if let Some(kind) = call_kind(call.callee) {
+                self.push(kind, expression);
+            }
+        }
+
+        walk_expression(self, expression);
+    }
+}
+
+/// Tells how a call leaves the contract from its callee, if it's known to.
+fn call_kind(callee: ExpressionNode) -> Option<ExternalCallKind> {
+    match callee.value {
+        Expression::CallOptionsExpression(call) => {
+            if call
+                .options
+                .iter()
+                .any(|option| option.value.name.value == "value")
+            {
+                Some(ExternalCallKind::Value)
+            } else {
+                call_kind(call.callee)
+            }
+        }
+        Expression::MemberAccessExpression(access) => match access.object.value {
+            Expression::ThisExpression => Some(ExternalCallKind::This),
+            // Unlike `token.transfer(...)`, the conversion makes it an ether transfer
+            Expression::CallExpression(conversion)
+                if conversion.callee.value
+                    == Expression::ElementaryTypeExpression(ElementaryTypeName::AddressPayable)
+                    && matches!(access.member.value, "transfer" | "send") =>
+            {
+                Some(ExternalCallKind::Value)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use optimizoor_parser::parse;
+
+    #[test]
+    fn try_calls_are_external() {
+        let program = parse(
+            r#"
+            contract Foo {
+                function bar() {
+                    try oracle.price(this.asset()) returns (uint price) {
+                        total += price;
+                    } catch {
+                        token.transfer(owner, 1);
+                    }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let calls: Vec<_> = external_calls(&program)
+            .into_iter()
+            .map(|call| (call.kind, call.function.unwrap()))
+            .collect();
+
+        assert_eq!(
+            calls,
+            [
+                (ExternalCallKind::Try, "bar".to_string()),
+                (ExternalCallKind::This, "bar".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn value_transfers_are_external() {
+        let program = parse(
+            r#"
+            contract Foo {
+                function pay(address to) {
+                    to.call{value: amount, gas: 5000}("");
+                    payable(msg.sender).transfer(amount);
+                    payable(to).send(amount);
+                    this.bar{gas: 5000}();
+                    token.transfer(to, amount);
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let calls: Vec<_> = external_calls(&program)
+            .into_iter()
+            .map(|call| call.kind)
+            .collect();
+
+        assert_eq!(
+            calls,
+            [
+                ExternalCallKind::Value,
+                ExternalCallKind::Value,
+                ExternalCallKind::Value,
+                ExternalCallKind::This,
+            ]
+        );
+    }
+}
@@ -1,3 +1,13 @@
-
-
+mod custom_errors;
+mod external_calls;
+mod pipeline;
+mod pragma;
 mod struct_packing;
+mod unchecked_loops;
+
+pub use self::custom_errors::*;
+pub use self::external_calls::*;
+pub use self::pipeline::*;
+pub use self::pragma::{pragma_allows, Version};
+pub use self::struct_packing::*;
+pub use self::unchecked_loops::*;
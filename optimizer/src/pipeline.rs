@@ -0,0 +1,193 @@
+//! Runs the optimisation passes a program's compiler version allows.
+
+use std::collections::HashSet;
+
+use optimizoor_ast::Program;
+
+use crate::pragma::{pragma_allows, Version};
+use crate::{
+    optimize_structs_except, replace_require_strings, uncheck_loop_increments, CustomErrorReport,
+    StructPackingReport, UncheckedLoopReport, CUSTOM_ERRORS_MIN_VERSION,
+    UNCHECKED_LOOPS_MIN_VERSION,
+};
+
+/// A single optimisation that rewrites the program in place. Passes are shared by the
+/// threads the files of a project are analyzed on.
+pub trait Pass: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Oldest compiler the rewritten code still compiles with, `None` if any will do.
+    fn min_version(&self) -> Option<Version> {
+        None
+    }
+
+    fn run<'ast>(&self, program: &'ast Program<'ast>) -> Vec<Report>;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Report {
+    CustomErrors(CustomErrorReport),
+    UncheckedLoop(UncheckedLoopReport),
+    StructPacking(StructPackingReport),
+}
+
+/// Replaces `require` strings with custom errors.
+pub struct CustomErrors;
+
+/// Wraps loop increments in `unchecked` blocks.
+pub struct UncheckedLoops;
+
+/// Reorders struct members into fewer storage slots.
+#[derive(Default)]
+pub struct StructPacking {
+    /// Structs left alone on top of those the program itself pins
+    pinned: HashSet<String>,
+}
+
+impl StructPacking {
+    pub fn pinning(pinned: HashSet<String>) -> Self {
+        StructPacking { pinned }
+    }
+}
+
+impl Pass for CustomErrors {
+    fn name(&self) -> &'static str {
+        "custom-errors"
+    }
+
+    fn min_version(&self) -> Option<Version> {
+        Some(CUSTOM_ERRORS_MIN_VERSION)
+    }
+
+    fn run<'ast>(&self, program: &'ast Program<'ast>) -> Vec<Report> {
+        replace_require_strings(program)
+            .into_iter()
+            .map(Report::CustomErrors)
+            .collect()
+    }
+}
+
+impl Pass for UncheckedLoops {
+    fn name(&self) -> &'static str {
+        "unchecked-loops"
+    }
+
+    fn min_version(&self) -> Option<Version> {
+        Some(UNCHECKED_LOOPS_MIN_VERSION)
+    }
+
+    fn run<'ast>(&self, program: &'ast Program<'ast>) -> Vec<Report> {
+        uncheck_loop_increments(program)
+            .into_iter()
+            .map(Report::UncheckedLoop)
+            .collect()
+    }
+}
+
+impl Pass for StructPacking {
+    fn name(&self) -> &'static str {
+        "struct-packing"
+    }
+
+    fn run<'ast>(&self, program: &'ast Program<'ast>) -> Vec<Report> {
+        optimize_structs_except(program, &self.pinned)
+            .into_iter()
+            .map(Report::StructPacking)
+            .collect()
+    }
+}
+
+/// Outcome of running a `Pipeline` over a program.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PipelineOutput {
+    pub reports: Vec<Report>,
+    /// Names of the passes the program's `pragma solidity` doesn't allow.
+    pub skipped: Vec<&'static str>,
+}
+
+pub struct Pipeline {
+    passes: Vec<Box<dyn Pass>>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Pipeline { passes: Vec::new() }
+    }
+
+    pub fn with_pass<P: Pass + 'static>(mut self, pass: P) -> Self {
+        self.passes.push(Box::new(pass));
+        self
+    }
+
+    /// Every pass, leaving the structs named in `pinned` in their order, like those the
+    /// other files of a project construct or expose.
+    pub fn pinning_structs(pinned: HashSet<String>) -> Self {
+        Pipeline::new()
+            .with_pass(CustomErrors)
+            .with_pass(UncheckedLoops)
+            .with_pass(StructPacking::pinning(pinned))
+    }
+
+    pub fn run<'ast>(&self, program: &'ast Program<'ast>) -> PipelineOutput {
+        let mut output = PipelineOutput::default();
+
+        for pass in &self.passes {
+            if let Some(required) = pass.min_version() {
+                if !pragma_allows(program, required) {
+                    output.skipped.push(pass.name());
+                    continue;
+                }
+            }
+
+            output.reports.extend(pass.run(program));
+        }
+
+        output
+    }
+}
+
+impl Default for Pipeline {
+    /// Every pass, in the order they should be applied.
+    fn default() -> Self {
+        Pipeline::pinning_structs(HashSet::new())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use optimizoor_parser::parse;
+
+    const SOURCE: &str = r#"
+        contract Foo {
+            function bar(uint256 n) public {
+                require(n > 0, "zero");
+                for (uint256 i = 0; i < n; i++) {}
+            }
+        }
+    "#;
+
+    #[test]
+    fn skips_passes_the_pragma_does_not_allow() {
+        let program = parse(&format!("pragma solidity >=0.8.0 <0.9.0;{}", SOURCE)).unwrap();
+        let output = Pipeline::default().run(&program);
+
+        assert_eq!(output.skipped, ["custom-errors"]);
+        assert!(matches!(output.reports[..], [Report::UncheckedLoop(_)]));
+
+        let program = parse(&format!("pragma solidity ^0.8.4;{}", SOURCE)).unwrap();
+        let output = Pipeline::default().run(&program);
+
+        assert!(output.skipped.is_empty());
+        assert!(matches!(
+            output.reports[..],
+            [Report::CustomErrors(_), Report::UncheckedLoop(_)]
+        ));
+
+        let program = parse(SOURCE).unwrap();
+        let output = Pipeline::default().run(&program);
+
+        assert_eq!(output.skipped, ["custom-errors", "unchecked-loops"]);
+        assert!(output.reports.is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+use optimizoor_ast::{
+    PragmaDirective, PragmaVersion, Program, SourceUnit, VersionComparator, VersionOperator,
+    VersionRangeList,
+};
+
+/// A `major.minor.patch` compiler version.
+pub type Version = (u32, u32, u32);
+
+/// Returns `true` if every compiler allowed by the `pragma solidity` directives
+/// of the program is at least `required`. Programs without a pragma are rejected
+/// since we can't tell which compiler will be used.
+pub fn pragma_allows(program: &Program<'_>, required: Version) -> bool {
+    program
+        .body()
+        .iter()
+        .filter_map(|unit| match unit.value {
+            SourceUnit::PragmaDirective(PragmaDirective::Solidity { ranges }) => {
+                Some(min_version(ranges))
+            }
+            _ => None,
+        })
+        .max()
+        .is_some_and(|version| version >= required)
+}
+
+/// Lowest version matched by a version pragma such as `solidity ^0.8.4`
+/// or `solidity >=0.6.0 <0.9.0 || ^0.5.0`.
+fn min_version(ranges: VersionRangeList<'_>) -> Version {
+    ranges
+        .iter()
+        .map(|range| {
+            range
+                .value
+                .comparators
+                .iter()
+                .filter_map(|comparator| lower_bound(comparator.value))
+                .max()
+                .unwrap_or((0, 0, 0))
+        })
+        .min()
+        .unwrap_or((0, 0, 0))
+}
+
+/// Lower bound set by a single comparator, `<` and `<=` don't set any.
+fn lower_bound(comparator: VersionComparator) -> Option<Version> {
+    let PragmaVersion {
+        major,
+        minor,
+        patch,
+    } = comparator.version;
+
+    match comparator.operator {
+        VersionOperator::Lesser | VersionOperator::LesserEquals => None,
+        VersionOperator::Greater => Some(match (minor, patch) {
+            (Some(minor), Some(patch)) => (major, minor, patch + 1),
+            (Some(minor), None) => (major, minor + 1, 0),
+            (None, _) => (major + 1, 0, 0),
+        }),
+        _ => Some((major, minor.unwrap_or(0), patch.unwrap_or(0))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use optimizoor_parser::parse;
+
+    fn min_version_of(source: &str) -> Version {
+        let program = parse(source).unwrap();
+
+        program
+            .body()
+            .iter()
+            .find_map(|unit| match unit.value {
+                SourceUnit::PragmaDirective(PragmaDirective::Solidity { ranges }) => {
+                    Some(min_version(ranges))
+                }
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn minimal_versions() {
+        assert_eq!(min_version_of("pragma solidity ^0.8.4;"), (0, 8, 4));
+        assert_eq!(min_version_of("pragma solidity 0.8.19;"), (0, 8, 19));
+        assert_eq!(min_version_of("pragma solidity >=0.6 <0.9;"), (0, 6, 0));
+        assert_eq!(min_version_of("pragma solidity >0.8.3;"), (0, 8, 4));
+        assert_eq!(min_version_of("pragma solidity >0.7;"), (0, 8, 0));
+        assert_eq!(
+            min_version_of("pragma solidity ^0.8.0 || ^0.7.0;"),
+            (0, 7, 0)
+        );
+        assert_eq!(min_version_of("pragma solidity <0.9.0;"), (0, 0, 0));
+        assert_eq!(min_version_of("pragma solidity 0.8.x;"), (0, 8, 0));
+    }
+
+    #[test]
+    fn ignores_other_pragmas() {
+        let program = parse("pragma abicoder v2; pragma experimental SMTChecker;").unwrap();
+
+        assert!(!pragma_allows(&program, (0, 0, 0)));
+    }
+}
@@ -1,55 +1,260 @@
+//! Struct Packing Optimisation
+
 use optimizoor_ast::{
-    ContractPart, ElementaryTypeName, Program, SourceUnit, StructDefinition, TypeName,
-    TypeNameNode, VariableDeclaration,
+    BlockNode, CallArguments, ContractDefinition, ContractPart, ElementaryTypeName, Expression,
+    ExpressionNode, FunctionDefinition, FunctionVisibility, NodeInner, Program, SimpleStatement,
+    SourceUnit, StateVariableVisibility, Statement, StatementNode, StructDefinition, TypeName,
+    Unknown, VariableDeclarationNode,
 };
 use regex::Regex;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
+use toolshed::list::GrowableList;
+
+/// Above this many fields smaller than a slot, the exhaustive search is replaced
+/// by first-fit decreasing, which is optimal or one slot off in practice.
+const MAX_EXHAUSTIVE_FIELDS: usize = 10;
+
+/// A struct whose fields were reordered to use less storage slots.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StructPackingReport {
+    /// `None` for structs declared at file level.
+    pub contract: Option<String>,
+    pub name: String,
+    pub slots_before: usize,
+    pub slots_after: usize,
+    /// Field names in their new order.
+    pub fields: Vec<String>,
+}
 
-/// Struct Packing Optimisation
+/// Optimizes the packing of all structs in the program, both those declared within
+/// contracts and at file level.
+///
+/// Changing the order of fields changes the order of arguments of the struct constructor
+/// and the ABI of the struct, so structs constructed with positional arguments, or used
+/// by public functions, events or public state variables are left untouched.
+pub fn optimize_structs<'ast>(program: &'ast Program<'ast>) -> Vec<StructPackingReport> {
+    optimize_structs_except(program, &HashSet::new())
+}
+
+/// Like `optimize_structs`, also leaving untouched the structs named in `except`. Other
+/// files of a project can construct or expose the structs a file declares, so their
+/// `pinned_structs` have to be taken into account too.
+pub fn optimize_structs_except<'ast>(
+    program: &'ast Program<'ast>,
+    except: &HashSet<String>,
+) -> Vec<StructPackingReport> {
+    let arena = program.arena();
+    let pinned = pinned_structs(program);
+    let mut reports = Vec::new();
+
+    let contracts: HashMap<_, _> = program
+        .body()
+        .iter()
+        .filter_map(|unit| match unit.value {
+            SourceUnit::ContractDefinition(contract_def) => {
+                Some((contract_def.name.value, contract_def))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let file_types: Types = program
+        .body()
+        .iter()
+        .filter_map(|unit| match unit.value {
+            SourceUnit::ContractDefinition(contract_def) => {
+                Some((contract_def.name.value, DataType::Address))
+            }
+            SourceUnit::StructDefinition(struct_def) => {
+                Some((struct_def.name.value, DataType::Slot))
+            }
+            SourceUnit::EnumDefinition(enum_def) => Some((enum_def.name.value, DataType::Enum)),
+            SourceUnit::UserDefinedValueTypeDefinition(definition) => Some((
+                definition.name.value,
+                elementary_data_type(definition.underlying.value),
+            )),
+            _ => None,
+        })
+        .collect();
+
+    let mut optimize =
+        |contract: Option<&str>, struct_def: &StructDefinition<'ast>, types: &Types<'ast>| {
+            let name = struct_def.name.value;
+
+            if pinned.contains(name) || except.contains(name) {
+                return None;
+            }
+
+            let (struct_def, report) = optimize_struct_definition(struct_def, types)?;
+
+            reports.push(StructPackingReport {
+                contract: contract.map(str::to_string),
+                ..report
+            });
+
+            let body = GrowableList::new();
+
+            for field in struct_def {
+                body.push(arena, field);
+            }
+
+            Some(body.as_list())
+        };
 
-/// Optimizes the packing of all structs in the program.
-pub fn optimize_structs(program: &mut Program<'_>) {
     for source_unit in program.body().iter() {
         match source_unit.value {
             SourceUnit::ContractDefinition(contract_def) => {
-                // get the struct definitions
-                let mut struct_defs = contract_def // contract_def is a ContractDefinition<'_>
-                    .body
-                    .iter()
-                    .filter_map(|part| match part.value {
-                        ContractPart::StructDefinition(struct_def) => {
-                            let mut struct_def = struct_def;
-                            optimize_struct_definition(&mut struct_def);
-                            Some(struct_def)
+                // Types declared in the contract shadow those declared at file level
+                let mut contract_types = file_types.clone();
+
+                inherited_types(
+                    &contract_def,
+                    &contracts,
+                    &mut HashSet::new(),
+                    &mut contract_types,
+                );
+
+                for part in contract_def.body.iter() {
+                    if let ContractPart::StructDefinition(struct_def) = part.value {
+                        let contract = Some(contract_def.name.value);
+
+                        if let Some(body) = optimize(contract, &struct_def, &contract_types) {
+                            let struct_def = StructDefinition { body, ..struct_def };
+
+                            part.set(arena.alloc(NodeInner::new(
+                                part.start,
+                                part.end,
+                                struct_def.into(),
+                            )));
                         }
-                        _ => None,
-                    });
+                    }
+                }
+            }
+            SourceUnit::StructDefinition(struct_def) => {
+                if let Some(body) = optimize(None, &struct_def, &file_types) {
+                    let struct_def = StructDefinition { body, ..struct_def };
+
+                    source_unit.set(arena.alloc(NodeInner::new(
+                        source_unit.start,
+                        source_unit.end,
+                        struct_def.into(),
+                    )));
+                }
             }
-            SourceUnit::PragmaDirective(_) => {}
-            SourceUnit::ImportDirective(_) => {}
+            _ => {}
         }
     }
+
+    reports
 }
 
-/// function to optimize a single struct
-fn optimize_struct_definition(struct_def: &mut StructDefinition) {
-    let struct_body = struct_def.body;
-    // map them into field type
-    let mut fields: Vec<Field> = struct_body
+/// Finds a better order for the fields of a single struct, returning the reordered
+/// field declarations if it uses less slots than the current one. Structs with fields
+/// of unknown size are left alone.
+fn optimize_struct_definition<'ast>(
+    struct_def: &StructDefinition<'ast>,
+    types: &Types<'ast>,
+) -> Option<(Vec<VariableDeclarationNode<'ast>>, StructPackingReport)> {
+    let fields = get_fields_from_definition(struct_def, types)?;
+    let slots_before = count_slots(&fields);
+
+    let packed_slots = pack_fields(fields);
+    let slots_after = packed_slots.len();
+
+    if slots_after >= slots_before {
+        return None;
+    }
+
+    let declarations: Vec<_> = struct_def.body.iter().copied().collect();
+
+    // Flatten the fields from the storage slots while maintaining the new order
+    let packed_fields: Vec<Field> = packed_slots
+        .into_iter()
+        .flat_map(|slot| slot.fields)
+        .collect();
+
+    let report = StructPackingReport {
+        contract: None,
+        name: struct_def.name.value.to_string(),
+        slots_before,
+        slots_after,
+        fields: packed_fields
+            .iter()
+            .map(|field| field.name.clone())
+            .collect(),
+    };
+
+    let reordered = packed_fields
         .iter()
-        .map(|field| {
-            let data_type = to_data_type(&field.value.type_name.value);
-            let size = data_type.size();
-            Field {
-                name: field.value.id.value.to_string(),
-                ty: data_type,
-                size: size,
-            }
-        })
+        .map(|field| declarations[field.index])
         .collect();
 
+    Some((reordered, report))
+}
+
+/// Number of slots used by the fields when laid out in the given order.
+fn count_slots(fields: &[Field]) -> usize {
+    let mut slots = 0;
+    let mut offset = 32;
+
+    for field in fields {
+        if offset + field.size > 32 {
+            slots += 1;
+            offset = 0;
+        }
+
+        offset += field.size;
+    }
+
+    slots
+}
+
+/// Packs fields into as few slots as possible. Fields taking a whole slot are kept
+/// first, in their original order.
+fn pack_fields(mut fields: Vec<Field>) -> Vec<StorageSlot> {
     // Sort fields in decreasing order of size
-    fields.sort_by(|a, b| b.size.cmp(&a.size));
+    fields.sort_by_key(|field| Reverse(field.size));
+
+    let split = fields.iter().take_while(|field| field.size >= 32).count();
+    let small = fields.split_off(split);
+
+    let mut slots: Vec<StorageSlot> = fields
+        .into_iter()
+        .map(|field| StorageSlot {
+            offset: field.size,
+            fields: vec![field],
+        })
+        .collect();
+
+    if small.len() > MAX_EXHAUSTIVE_FIELDS {
+        slots.extend(first_fit(small));
+    } else {
+        slots.extend(bin_packing(small, Vec::new()));
+    }
+
+    slots
+}
+
+/// Puts each field in the first slot it fits in, fields are expected to be sorted by size.
+fn first_fit(fields: Vec<Field>) -> Vec<StorageSlot> {
+    let mut slots: Vec<StorageSlot> = Vec::new();
+
+    for field in fields {
+        match slots.iter_mut().find(|slot| slot.offset + field.size <= 32) {
+            Some(slot) => {
+                slot.offset += field.size;
+                slot.fields.push(field);
+            }
+            None => slots.push(StorageSlot {
+                offset: field.size,
+                fields: vec![field],
+            }),
+        }
+    }
+
+    slots
 }
 
 /// Performs a bin-packing algorithm to pack fields into storage slots.
@@ -118,67 +323,95 @@ fn count_filled_slots(slots: &[StorageSlot]) -> usize {
     slots.iter().filter(|slot| slot.offset == 32).count()
 }
 
-/// Extracts and transforms fields from a struct definition into a vector of `Field` objects.
-fn get_fields_from_definition(struct_def: &StructDefinition) -> Vec<Field> {
+/// Extracts and transforms fields from a struct definition into a vector of `Field` objects,
+/// `None` if the size of any of them is unknown.
+fn get_fields_from_definition(struct_def: &StructDefinition, types: &Types) -> Option<Vec<Field>> {
     struct_def
         .body
         .iter()
-        .map(|field| {
-            let data_type = to_data_type(&field.value.type_name.value);
-            Field {
+        .enumerate()
+        .map(|(index, field)| {
+            let data_type = to_data_type(&field.value.type_name.value, types)?;
+            Some(Field {
                 name: field.value.id.value.to_string(),
-                ty: data_type.clone(),
+                index,
                 size: data_type.size(),
-            }
+            })
         })
         .collect()
 }
 
-/// Applies the optimized packing order of fields back to the struct definition.
-fn apply_packing_to_definition(struct_def: &mut StructDefinition, packed_slots: Vec<StorageSlot>) {
-    // Flatten the fields from the storage slots while maintaining the new order
-    let packed_fields: Vec<Field> = packed_slots
-        .into_iter()
-        .flat_map(|slot| slot.fields)
-        .collect();
+/// Data types of the types declared by name: contracts, structs, enums and user
+/// defined value types.
+type Types<'ast> = HashMap<&'ast str, DataType>;
+
+/// Adds the types declared within the contract and the contracts it inherits from in
+/// the same file, the most derived ones last so that they shadow the others.
+fn inherited_types<'ast>(
+    contract_def: &ContractDefinition<'ast>,
+    contracts: &HashMap<&'ast str, ContractDefinition<'ast>>,
+    visited: &mut HashSet<&'ast str>,
+    types: &mut Types<'ast>,
+) {
+    if !visited.insert(contract_def.name.value) {
+        return;
+    }
 
-    // How do i convert back with new updated location? the id would shift as well
+    for base in contract_def.inherits.iter() {
+        if let Some(base) = contracts.get(base.value) {
+            inherited_types(base, contracts, visited, types);
+        }
+    }
+
+    types.extend(
+        contract_def
+            .body
+            .iter()
+            .filter_map(|part| match part.value {
+                ContractPart::StructDefinition(struct_def) => {
+                    Some((struct_def.name.value, DataType::Slot))
+                }
+                ContractPart::EnumDefinition(enum_def) => {
+                    Some((enum_def.name.value, DataType::Enum))
+                }
+                ContractPart::UserDefinedValueTypeDefinition(definition) => Some((
+                    definition.name.value,
+                    elementary_data_type(definition.underlying.value),
+                )),
+                _ => None,
+            }),
+    );
 }
 
 // Converts from AST type to field type for sorting. Only for struct packing now
-fn to_data_type(ty: &TypeName) -> DataType {
+fn to_data_type(ty: &TypeName, types: &Types) -> Option<DataType> {
     match ty {
-        TypeName::ElementaryTypeName(name) => match name {
-            ElementaryTypeName::Address => DataType::Address,
-            ElementaryTypeName::Bool => DataType::Bool,
-            ElementaryTypeName::String => DataType::String,
-            ElementaryTypeName::Bytes => DataType::Bytes(None),
-            ElementaryTypeName::Int(size) => DataType::Int(Some(*size)),
-            ElementaryTypeName::Uint(size) => DataType::Uint(Some(*size)),
-            _ => unimplemented!(),
-        },
-        _ => unimplemented!(),
-        // TypeName::UserDefinedTypeName(name) => DataType::String,
-        // TypeName::Mapping(_) => DataType::String, // Unimplemented
-        // TypeName::ArrayTypeName => DataType::String, // Unimplemented
-        // TypeName::FunctionTypeName => DataType::String, // Unimplemented
+        TypeName::ElementaryTypeName(name) => Some(elementary_data_type(*name)),
+        // Names declared in other files, or qualified by a contract, are left unresolved
+        TypeName::UserDefinedTypeName(name) => types.get(name).cloned(),
+        // Arrays and mappings always start a new slot, and so does the next field
+        TypeName::Mapping(_) | TypeName::ArrayTypeName(_) => Some(DataType::Slot),
+        // Internal and external function types differ in size
+        TypeName::FunctionTypeName => None,
     }
 }
 
-// Converts from Data type to AST type after sorting.
-fn to_type_name(ty: &DataType) -> TypeName {
-    match ty {
-        DataType::Address => TypeName::ElementaryTypeName(ElementaryTypeName::Address),
-        DataType::Bool => TypeName::ElementaryTypeName(ElementaryTypeName::Bool),
-        DataType::String => TypeName::ElementaryTypeName(ElementaryTypeName::String),
-        DataType::Bytes(None) => TypeName::ElementaryTypeName(ElementaryTypeName::Bytes),
-        DataType::Int(Some(size)) => TypeName::ElementaryTypeName(ElementaryTypeName::Int(*size)),
-        DataType::Uint(Some(size)) => TypeName::ElementaryTypeName(ElementaryTypeName::Uint(*size)),
-        _ => unimplemented!(),
+fn elementary_data_type(name: ElementaryTypeName) -> DataType {
+    match name {
+        ElementaryTypeName::Address | ElementaryTypeName::AddressPayable => DataType::Address,
+        ElementaryTypeName::Bool => DataType::Bool,
+        ElementaryTypeName::String => DataType::String,
+        ElementaryTypeName::Bytes => DataType::Bytes(None),
+        ElementaryTypeName::Byte(size) => DataType::Bytes(Some(size)),
+        ElementaryTypeName::Int(size) => DataType::Int(Some(size)),
+        ElementaryTypeName::Uint(size) => DataType::Uint(Some(size)),
+        ElementaryTypeName::Fixed(size, _) | ElementaryTypeName::Ufixed(size, _) => {
+            DataType::Fixed(size)
+        }
     }
 }
 
-/// Data types for struct fields
+/// Data types for struct fields, sizes are in bytes
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 enum DataType {
     String,
@@ -186,15 +419,21 @@ enum DataType {
     Bool,
     Int(Option<u8>),  // None for "int", Some(size) for "int<size>"
     Uint(Option<u8>), // None for "uint", Some(size) for "uint<size>"
+    Fixed(u8),
+    /// Addresses, and contracts and interfaces
     Address,
+    /// Enums have at most 256 members, which fit in a single byte
+    Enum,
+    /// Any type that takes at least a whole slot
+    Slot,
 }
 
 impl FromStr for DataType {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let int_regex = Regex::new(r"u?int(\d+)").unwrap();
-        let bytes_regex = Regex::new(r"bytes(\d+)").unwrap();
+        let int_regex = Regex::new(r"^u?int(\d+)$").unwrap();
+        let bytes_regex = Regex::new(r"^bytes(\d+)$").unwrap();
 
         match s {
             "string" => Ok(DataType::String),
@@ -203,19 +442,33 @@ impl FromStr for DataType {
             "int" => Ok(DataType::Int(None)),
             "uint" => Ok(DataType::Uint(None)),
             "address" => Ok(DataType::Address),
-            _ if s.ends_with("[]") => Ok(DataType::String), // Assuming the type is dynamic array
+            _ if s.ends_with("[]") => Ok(DataType::Slot), // Assuming the type is dynamic array
             _ if int_regex.is_match(s) => {
                 let captures = int_regex.captures(s).unwrap();
-                let size = captures.get(1).unwrap().as_str().parse::<u8>().unwrap();
+                let bits = captures
+                    .get(1)
+                    .unwrap()
+                    .as_str()
+                    .parse::<u16>()
+                    .map_err(|_| ())?;
+                if bits == 0 || bits > 256 || bits % 8 != 0 {
+                    return Err(());
+                }
+                let size = Some((bits / 8) as u8);
                 if s.starts_with("uint") {
-                    Ok(DataType::Uint(Some(size)))
+                    Ok(DataType::Uint(size))
                 } else {
-                    Ok(DataType::Int(Some(size)))
+                    Ok(DataType::Int(size))
                 }
             }
             _ if bytes_regex.is_match(s) => {
                 let captures = bytes_regex.captures(s).unwrap();
-                let size = captures.get(1).unwrap().as_str().parse::<u8>().unwrap();
+                let size = captures
+                    .get(1)
+                    .unwrap()
+                    .as_str()
+                    .parse::<u8>()
+                    .map_err(|_| ())?;
                 Ok(DataType::Bytes(Some(size)))
             }
             _ => Err(()), // Unknown type
@@ -226,36 +479,309 @@ impl FromStr for DataType {
 impl DataType {
     fn size(&self) -> u8 {
         match self {
-            DataType::String | DataType::Bytes(None) => 32,
-            DataType::Bool => 1,
-            DataType::Int(None) | DataType::Uint(None) | DataType::Address => 32,
-            DataType::Int(Some(size)) | DataType::Uint(Some(size)) => size / 8,
-            DataType::Bytes(Some(size)) => *size,
+            DataType::String | DataType::Bytes(None) | DataType::Slot => 32,
+            DataType::Bool | DataType::Enum => 1,
+            DataType::Address => 20,
+            DataType::Int(None) | DataType::Uint(None) => 32,
+            DataType::Int(Some(size)) | DataType::Uint(Some(size)) => *size,
+            DataType::Bytes(Some(size)) | DataType::Fixed(size) => *size,
         }
     }
+}
 
-    fn clone(&self) -> DataType {
-        match self {
-            DataType::String => DataType::String,
-            DataType::Bytes(size) => DataType::Bytes(*size),
-            DataType::Bool => DataType::Bool,
-            DataType::Int(size) => DataType::Int(*size),
-            DataType::Uint(size) => DataType::Uint(*size),
-            DataType::Address => DataType::Address,
+/// Names of structs whose field order can't change as far as the program can tell:
+/// those it constructs with positional arguments or exposes in its ABI, whichever file
+/// declares them.
+pub fn pinned_structs<'ast>(program: &Program<'ast>) -> HashSet<&'ast str> {
+    let mut pinned = HashSet::new();
+    let mut structs = Vec::new();
+    let mut functions = Vec::new();
+    let mut initializers = Vec::new();
+
+    for source_unit in program.body().iter() {
+        match source_unit.value {
+            SourceUnit::ContractDefinition(contract_def) => {
+                for part in contract_def.body.iter() {
+                    match part.value {
+                        ContractPart::StructDefinition(struct_def) => structs.push(struct_def),
+                        ContractPart::FunctionDefinition(function) => {
+                            let exposed = !matches!(
+                                function.visibility.map(|visibility| visibility.value),
+                                Some(FunctionVisibility::Internal)
+                                    | Some(FunctionVisibility::Private)
+                            );
+
+                            if exposed {
+                                for param in function.params.iter().chain(function.returns.iter()) {
+                                    mentioned_structs(param.value.type_name.value, &mut pinned);
+                                }
+                            }
+
+                            functions.push(function);
+                        }
+                        ContractPart::EventDefinition(event) => {
+                            for param in event.params.iter() {
+                                mentioned_structs(param.value.type_name.value, &mut pinned);
+                            }
+                        }
+                        ContractPart::StateVariableDeclaration(variable) => {
+                            if variable.visibility.map(|visibility| visibility.value)
+                                == Some(StateVariableVisibility::Public)
+                            {
+                                mentioned_structs(variable.type_name.value, &mut pinned);
+                            }
+
+                            initializers.extend(variable.init);
+                        }
+                        ContractPart::ModifierDefinition(modifier) => {
+                            for statement in modifier.block.value.body.iter() {
+                                constructed_structs(*statement, &mut pinned);
+                            }
+                        }
+                        ContractPart::Unknown(unknown) => {
+                            mentioned_in_unknown(unknown, &mut pinned)
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            SourceUnit::StructDefinition(struct_def) => structs.push(struct_def),
+            SourceUnit::FunctionDefinition(function) => functions.push(function),
+            SourceUnit::ConstantDeclaration(constant) => initializers.push(constant.init),
+            SourceUnit::Unknown(unknown) => mentioned_in_unknown(unknown, &mut pinned),
+            _ => {}
+        }
+    }
+
+    for function in functions {
+        constructed_in_function(&function, &mut pinned);
+    }
+
+    for init in initializers {
+        constructed_in_expression(init, &mut pinned);
+    }
+
+    // Structs nested within pinned structs are part of the same ABI
+    loop {
+        let before = pinned.len();
+
+        for struct_def in structs.iter() {
+            if pinned.contains(struct_def.name.value) {
+                for field in struct_def.body.iter() {
+                    mentioned_structs(field.value.type_name.value, &mut pinned);
+                }
+            }
         }
+
+        if pinned.len() == before {
+            break;
+        }
+    }
+
+    pinned
+}
+
+/// Adds the names of user defined types within the type to the set.
+fn mentioned_structs<'ast>(type_name: TypeName<'ast>, names: &mut HashSet<&'ast str>) {
+    match type_name {
+        TypeName::UserDefinedTypeName(name) => {
+            names.insert(name);
+        }
+        TypeName::Mapping(mapping) => mentioned_structs(mapping.to.value, names),
+        TypeName::ArrayTypeName(array) => mentioned_structs(array.base.value, names),
+        _ => {}
+    }
+}
+
+/// Adds every identifier within source the parser couldn't make sense of, since it
+/// might use any of the structs.
+fn mentioned_in_unknown<'ast>(unknown: Unknown<'ast>, names: &mut HashSet<&'ast str>) {
+    let words = unknown
+        .raw
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '$');
+
+    names.extend(words.filter(|word| !word.is_empty()));
+}
+
+fn constructed_in_function<'ast>(
+    function: &FunctionDefinition<'ast>,
+    names: &mut HashSet<&'ast str>,
+) {
+    if let Some(block) = function.block {
+        for statement in block.value.body.iter() {
+            constructed_structs(*statement, names);
+        }
+    }
+}
+
+/// Adds the names of all callees called with positional arguments to the set, which
+/// includes struct constructors such as `Point(1, 2)`.
+fn constructed_structs<'ast>(statement: StatementNode<'ast>, names: &mut HashSet<&'ast str>) {
+    let block = |block: BlockNode<'ast>, names: &mut HashSet<&'ast str>| {
+        for statement in block.value.body.iter() {
+            constructed_structs(*statement, names);
+        }
+    };
+
+    match statement.value {
+        Statement::ExpressionStatement(expression) => constructed_in_expression(expression, names),
+        Statement::BlockStatement(body) => {
+            for statement in body.body.iter() {
+                constructed_structs(*statement, names);
+            }
+        }
+        Statement::UncheckedBlock(unchecked) => block(unchecked.block, names),
+        Statement::TryStatement(statement) => {
+            constructed_in_expression(statement.call, names);
+            block(statement.body, names);
+
+            for clause in statement.clauses.iter() {
+                block(clause.value.body, names);
+            }
+        }
+        Statement::IfStatement(statement) => {
+            constructed_in_expression(statement.test, names);
+            constructed_structs(statement.consequent, names);
+
+            if let Some(alternate) = statement.alternate {
+                constructed_structs(alternate, names);
+            }
+        }
+        Statement::WhileStatement(statement) => {
+            constructed_in_expression(statement.test, names);
+            constructed_structs(statement.body, names);
+        }
+        Statement::DoWhileStatement(statement) => {
+            constructed_structs(statement.body, names);
+            constructed_in_expression(statement.test, names);
+        }
+        Statement::ForStatement(statement) => {
+            if let Some(init) = statement.init {
+                match init.value {
+                    SimpleStatement::VariableDefinitionStatement(definition) => {
+                        if let Some(init) = definition.init {
+                            constructed_in_expression(init, names);
+                        }
+                    }
+                    SimpleStatement::InferredDefinitionStatement(definition) => {
+                        constructed_in_expression(definition.init, names)
+                    }
+                    SimpleStatement::ExpressionStatement(expression) => {
+                        constructed_in_expression(expression, names)
+                    }
+                }
+            }
+
+            statement
+                .test
+                .into_iter()
+                .chain(statement.update)
+                .for_each(|expression| constructed_in_expression(expression, names));
+
+            constructed_structs(statement.body, names);
+        }
+        Statement::ReturnStatement(statement) => {
+            if let Some(value) = statement.value {
+                constructed_in_expression(value, names);
+            }
+        }
+        Statement::VariableDefinitionStatement(statement) => {
+            if let Some(init) = statement.init {
+                constructed_in_expression(init, names);
+            }
+        }
+        Statement::InferredDefinitionStatement(statement) => {
+            constructed_in_expression(statement.init, names)
+        }
+        Statement::EmitStatement(statement) => constructed_in_expression(statement.event, names),
+        Statement::RevertStatement(statement) => constructed_in_expression(statement.error, names),
+        Statement::Unknown(unknown) => mentioned_in_unknown(unknown, names),
+        Statement::InlineAssemblyStatement(_)
+        | Statement::Placeholder
+        | Statement::ContinueStatement
+        | Statement::BreakStatement
+        | Statement::ThrowStatement => {}
     }
 }
 
-fn main() {
-    let data_type = "uint256".parse::<DataType>().expect("Invalid data type");
-    println!("The size of {:?} is {} bytes.", data_type, data_type.size());
+fn constructed_in_expression<'ast>(
+    expression: ExpressionNode<'ast>,
+    names: &mut HashSet<&'ast str>,
+) {
+    match expression.value {
+        Expression::CallExpression(call) => {
+            // Named arguments are matched to fields by name, so the order doesn't matter
+            if let CallArguments::Positional(_) = call.arguments {
+                match call.callee.value {
+                    Expression::IdentifierExpression(name) => {
+                        names.insert(name);
+                    }
+                    // `Library.Struct(...)`
+                    Expression::MemberAccessExpression(access) => {
+                        names.insert(access.member.value);
+                    }
+                    _ => {}
+                }
+            }
+
+            constructed_in_expression(call.callee, names);
+
+            for argument in call.arguments.values() {
+                constructed_in_expression(argument, names);
+            }
+        }
+        Expression::CallOptionsExpression(call) => {
+            constructed_in_expression(call.callee, names);
+
+            for option in call.options.iter() {
+                constructed_in_expression(option.value.value, names);
+            }
+        }
+        Expression::PrefixExpression(prefix) => constructed_in_expression(prefix.operand, names),
+        Expression::PostfixExpression(postfix) => constructed_in_expression(postfix.operand, names),
+        Expression::BinaryExpression(binary) => {
+            constructed_in_expression(binary.left, names);
+            constructed_in_expression(binary.right, names);
+        }
+        Expression::AssignmentExpression(assignment) => {
+            constructed_in_expression(assignment.left, names);
+            constructed_in_expression(assignment.right, names);
+        }
+        Expression::TupleExpression(tuple) => {
+            for expression in tuple.expressions.iter() {
+                constructed_in_expression(*expression, names);
+            }
+        }
+        Expression::MemberAccessExpression(access) => {
+            constructed_in_expression(access.object, names)
+        }
+        Expression::IndexAccessExpression(access) => {
+            constructed_in_expression(access.array, names);
+
+            if let Some(index) = access.index {
+                constructed_in_expression(index, names);
+            }
+        }
+        Expression::ConditionalExpression(conditional) => {
+            constructed_in_expression(conditional.test, names);
+            constructed_in_expression(conditional.consequent, names);
+            constructed_in_expression(conditional.alternate, names);
+        }
+        Expression::ThisExpression
+        | Expression::IdentifierExpression(_)
+        | Expression::PrimitiveExpression(_)
+        | Expression::ElementaryTypeExpression(_)
+        | Expression::NewExpression(_)
+        | Expression::TypeExpression(_) => {}
+    }
 }
 
 /// Field is a struct that represents a field in a struct
 #[derive(Clone)]
 struct Field {
     name: String,
-    ty: DataType,
+    /// Position of the field within the struct definition
+    index: usize,
     size: u8, // Size of the field in bytes
 }
 
@@ -266,3 +792,227 @@ struct StorageSlot {
     fields: Vec<Field>,
     offset: u8, // Offset of the storage slot in the struct i.e How many bytes before 32 bytes slot hits.
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use optimizoor_parser::parse;
+
+    fn struct_fields<'ast>(struct_def: StructDefinition<'ast>) -> Vec<&'ast str> {
+        struct_def
+            .body
+            .iter()
+            .map(|field| field.value.id.value)
+            .collect()
+    }
+
+    #[test]
+    fn data_type_sizes() {
+        assert_eq!("uint256".parse::<DataType>().unwrap().size(), 32);
+        assert_eq!("int64".parse::<DataType>().unwrap().size(), 8);
+        assert_eq!("bytes4".parse::<DataType>().unwrap().size(), 4);
+        assert_eq!("address".parse::<DataType>().unwrap().size(), 20);
+        assert_eq!("uint[]".parse::<DataType>().unwrap().size(), 32);
+        assert_eq!("uint512".parse::<DataType>(), Err(()));
+    }
+
+    #[test]
+    fn packs_contract_and_file_level_structs() {
+        let program = parse(
+            r#"
+            struct Order {
+                uint128 amount;
+                uint256 id;
+                uint128 price;
+            }
+
+            contract Exchange {
+                struct Account {
+                    bool active;
+                    uint256 balance;
+                    address owner;
+                    mapping(address => uint) allowance;
+                    uint64 nonce;
+                }
+
+                struct Packed {
+                    uint128 a;
+                    uint128 b;
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let reports = optimize_structs(&program);
+
+        assert_eq!(
+            reports,
+            vec![
+                StructPackingReport {
+                    contract: None,
+                    name: "Order".into(),
+                    slots_before: 3,
+                    slots_after: 2,
+                    fields: vec!["id".into(), "amount".into(), "price".into()],
+                },
+                StructPackingReport {
+                    contract: Some("Exchange".into()),
+                    name: "Account".into(),
+                    slots_before: 5,
+                    slots_after: 3,
+                    fields: vec![
+                        "balance".into(),
+                        "allowance".into(),
+                        "owner".into(),
+                        "nonce".into(),
+                        "active".into(),
+                    ],
+                },
+            ]
+        );
+
+        let units: Vec<_> = program.body().iter().map(|unit| unit.value).collect();
+
+        match units[0] {
+            SourceUnit::StructDefinition(order) => {
+                assert_eq!(struct_fields(order), ["id", "amount", "price"]);
+            }
+            _ => panic!("Expected a struct"),
+        }
+
+        match units[1] {
+            SourceUnit::ContractDefinition(contract) => {
+                let structs: Vec<_> = contract
+                    .body
+                    .iter()
+                    .filter_map(|part| match part.value {
+                        ContractPart::StructDefinition(struct_def) => {
+                            Some(struct_fields(struct_def))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+
+                assert_eq!(
+                    structs,
+                    [
+                        vec!["balance", "allowance", "owner", "nonce", "active"],
+                        vec!["a", "b"],
+                    ]
+                );
+            }
+            _ => panic!("Expected a contract"),
+        }
+    }
+
+    #[test]
+    fn sizes_user_defined_value_types() {
+        let program = parse(
+            r#"
+            type Amount is uint64;
+
+            struct Position { Amount amount; uint256 id; Amount fee; }
+
+            contract Pool {
+                type Price is uint128;
+
+                struct Quote { Price bid; uint256 at; Price ask; }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let reports: Vec<_> = optimize_structs(&program)
+            .into_iter()
+            .map(|report| (report.name, report.slots_before, report.slots_after))
+            .collect();
+
+        assert_eq!(reports, [("Position".into(), 3, 2), ("Quote".into(), 3, 2)]);
+    }
+
+    #[test]
+    fn sizes_enums_and_contracts() {
+        let program = parse(
+            r#"
+            contract Token {}
+
+            contract Base {
+                enum Status { Active, Paused }
+            }
+
+            contract Vault is Base {
+                struct Flags { uint8 a; Status s; uint8 b; }
+                struct Holding { Token token; uint256 amount; Status status; }
+                struct Foreign { uint128 a; Imported b; uint128 c; }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let reports: Vec<_> = optimize_structs(&program)
+            .into_iter()
+            .map(|report| (report.name, report.slots_before, report.slots_after))
+            .collect();
+
+        assert_eq!(reports, [("Holding".into(), 3, 2)]);
+    }
+
+    #[test]
+    fn keeps_structs_other_files_depend_on() {
+        let types =
+            parse("struct Order { uint128 amount; address maker; uint128 price; }").unwrap();
+        let book = parse(
+            r#"
+            import "./Types.sol";
+
+            contract Book {
+                function place(uint128 a, uint128 p) external returns (Order memory order) {
+                    order = Order(a, msg.sender, p);
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let pinned: HashSet<String> = pinned_structs(&book)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+
+        assert!(pinned.contains("Order"));
+        assert_eq!(optimize_structs_except(&types, &pinned), vec![]);
+        assert_eq!(optimize_structs(&types)[0].slots_after, 2);
+    }
+
+    #[test]
+    fn keeps_structs_with_fixed_order() {
+        let program = parse(
+            r#"
+            struct Built { uint128 a; uint256 b; uint128 c; }
+            struct Returned { uint128 a; uint256 b; uint128 c; }
+            struct Emitted { uint128 a; uint256 b; uint128 c; }
+            struct Nested { uint128 a; uint256 b; uint128 c; }
+
+            contract Foo {
+                event Log(Emitted emitted);
+
+                function build() internal {
+                    built = Built(1, 2, 3);
+                }
+
+                function get() returns (Returned) {}
+            }
+
+            struct Outer { Nested nested; }
+
+            contract Bar {
+                Outer public outer;
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(optimize_structs(&program), vec![]);
+    }
+}
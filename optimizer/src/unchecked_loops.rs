@@ -0,0 +1,721 @@
+//! Unchecked Loop Increment Optimisation
+
+use optimizoor_ast::{
+    AssemblyItem, AssemblyItemList, AssignmentOperator, BinaryOperator, Block, BlockNode,
+    ContractPart, ElementaryTypeName, Expression, ExpressionNode, ForStatement, FunctionDefinition,
+    Node, NodeInner, NumberUnit, PostfixOperator, PrefixExpression, PrefixOperator, Primitive,
+    Program, SimpleStatement, SourceUnit, Statement, StatementList, StatementNode, TypeName,
+    UncheckedBlock,
+};
+use toolshed::list::{GrowableList, List};
+use toolshed::Arena;
+
+use crate::pragma::{pragma_allows, Version};
+
+/// `unchecked` blocks were introduced in solidity 0.8.0, along with checked arithmetic.
+pub const UNCHECKED_LOOPS_MIN_VERSION: Version = (0, 8, 0);
+
+/// A loop whose counter increment was moved into an `unchecked` block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UncheckedLoopReport {
+    pub contract: String,
+    pub function: Option<String>,
+    pub counter: String,
+    /// Byte offset of the `for` statement in the source.
+    pub start: u32,
+}
+
+/// Rewrites `for (uint i; i < bound; i++) { ... }` into
+/// `for (uint i; i < bound;) { ... unchecked { ++i; } }` when the increment can't overflow.
+///
+/// The bound must be an array `length` (for `uint256` counters), an integer literal that
+/// fits the counter type, or a function parameter of the same type as the counter. The
+/// counter can't be modified anywhere within the loop body, and every `continue` of the
+/// loop is preceded by the increment so it still runs.
+pub fn uncheck_loop_increments<'ast>(program: &'ast Program<'ast>) -> Vec<UncheckedLoopReport> {
+    if !pragma_allows(program, UNCHECKED_LOOPS_MIN_VERSION) {
+        return Vec::new();
+    }
+
+    let arena = program.arena();
+    let mut reports = Vec::new();
+
+    for unit in program.body().iter() {
+        let contract = match &unit.value {
+            SourceUnit::ContractDefinition(contract) => contract,
+            _ => continue,
+        };
+
+        for part in contract.body.iter() {
+            let function = match &part.value {
+                ContractPart::FunctionDefinition(function) => function,
+                _ => continue,
+            };
+
+            let block = match &function.block {
+                Some(block) => block,
+                None => continue,
+            };
+
+            let mut loops = Vec::new();
+
+            for statement in block.value.body.iter() {
+                collect_loops(statement, &mut loops);
+            }
+
+            for statement in loops {
+                if let Some(counter) = uncheck_loop(arena, function, statement) {
+                    reports.push(UncheckedLoopReport {
+                        contract: contract.name.value.to_string(),
+                        function: function.name.map(|name| name.value.to_string()),
+                        counter: counter.to_string(),
+                        start: statement.start,
+                    });
+                }
+            }
+        }
+    }
+
+    reports
+}
+
+/// Collects `for` statements by reference, inner loops before the loops that contain them,
+/// so that inner loops are rewritten before the body of the outer loop is rebuilt.
+fn collect_loops<'ast>(
+    statement: &'ast StatementNode<'ast>,
+    loops: &mut Vec<&'ast StatementNode<'ast>>,
+) {
+    match &statement.value {
+        Statement::BlockStatement(block) => {
+            for statement in block.body.iter() {
+                collect_loops(statement, loops);
+            }
+        }
+        Statement::UncheckedBlock(unchecked) => {
+            for statement in unchecked.block.value.body.iter() {
+                collect_loops(statement, loops);
+            }
+        }
+        Statement::IfStatement(statement) => {
+            collect_loops(&statement.consequent, loops);
+
+            if let Some(alternate) = &statement.alternate {
+                collect_loops(alternate, loops);
+            }
+        }
+        Statement::WhileStatement(statement) => collect_loops(&statement.body, loops),
+        Statement::DoWhileStatement(statement) => collect_loops(&statement.body, loops),
+        Statement::TryStatement(statement) => {
+            for statement in statement.body.value.body.iter() {
+                collect_loops(statement, loops);
+            }
+
+            for clause in statement.clauses.iter() {
+                for statement in clause.value.body.value.body.iter() {
+                    collect_loops(statement, loops);
+                }
+            }
+        }
+        Statement::ForStatement(for_statement) => {
+            collect_loops(&for_statement.body, loops);
+
+            loops.push(statement);
+        }
+        _ => {}
+    }
+}
+
+fn uncheck_loop<'ast>(
+    arena: &'ast Arena,
+    function: &FunctionDefinition<'ast>,
+    statement: &'ast StatementNode<'ast>,
+) -> Option<&'ast str> {
+    let for_statement = match &statement.value {
+        Statement::ForStatement(for_statement) => for_statement,
+        _ => return None,
+    };
+
+    let (counter, counter_type) = match for_statement.init?.value {
+        SimpleStatement::VariableDefinitionStatement(definition) => {
+            let declaration = definition.declaration.value;
+
+            match declaration.type_name.value {
+                TypeName::ElementaryTypeName(ElementaryTypeName::Uint(size)) => {
+                    (declaration.id.value, size)
+                }
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    let update = for_statement.update?;
+
+    if !is_increment(update, counter)
+        || !is_bounded(for_statement.test?, counter, counter_type, function)
+        || modifies_statement(for_statement.body, counter)
+    {
+        return None;
+    }
+
+    for continue_statement in collect_continues(&for_statement.body) {
+        let (start, end) = (continue_statement.start, continue_statement.end);
+        let increment = unchecked_increment(arena, counter, update);
+        let jump = alloc(arena, start, end, Statement::ContinueStatement);
+
+        let block = Block {
+            body: List::from_iter(arena, [increment, jump]),
+        };
+
+        continue_statement.set(arena.alloc(NodeInner::new(start, end, block.into())));
+    }
+
+    let body = GrowableList::new();
+
+    match for_statement.body.value {
+        Statement::BlockStatement(block) => {
+            for statement in block.body.iter() {
+                body.push(arena, *statement);
+            }
+        }
+        _ => body.push(arena, for_statement.body),
+    }
+
+    body.push(arena, unchecked_increment(arena, counter, update));
+
+    let (start, end) = (for_statement.body.start, for_statement.body.end);
+    let block = Block {
+        body: body.as_list(),
+    };
+
+    let for_statement = ForStatement {
+        update: None,
+        body: alloc(arena, start, end, block.into()),
+        ..*for_statement
+    };
+
+    statement.set(arena.alloc(NodeInner::new(
+        statement.start,
+        statement.end,
+        for_statement.into(),
+    )));
+
+    Some(counter)
+}
+
+/// `i++`, `++i` or `i += 1`.
+fn is_increment(update: ExpressionNode, counter: &str) -> bool {
+    match update.value {
+        Expression::PostfixExpression(postfix) => {
+            postfix.operator.value == PostfixOperator::Increment
+                && is_identifier(postfix.operand, counter)
+        }
+        Expression::PrefixExpression(prefix) => {
+            prefix.operator.value == PrefixOperator::Increment
+                && is_identifier(prefix.operand, counter)
+        }
+        Expression::AssignmentExpression(assignment) => {
+            assignment.operator.value == AssignmentOperator::Addition
+                && is_identifier(assignment.left, counter)
+                && integer_literal(assignment.right) == Some(1)
+        }
+        _ => false,
+    }
+}
+
+/// `i < bound` where `bound` can't be larger than the maximum value of the counter type.
+fn is_bounded(
+    test: ExpressionNode,
+    counter: &str,
+    size: u8,
+    function: &FunctionDefinition,
+) -> bool {
+    let test = match test.value {
+        Expression::BinaryExpression(binary) if binary.operator.value == BinaryOperator::Lesser => {
+            binary
+        }
+        _ => return false,
+    };
+
+    if !is_identifier(test.left, counter) {
+        return false;
+    }
+
+    match test.right.value {
+        // Array lengths are bound by memory and storage limits, way below `2**256 - 1`
+        Expression::MemberAccessExpression(access) => access.member.value == "length" && size == 32,
+        Expression::PrimitiveExpression(_) => match integer_literal(test.right) {
+            Some(bound) => size >= 16 || bound < 1u128 << (size as u32 * 8),
+            // Literals that don't fit in `u128` only fit in the largest counters
+            None => size == 32 && is_integer(test.right),
+        },
+        Expression::IdentifierExpression(bound) => function.params.iter().any(|param| {
+            param.value.name.map(|name| name.value) == Some(bound)
+                && param.value.type_name.value
+                    == TypeName::ElementaryTypeName(ElementaryTypeName::Uint(size))
+        }),
+        _ => false,
+    }
+}
+
+fn is_identifier(expression: ExpressionNode, name: &str) -> bool {
+    matches!(expression.value, Expression::IdentifierExpression(identifier) if identifier == name)
+}
+
+fn is_integer(expression: ExpressionNode) -> bool {
+    matches!(
+        expression.value,
+        Expression::PrimitiveExpression(Primitive::IntegerNumber(_, NumberUnit::None))
+            | Expression::PrimitiveExpression(Primitive::HexNumber(_))
+    )
+}
+
+/// Value of an integer literal without a unit, if it fits in `u128`.
+fn integer_literal(expression: ExpressionNode) -> Option<u128> {
+    match expression.value {
+        Expression::PrimitiveExpression(Primitive::IntegerNumber(number, NumberUnit::None)) => {
+            number.parse().ok()
+        }
+        Expression::PrimitiveExpression(Primitive::HexNumber(number)) => {
+            u128::from_str_radix(&number[2..], 16).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Returns `true` if the statement could change the value of `name`.
+fn modifies_statement(statement: StatementNode, name: &str) -> bool {
+    match statement.value {
+        Statement::ExpressionStatement(expression) => modifies(expression, name),
+        Statement::BlockStatement(block) => block.body.iter().any(|s| modifies_statement(*s, name)),
+        Statement::UncheckedBlock(unchecked) => unchecked
+            .block
+            .value
+            .body
+            .iter()
+            .any(|s| modifies_statement(*s, name)),
+        Statement::TryStatement(statement) => {
+            modifies(statement.call, name)
+                || statement
+                    .body
+                    .value
+                    .body
+                    .iter()
+                    .any(|s| modifies_statement(*s, name))
+                || statement.clauses.iter().any(|clause| {
+                    clause
+                        .value
+                        .body
+                        .value
+                        .body
+                        .iter()
+                        .any(|s| modifies_statement(*s, name))
+                })
+        }
+        Statement::IfStatement(statement) => {
+            modifies(statement.test, name)
+                || modifies_statement(statement.consequent, name)
+                || statement
+                    .alternate
+                    .is_some_and(|alternate| modifies_statement(alternate, name))
+        }
+        Statement::WhileStatement(statement) => {
+            modifies(statement.test, name) || modifies_statement(statement.body, name)
+        }
+        Statement::DoWhileStatement(statement) => {
+            modifies(statement.test, name) || modifies_statement(statement.body, name)
+        }
+        Statement::ForStatement(statement) => {
+            statement.init.is_some_and(|init| match init.value {
+                SimpleStatement::VariableDefinitionStatement(definition) => {
+                    definition.init.is_some_and(|init| modifies(init, name))
+                }
+                SimpleStatement::InferredDefinitionStatement(definition) => {
+                    modifies(definition.init, name)
+                }
+                SimpleStatement::ExpressionStatement(expression) => modifies(expression, name),
+            }) || statement.test.is_some_and(|test| modifies(test, name))
+                || statement
+                    .update
+                    .is_some_and(|update| modifies(update, name))
+                || modifies_statement(statement.body, name)
+        }
+        Statement::ReturnStatement(statement) => {
+            statement.value.is_some_and(|value| modifies(value, name))
+        }
+        Statement::VariableDefinitionStatement(statement) => {
+            statement.init.is_some_and(|init| modifies(init, name))
+        }
+        Statement::InferredDefinitionStatement(statement) => modifies(statement.init, name),
+        Statement::EmitStatement(statement) => modifies(statement.event, name),
+        Statement::RevertStatement(statement) => modifies(statement.error, name),
+        Statement::InlineAssemblyStatement(statement) => {
+            assembly_assigns(statement.block.value.items, name)
+        }
+        // Unparsed source might do anything
+        Statement::Unknown(_) => true,
+        Statement::Placeholder
+        | Statement::ContinueStatement
+        | Statement::BreakStatement
+        | Statement::ThrowStatement => false,
+    }
+}
+
+/// Returns `true` if the assembly assigns to `name`. Yul functions can't access variables
+/// from outside of their body, so they are skipped.
+fn assembly_assigns(items: AssemblyItemList, name: &str) -> bool {
+    items.iter().any(|item| match item.value {
+        AssemblyItem::AssemblyAssignment(assignment) => {
+            assignment.ids.iter().any(|id| id.value == name)
+        }
+        AssemblyItem::InlineAssemblyBlock(block) => assembly_assigns(block.items, name),
+        AssemblyItem::AssemblyIf(statement) => assembly_assigns(statement.body.value.items, name),
+        AssemblyItem::AssemblySwitch(switch) => {
+            switch
+                .cases
+                .iter()
+                .any(|case| assembly_assigns(case.value.body.value.items, name))
+                || switch
+                    .default
+                    .is_some_and(|default| assembly_assigns(default.value.items, name))
+        }
+        AssemblyItem::AssemblyFor(statement) => [statement.init, statement.update, statement.body]
+            .iter()
+            .any(|block| assembly_assigns(block.value.items, name)),
+        AssemblyItem::AssemblyLocalBinding(_)
+        | AssemblyItem::AssemblyFunctionDefinition(_)
+        | AssemblyItem::FunctionalAssemblyExpression(_)
+        | AssemblyItem::Leave
+        | AssemblyItem::Break
+        | AssemblyItem::Continue => false,
+    })
+}
+
+/// Returns `true` if the expression could change the value of `name`.
+fn modifies(expression: ExpressionNode, name: &str) -> bool {
+    match expression.value {
+        Expression::AssignmentExpression(assignment) => {
+            is_assignee(assignment.left, name) || modifies(assignment.right, name)
+        }
+        Expression::PrefixExpression(prefix) => match prefix.operator.value {
+            PrefixOperator::Increment | PrefixOperator::Decrement | PrefixOperator::Delete => {
+                is_identifier(prefix.operand, name) || modifies(prefix.operand, name)
+            }
+            _ => modifies(prefix.operand, name),
+        },
+        Expression::PostfixExpression(postfix) => {
+            is_identifier(postfix.operand, name) || modifies(postfix.operand, name)
+        }
+        Expression::BinaryExpression(binary) => {
+            modifies(binary.left, name) || modifies(binary.right, name)
+        }
+        Expression::TupleExpression(tuple) => tuple
+            .expressions
+            .iter()
+            .any(|expression| modifies(*expression, name)),
+        Expression::CallExpression(call) => {
+            modifies(call.callee, name)
+                || call
+                    .arguments
+                    .values()
+                    .any(|argument| modifies(argument, name))
+        }
+        Expression::CallOptionsExpression(call) => {
+            modifies(call.callee, name)
+                || call
+                    .options
+                    .iter()
+                    .any(|option| modifies(option.value.value, name))
+        }
+        Expression::MemberAccessExpression(access) => modifies(access.object, name),
+        Expression::IndexAccessExpression(access) => {
+            modifies(access.array, name) || access.index.is_some_and(|index| modifies(index, name))
+        }
+        Expression::ConditionalExpression(conditional) => {
+            modifies(conditional.test, name)
+                || modifies(conditional.consequent, name)
+                || modifies(conditional.alternate, name)
+        }
+        Expression::ThisExpression
+        | Expression::IdentifierExpression(_)
+        | Expression::PrimitiveExpression(_)
+        | Expression::ElementaryTypeExpression(_)
+        | Expression::NewExpression(_)
+        | Expression::TypeExpression(_) => false,
+    }
+}
+
+/// `name = ...` or `(name, other) = ...`.
+fn is_assignee(left: ExpressionNode, name: &str) -> bool {
+    match left.value {
+        Expression::TupleExpression(tuple) => tuple
+            .expressions
+            .iter()
+            .any(|expression| is_assignee(*expression, name)),
+        _ => is_identifier(left, name),
+    }
+}
+
+/// `continue` statements that belong to the loop with the given body, nested loops excluded.
+fn collect_continues<'ast>(body: &'ast StatementNode<'ast>) -> Vec<&'ast StatementNode<'ast>> {
+    fn collect<'ast>(
+        statement: &'ast StatementNode<'ast>,
+        continues: &mut Vec<&'ast StatementNode<'ast>>,
+    ) {
+        match &statement.value {
+            Statement::ContinueStatement => continues.push(statement),
+            Statement::BlockStatement(block) => collect_list(&block.body, continues),
+            Statement::UncheckedBlock(unchecked) => {
+                collect_list(&unchecked.block.value.body, continues)
+            }
+            Statement::TryStatement(statement) => {
+                collect_list(&statement.body.value.body, continues);
+
+                for clause in statement.clauses.iter() {
+                    collect_list(&clause.value.body.value.body, continues);
+                }
+            }
+            Statement::IfStatement(statement) => {
+                collect(&statement.consequent, continues);
+
+                if let Some(alternate) = &statement.alternate {
+                    collect(alternate, continues);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_list<'ast>(
+        list: &'ast StatementList<'ast>,
+        continues: &mut Vec<&'ast StatementNode<'ast>>,
+    ) {
+        for statement in list.iter() {
+            collect(statement, continues);
+        }
+    }
+
+    let mut continues = Vec::new();
+
+    collect(body, &mut continues);
+
+    continues
+}
+
+/// `unchecked { ++counter; }`, located at the original increment.
+fn unchecked_increment<'ast>(
+    arena: &'ast Arena,
+    counter: &'ast str,
+    update: ExpressionNode<'ast>,
+) -> StatementNode<'ast> {
+    let (start, end) = (update.start, update.end);
+
+    let increment = PrefixExpression {
+        operator: alloc(arena, start, end, PrefixOperator::Increment),
+        operand: alloc(arena, start, end, Expression::IdentifierExpression(counter)),
+    };
+    let increment: ExpressionNode = alloc(arena, start, end, increment.into());
+    let block: BlockNode = alloc(
+        arena,
+        start,
+        end,
+        Block {
+            body: List::from(arena, alloc(arena, start, end, increment.into())),
+        },
+    );
+
+    alloc(arena, start, end, UncheckedBlock { block }.into())
+}
+
+#[inline]
+fn alloc<'ast, T: Copy>(arena: &'ast Arena, start: u32, end: u32, value: T) -> Node<'ast, T> {
+    Node::new(arena.alloc(NodeInner::new(start, end, value)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use optimizoor_parser::parse;
+
+    fn function_loops<'ast>(program: &Program<'ast>) -> Vec<ForStatement<'ast>> {
+        let mut loops = Vec::new();
+
+        for unit in program.body().iter() {
+            if let SourceUnit::ContractDefinition(contract) = unit.value {
+                for part in contract.body.iter() {
+                    if let ContractPart::FunctionDefinition(function) = part.value {
+                        for statement in function.block.unwrap().value.body.iter() {
+                            if let Statement::ForStatement(statement) = statement.value {
+                                loops.push(statement);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        loops
+    }
+
+    fn last_statement<'ast>(statement: StatementNode<'ast>) -> Statement<'ast> {
+        match statement.value {
+            Statement::BlockStatement(block) => block.body.iter().last().unwrap().value,
+            _ => panic!("Expected a block"),
+        }
+    }
+
+    fn is_unchecked_increment(statement: Statement, counter: &str) -> bool {
+        match statement {
+            Statement::UncheckedBlock(unchecked) => {
+                match unchecked.block.value.body.only_element().map(|s| s.value) {
+                    Some(Statement::ExpressionStatement(expression)) => match expression.value {
+                        Expression::PrefixExpression(prefix) => {
+                            prefix.operator.value == PrefixOperator::Increment
+                                && is_identifier(prefix.operand, counter)
+                        }
+                        _ => false,
+                    },
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn unchecks_bounded_loops() {
+        let program = parse(
+            r#"
+            pragma solidity ^0.8.0;
+
+            contract Loops {
+                function sum(uint count) returns (uint total) {
+                    for (uint i = 0; i < values.length; i++) {
+                        total += values[i];
+                    }
+                    for (uint j; j < count; ++j) total += j;
+                    for (uint8 k; k < 255; k += 1) {}
+                    for (uint l; l < count; l++) {
+                        assembly {
+                            let value := sload(add(values.slot, l))
+                            if iszero(value) { total := add(total, 1) }
+                        }
+                    }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        let reports = uncheck_loop_increments(&program);
+        let counters: Vec<_> = reports.iter().map(|r| r.counter.as_str()).collect();
+
+        assert_eq!(counters, ["i", "j", "k", "l"]);
+        assert_eq!(reports[0].function.as_deref(), Some("sum"));
+
+        for (statement, counter) in function_loops(&program).into_iter().zip(counters) {
+            assert_eq!(statement.update, None);
+            assert!(is_unchecked_increment(
+                last_statement(statement.body),
+                counter
+            ));
+        }
+    }
+
+    #[test]
+    fn keeps_unsafe_loops() {
+        let program = parse(
+            r#"
+            pragma solidity ^0.8.0;
+
+            contract Loops {
+                function run(uint8 count) {
+                    for (uint8 a; a < values.length; a++) {}
+                    for (uint8 b; b < 256; b++) {}
+                    for (uint c; c < count; c++) {}
+                    for (uint d; d <= values.length; d++) {}
+                    for (uint e; e < values.length; e++) { e += 2; }
+                    for (uint f; f < values.length; f += 2) {}
+                    for (uint g; g < values.length; g++) { assembly { g := sload(g) } }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(uncheck_loop_increments(&program), vec![]);
+
+        for statement in function_loops(&program) {
+            assert!(statement.update.is_some());
+        }
+    }
+
+    #[test]
+    fn increments_before_continue() {
+        let program = parse(
+            r#"
+            pragma solidity ^0.8.0;
+
+            contract Loops {
+                function run() {
+                    for (uint i; i < values.length; i++) {
+                        if (values[i] == 0) continue;
+                        for (uint j; j < values.length; j) {
+                            continue;
+                        }
+                    }
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(uncheck_loop_increments(&program).len(), 1);
+
+        let statement = function_loops(&program)[0];
+        let body: Vec<_> = match statement.body.value {
+            Statement::BlockStatement(block) => block.body.iter().map(|s| s.value).collect(),
+            _ => panic!("Expected a block"),
+        };
+
+        match body[0] {
+            Statement::IfStatement(statement) => match statement.consequent.value {
+                Statement::BlockStatement(block) => {
+                    let body: Vec<_> = block.body.iter().map(|s| s.value).collect();
+
+                    assert!(is_unchecked_increment(body[0], "i"));
+                    assert_eq!(body[1], Statement::ContinueStatement);
+                }
+                _ => panic!("Expected a block"),
+            },
+            _ => panic!("Expected an if statement"),
+        }
+
+        match body[1] {
+            Statement::ForStatement(inner) => {
+                assert_eq!(last_statement(inner.body), Statement::ContinueStatement,);
+            }
+            _ => panic!("Expected a for statement"),
+        }
+
+        assert!(is_unchecked_increment(body[2], "i"));
+    }
+
+    #[test]
+    fn requires_solidity_0_8() {
+        let program = parse(
+            r#"
+            pragma solidity ^0.7.0;
+
+            contract Loops {
+                function run() {
+                    for (uint i; i < values.length; i++) {}
+                }
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(uncheck_loop_increments(&program), vec![]);
+    }
+}
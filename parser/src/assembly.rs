@@ -30,36 +30,106 @@ impl<'ast> Parser<'ast> {
     fn assembly_item(&mut self) -> Option<AssemblyItemNode<'ast>> {
         match self.lexer.token {
             Token::BraceOpen => self.inline_assembly_block(),
-            Token::Identifier => self.assembly_identifier(),
-            _ => None,
+            Token::KeywordIf => self.assembly_if(),
+            Token::KeywordFor => self.assembly_for(),
+            Token::DeclarationFunction => self.assembly_function_definition(),
+            Token::KeywordBreak => self.node_at_token(AssemblyItem::Break),
+            Token::KeywordContinue => self.node_at_token(AssemblyItem::Continue),
+            Token::ReservedWord => match self.lexer.slice() {
+                "let" => self.assembly_local_binding(),
+                "switch" => self.assembly_switch(),
+                _ => None,
+            },
+            Token::Identifier if self.lexer.slice() == "leave" => {
+                self.node_at_token(AssemblyItem::Leave)
+            }
+            _ => self.assembly_assignment_or_call(),
         }
     }
 
-    fn assembly_identifier(&mut self) -> Option<AssemblyItemNode<'ast>> {
-        let (start, end) = self.loc();
-        let identifier = self.lexer.slice();
+    /// Builtins such as `return`, `byte` or `address` are keywords in Solidity,
+    /// but plain identifiers in Yul.
+    fn is_assembly_identifier(&self) -> bool {
+        matches!(
+            self.lexer.token,
+            Token::Identifier
+                | Token::IdentifierBuiltin
+                | Token::KeywordReturn
+                | Token::TypeByte
+                | Token::TypeAddress
+        )
+    }
+
+    fn assembly_identifier(&mut self) -> Option<IdentifierNode<'ast>> {
+        if !self.is_assembly_identifier() {
+            return None;
+        }
+
+        Some(self.identifier_path_with(Self::is_assembly_identifier))
+    }
 
-        self.lexer.advance();
+    fn assembly_identifier_list(&mut self) -> IdentifierList<'ast> {
+        let ids = GrowableList::new();
 
-        if self.allow(Token::AssemblyBind) {
-            let id = self.node_at(start, end, identifier);
-            let init = expect!(self, self.functional_assembly_expression());
+        while let Some(id) = self.assembly_identifier() {
+            ids.push(self.arena, id);
 
-            return self.node_at(start, init.end, AssemblyAssignment { id, init });
+            if !self.allow(Token::Comma) {
+                break;
+            }
         }
 
-        self.node_at(start, end, identifier)
+        ids.as_list()
     }
 
-    fn functional_assembly_expression(&mut self) -> Option<FunctionalAssemblyExpressionNode<'ast>> {
-        let id = self.expect_str_node(Token::Identifier);
+    /// `L` should be either `Primitive` or `AssemblyExpression`
+    fn assembly_literal<L>(&mut self) -> Option<Node<'ast, L>>
+    where
+        L: From<Primitive<'ast>> + Copy,
+    {
+        match self.lexer.token {
+            Token::LiteralTrue => self.node_at_token(Primitive::Bool(true)),
+            Token::LiteralFalse => self.node_at_token(Primitive::Bool(false)),
+            Token::LiteralHex => self.node_from_slice(Primitive::HexNumber),
+            Token::LiteralInteger => self.node_from_slice(|slice| {
+                Primitive::IntegerNumber(slice, NumberUnit::None)
+            }),
+            Token::LiteralString => self.node_from_slice(Primitive::String),
+            Token::LiteralHexString => {
+                self.node_from_slice(|slice| Primitive::HexString(&slice[3..]))
+            }
+            _ => None,
+        }
+    }
 
+    fn assembly_expression(&mut self) -> Option<AssemblyExpressionNode<'ast>> {
+        if let Some(literal) = self.assembly_literal() {
+            return Some(literal);
+        }
+
+        let id = self.assembly_identifier()?;
+
+        if self.lexer.token != Token::ParenOpen {
+            return self.node_at(id.start, id.end, id.value);
+        }
+
+        self.functional_assembly_expression(id)
+    }
+
+    fn functional_assembly_expression<F>(&mut self, id: IdentifierNode<'ast>) -> Option<Node<'ast, F>>
+    where
+        F: From<FunctionalAssemblyExpression<'ast>> + Copy,
+    {
         self.expect(Token::ParenOpen);
 
         let arguments = GrowableList::new();
 
-        while let Some(item) = self.assembly_item() {
-            arguments.push(self.arena, item);
+        while let Some(argument) = self.assembly_expression() {
+            arguments.push(self.arena, argument);
+
+            if !self.allow(Token::Comma) {
+                break;
+            }
         }
 
         let end = self.expect_end(Token::ParenClose);
@@ -73,4 +143,459 @@ impl<'ast> Parser<'ast> {
             },
         )
     }
+
+    fn assembly_assignment_or_call(&mut self) -> Option<AssemblyItemNode<'ast>> {
+        let first = self.assembly_identifier()?;
+
+        if self.lexer.token == Token::ParenOpen {
+            return self.functional_assembly_expression(first);
+        }
+
+        let ids = GrowableList::new();
+
+        ids.push(self.arena, first);
+
+        if self.allow(Token::Comma) {
+            for id in self.assembly_identifier_list().iter() {
+                ids.push(self.arena, *id);
+            }
+        }
+
+        self.expect(Token::AssemblyBind);
+
+        let init = expect!(self, self.assembly_expression());
+
+        self.node_at(
+            first.start,
+            init.end,
+            AssemblyAssignment {
+                ids: ids.as_list(),
+                init,
+            },
+        )
+    }
+
+    fn assembly_local_binding(&mut self) -> Option<AssemblyItemNode<'ast>> {
+        let start = self.start_then_advance();
+        let ids = self.assembly_identifier_list();
+        let mut end = match ids.iter().last() {
+            Some(id) => id.end,
+            None => {
+                self.error();
+
+                return None;
+            }
+        };
+
+        let init = if self.allow(Token::AssemblyBind) {
+            let init = expect!(self, self.assembly_expression());
+
+            end = init.end;
+
+            Some(init)
+        } else {
+            None
+        };
+
+        self.node_at(start, end, AssemblyLocalBinding { ids, init })
+    }
+
+    fn assembly_block(&mut self) -> Option<InlineAssemblyBlockNode<'ast>> {
+        if self.lexer.token != Token::BraceOpen {
+            self.error_expected(&[Token::BraceOpen]);
+
+            return None;
+        }
+
+        self.inline_assembly_block()
+    }
+
+    fn assembly_if(&mut self) -> Option<AssemblyItemNode<'ast>> {
+        let start = self.start_then_advance();
+        let test = expect!(self, self.assembly_expression());
+        let body = self.assembly_block()?;
+
+        self.node_at(start, body.end, AssemblyIf { test, body })
+    }
+
+    fn assembly_switch(&mut self) -> Option<AssemblyItemNode<'ast>> {
+        let start = self.start_then_advance();
+        let test = expect!(self, self.assembly_expression());
+        let cases = GrowableList::new();
+        let mut end = test.end;
+
+        while self.lexer.token == Token::ReservedWord && self.lexer.slice() == "case" {
+            let case_start = self.start_then_advance();
+            let value = expect!(self, self.assembly_literal());
+            let body = self.assembly_block()?;
+
+            end = body.end;
+            cases.push(self.arena, self.node_at(case_start, end, AssemblyCase { value, body }));
+        }
+
+        let default = if self.lexer.token == Token::ReservedWord && self.lexer.slice() == "default"
+        {
+            self.lexer.advance();
+
+            let body = self.assembly_block()?;
+
+            end = body.end;
+
+            Some(body)
+        } else {
+            None
+        };
+
+        if cases.as_list().is_empty() && default.is_none() {
+            self.error();
+
+            return None;
+        }
+
+        self.node_at(
+            start,
+            end,
+            AssemblySwitch {
+                test,
+                cases: cases.as_list(),
+                default,
+            },
+        )
+    }
+
+    fn assembly_for(&mut self) -> Option<AssemblyItemNode<'ast>> {
+        let start = self.start_then_advance();
+        let init = self.assembly_block()?;
+        let test = expect!(self, self.assembly_expression());
+        let update = self.assembly_block()?;
+        let body = self.assembly_block()?;
+
+        self.node_at(
+            start,
+            body.end,
+            AssemblyFor {
+                init,
+                test,
+                update,
+                body,
+            },
+        )
+    }
+
+    fn assembly_function_definition(&mut self) -> Option<AssemblyItemNode<'ast>> {
+        let start = self.start_then_advance();
+        let name = self.expect_str_node(Token::Identifier);
+
+        self.expect(Token::ParenOpen);
+
+        let params = self.assembly_identifier_list();
+
+        self.expect(Token::ParenClose);
+
+        let returns = if self.allow(Token::AssemblyArrow) {
+            self.assembly_identifier_list()
+        } else {
+            NodeList::empty()
+        };
+
+        let body = self.assembly_block()?;
+
+        self.node_at(
+            start,
+            body.end,
+            AssemblyFunctionDefinition {
+                name,
+                params,
+                returns,
+                body,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::mock;
+
+    use super::*;
+    use mock::{assert_units, Mock};
+
+    #[test]
+    fn yul_statements() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                function() {
+                    assembly ("memory-safe") {
+                        let a, b := f(x.slot, 0x20)
+                        switch a case 0 { leave } default { b := 1 }
+                        for { let i := 0 } lt(i, 2) { i := add(i, 1) } { break }
+                        function g(v) -> r { if v { r := "s" } }
+                    }
+                }
+            }
+
+        "#,
+            [m.node(
+                14,
+                425,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        411,
+                        FunctionDefinition {
+                            name: None,
+                            params: NodeList::empty(),
+                            visibility: None,
+                            mutability: None,
+                            modifiers: NodeList::empty(),
+                            returns: NodeList::empty(),
+                            block: m.node(
+                                56,
+                                411,
+                                Block {
+                                    body: m.list([m.node(
+                                        78,
+                                        393,
+                                        InlineAssemblyStatement {
+                                            string: None,
+                                            flags: m.list([m.node(88, 101, "\"memory-safe\"")]),
+                                            block: m.node(
+                                                103,
+                                                393,
+                                                InlineAssemblyBlock {
+                                                    items: m.list([
+                                                        m.node(
+                                                            129,
+                                                            156,
+                                                            AssemblyLocalBinding {
+                                                                ids: m.list([
+                                                                    m.node(133, 134, "a"),
+                                                                    m.node(136, 137, "b"),
+                                                                ]),
+                                                                init: m.node(
+                                                                    141,
+                                                                    156,
+                                                                    FunctionalAssemblyExpression {
+                                                                        id: m.node(141, 142, "f"),
+                                                                        arguments: m.list([
+                                                                            m.node(143, 149, "x.slot"),
+                                                                            m.node(
+                                                                                151,
+                                                                                155,
+                                                                                Primitive::HexNumber("0x20"),
+                                                                            ),
+                                                                        ]),
+                                                                    },
+                                                                ),
+                                                            },
+                                                        ),
+                                                        m.node(
+                                                            181,
+                                                            225,
+                                                            AssemblySwitch {
+                                                                test: m.node(188, 189, "a"),
+                                                                cases: m.list([m.node(
+                                                                    190,
+                                                                    206,
+                                                                    AssemblyCase {
+                                                                        value: m.node(
+                                                                            195,
+                                                                            196,
+                                                                            Primitive::IntegerNumber(
+                                                                                "0",
+                                                                                NumberUnit::None,
+                                                                            ),
+                                                                        ),
+                                                                        body: m.node(
+                                                                            197,
+                                                                            206,
+                                                                            InlineAssemblyBlock {
+                                                                                items: m.list([m.node(
+                                                                                    199,
+                                                                                    204,
+                                                                                    AssemblyItem::Leave,
+                                                                                )]),
+                                                                            },
+                                                                        ),
+                                                                    },
+                                                                )]),
+                                                                default: m.node(
+                                                                    215,
+                                                                    225,
+                                                                    InlineAssemblyBlock {
+                                                                        items: m.list([m.node(
+                                                                            217,
+                                                                            223,
+                                                                            AssemblyAssignment {
+                                                                                ids: m.list([m.node(
+                                                                                    217, 218, "b",
+                                                                                )]),
+                                                                                init: m.node(
+                                                                                    222,
+                                                                                    223,
+                                                                                    Primitive::IntegerNumber(
+                                                                                        "1",
+                                                                                        NumberUnit::None,
+                                                                                    ),
+                                                                                ),
+                                                                            },
+                                                                        )]),
+                                                                    },
+                                                                ),
+                                                            },
+                                                        ),
+                                                        m.node(
+                                                            250,
+                                                            306,
+                                                            AssemblyFor {
+                                                                init: m.node(
+                                                                    254,
+                                                                    268,
+                                                                    InlineAssemblyBlock {
+                                                                        items: m.list([m.node(
+                                                                            256,
+                                                                            266,
+                                                                            AssemblyLocalBinding {
+                                                                                ids: m.list([m.node(
+                                                                                    260, 261, "i",
+                                                                                )]),
+                                                                                init: m.node(
+                                                                                    265,
+                                                                                    266,
+                                                                                    Primitive::IntegerNumber(
+                                                                                        "0",
+                                                                                        NumberUnit::None,
+                                                                                    ),
+                                                                                ),
+                                                                            },
+                                                                        )]),
+                                                                    },
+                                                                ),
+                                                                test: m.node(
+                                                                    269,
+                                                                    277,
+                                                                    FunctionalAssemblyExpression {
+                                                                        id: m.node(269, 271, "lt"),
+                                                                        arguments: m.list([
+                                                                            m.node(272, 273, "i"),
+                                                                            m.node(
+                                                                                275,
+                                                                                276,
+                                                                                Primitive::IntegerNumber(
+                                                                                    "2",
+                                                                                    NumberUnit::None,
+                                                                                ),
+                                                                            ),
+                                                                        ]),
+                                                                    },
+                                                                ),
+                                                                update: m.node(
+                                                                    278,
+                                                                    296,
+                                                                    InlineAssemblyBlock {
+                                                                        items: m.list([m.node(
+                                                                            280,
+                                                                            294,
+                                                                            AssemblyAssignment {
+                                                                                ids: m.list([m.node(
+                                                                                    280, 281, "i",
+                                                                                )]),
+                                                                                init: m.node(
+                                                                                    285,
+                                                                                    294,
+                                                                                    FunctionalAssemblyExpression {
+                                                                                        id: m.node(
+                                                                                            285, 288, "add",
+                                                                                        ),
+                                                                                        arguments: m.list([
+                                                                                            m.node(289, 290, "i"),
+                                                                                            m.node(
+                                                                                                292,
+                                                                                                293,
+                                                                                                Primitive::IntegerNumber(
+                                                                                                    "1",
+                                                                                                    NumberUnit::None,
+                                                                                                ),
+                                                                                            ),
+                                                                                        ]),
+                                                                                    },
+                                                                                ),
+                                                                            },
+                                                                        )]),
+                                                                    },
+                                                                ),
+                                                                body: m.node(
+                                                                    297,
+                                                                    306,
+                                                                    InlineAssemblyBlock {
+                                                                        items: m.list([m.node(
+                                                                            299,
+                                                                            304,
+                                                                            AssemblyItem::Break,
+                                                                        )]),
+                                                                    },
+                                                                ),
+                                                            },
+                                                        ),
+                                                        m.node(
+                                                            331,
+                                                            371,
+                                                            AssemblyFunctionDefinition {
+                                                                name: m.node(340, 341, "g"),
+                                                                params: m.list([m.node(342, 343, "v")]),
+                                                                returns: m.list([m.node(348, 349, "r")]),
+                                                                body: m.node(
+                                                                    350,
+                                                                    371,
+                                                                    InlineAssemblyBlock {
+                                                                        items: m.list([m.node(
+                                                                            352,
+                                                                            369,
+                                                                            AssemblyIf {
+                                                                                test: m.node(355, 356, "v"),
+                                                                                body: m.node(
+                                                                                    357,
+                                                                                    369,
+                                                                                    InlineAssemblyBlock {
+                                                                                        items: m.list([m.node(
+                                                                                            359,
+                                                                                            367,
+                                                                                            AssemblyAssignment {
+                                                                                                ids: m.list([m.node(
+                                                                                                    359, 360, "r",
+                                                                                                )]),
+                                                                                                init: m.node(
+                                                                                                    364,
+                                                                                                    367,
+                                                                                                    Primitive::String("\"s\""),
+                                                                                                ),
+                                                                                            },
+                                                                                        )]),
+                                                                                    },
+                                                                                ),
+                                                                            },
+                                                                        )]),
+                                                                    },
+                                                                ),
+                                                            },
+                                                        ),
+                                                    ]),
+                                                },
+                                            ),
+                                        },
+                                    )]),
+                                },
+                            ),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use toolshed::list::{GrowableList, ListBuilder};
 
-use crate::{ModifierContext, Parser, RegularTypeNameContext, TOP};
+use crate::{ErrorKind, ModifierContext, Parser, RegularTypeNameContext, TOP};
 use ast::*;
 use lexer::Token;
 
@@ -9,6 +9,8 @@ impl<'ast> Parser<'ast> {
         let start = self.start_then_advance();
         let name = self.expect_str_node(Token::Identifier);
 
+        trace_span!("contract", name = name.value);
+
         let inherits = if self.allow(Token::KeywordIs) {
             let builder = ListBuilder::new(self.arena, self.expect_str_node(Token::Identifier));
 
@@ -25,35 +27,41 @@ impl<'ast> Parser<'ast> {
 
         let builder = GrowableList::new();
 
-        while let Some(part) = self.contract_part() {
+        while let Some(part) = self.list_item(Token::BraceClose, Self::contract_part) {
             builder.push(self.arena, part);
         }
 
         let end = self.expect_end(Token::BraceClose);
 
-        let node = self.node_at(
+        let body = builder.as_list();
+
+        trace_event!(parts = body.iter().count(), "parsed contract");
+
+        self.node_at(
             start,
             end,
             ContractDefinition {
                 name,
                 inherits,
-                body: builder.as_list(),
+                body,
             },
-        );
-
-        println!("{:?} SOURCe", node);
-
-        Some(node)
+        )
     }
 
-    fn contract_part(&mut self) -> Option<ContractPartNode<'ast>> {
+    pub fn contract_part(&mut self) -> Option<ContractPartNode<'ast>> {
         match self.lexer.token {
-            Token::KeywordUsing => self.using_for_declaration(),
+            Token::KeywordUsing => self.using_for_declaration(false),
             Token::DeclarationStruct => self.struct_defintion(),
             Token::DeclarationModifier => self.modifier_definition(),
             Token::DeclarationFunction => self.function_definition(),
             Token::DeclarationEvent => self.event_definition(),
             Token::DeclarationEnum => self.enum_definition(),
+            Token::KeywordType => self.user_defined_value_type_definition(),
+            Token::Identifier
+                if self.lexer.slice() == "error" && self.peek() == Token::Identifier =>
+            {
+                self.error_definition()
+            }
             _ => self.state_variable_declaration(),
         }
     }
@@ -110,9 +118,27 @@ impl<'ast> Parser<'ast> {
         )
     }
 
-    fn using_for_declaration(&mut self) -> Option<ContractPartNode<'ast>> {
+    /// `P` should be either `ContractPart` or `SourceUnit`, `global` is only allowed
+    /// for the latter, `file_level` ones.
+    pub fn using_for_declaration<P>(&mut self, file_level: bool) -> Option<Node<'ast, P>>
+    where
+        P: From<UsingForDeclaration<'ast>> + Copy,
+    {
         let start = self.start_then_advance();
-        let id = self.expect_str_node(Token::Identifier);
+
+        let (id, functions) = if self.allow(Token::BraceOpen) {
+            let builder = ListBuilder::new(self.arena, self.using_for_function());
+
+            while self.allow(Token::Comma) {
+                builder.push(self.arena, self.using_for_function());
+            }
+
+            self.expect(Token::BraceClose);
+
+            (None, builder.as_list())
+        } else {
+            (Some(self.identifier_path()), NodeList::empty())
+        };
 
         self.expect(Token::KeywordFor);
 
@@ -125,12 +151,85 @@ impl<'ast> Parser<'ast> {
             type_name => type_name,
         };
 
+        let global = match self.lexer.token {
+            Token::Identifier if self.lexer.slice() == "global" => {
+                if !file_level {
+                    self.report(ErrorKind::MisplacedGlobal, Vec::new());
+                }
+
+                self.node_at_token(Flag)
+            }
+            _ => None,
+        };
+
         let end = self.expect_end(Token::Semicolon);
 
-        self.node_at(start, end, UsingForDeclaration { id, type_name })
+        self.node_at(
+            start,
+            end,
+            UsingForDeclaration {
+                id,
+                functions,
+                type_name,
+                global,
+            },
+        )
+    }
+
+    fn using_for_function(&mut self) -> Node<'ast, UsingForFunction<'ast>> {
+        let id = self.identifier_path();
+
+        let operator = if self.allow(Token::KeywordAs) {
+            self.user_definable_operator()
+        } else {
+            None
+        };
+
+        let end = operator.end().unwrap_or(id.end);
+
+        self.node_at(id.start, end, UsingForFunction { id, operator })
+    }
+
+    fn user_definable_operator(&mut self) -> Option<Node<'ast, UserDefinableOperator>> {
+        let operator = match self.lexer.token {
+            Token::OperatorBitAnd => UserDefinableOperator::BitAnd,
+            Token::OperatorBitOr => UserDefinableOperator::BitOr,
+            Token::OperatorBitXor => UserDefinableOperator::BitXor,
+            Token::OperatorBitNot => UserDefinableOperator::BitNot,
+            Token::OperatorAddition => UserDefinableOperator::Addition,
+            Token::OperatorSubtraction => UserDefinableOperator::Subtraction,
+            Token::OperatorMultiplication => UserDefinableOperator::Multiplication,
+            Token::OperatorDivision => UserDefinableOperator::Division,
+            Token::OperatorRemainder => UserDefinableOperator::Remainder,
+            Token::OperatorEquality => UserDefinableOperator::Equality,
+            Token::OperatorInequality => UserDefinableOperator::Inequality,
+            Token::OperatorLesser => UserDefinableOperator::Lesser,
+            Token::OperatorLesserEquals => UserDefinableOperator::LesserEquals,
+            Token::OperatorGreater => UserDefinableOperator::Greater,
+            Token::OperatorGreaterEquals => UserDefinableOperator::GreaterEquals,
+            _ => {
+                self.error();
+
+                return None;
+            }
+        };
+
+        self.node_at_token(operator)
+    }
+
+    fn identifier_path(&mut self) -> IdentifierNode<'ast> {
+        if self.lexer.token != Token::Identifier {
+            return self.expect_str_node(Token::Identifier);
+        }
+
+        self.identifier_path_with(|par| par.lexer.token == Token::Identifier)
     }
 
-    fn struct_defintion(&mut self) -> Option<ContractPartNode<'ast>> {
+    /// `P` should be either `ContractPart` or `SourceUnit`
+    pub fn struct_defintion<P>(&mut self) -> Option<Node<'ast, P>>
+    where
+        P: From<StructDefinition<'ast>> + Copy,
+    {
         let start = self.start_then_advance();
         let name = self.expect_str_node(Token::Identifier);
 
@@ -250,7 +349,11 @@ impl<'ast> Parser<'ast> {
         )
     }
 
-    fn enum_definition(&mut self) -> Option<ContractPartNode<'ast>> {
+    /// `P` should be either `ContractPart` or `SourceUnit`
+    pub fn enum_definition<P>(&mut self) -> Option<Node<'ast, P>>
+    where
+        P: From<EnumDefinition<'ast>> + Copy,
+    {
         let start = self.start_then_advance();
         let name = self.expect_str_node(Token::Identifier);
 
@@ -272,11 +375,58 @@ impl<'ast> Parser<'ast> {
 
         self.node_at(start, end, EnumDefinition { name, variants })
     }
+
+    /// `P` should be either `ContractPart` or `SourceUnit`
+    pub fn error_definition<P>(&mut self) -> Option<Node<'ast, P>>
+    where
+        P: From<ErrorDefinition<'ast>> + Copy,
+    {
+        let start = self.start_then_advance();
+        let name = self.expect_str_node(Token::Identifier);
+
+        self.expect(Token::ParenOpen);
+
+        let params = self.parameter_list();
+
+        self.expect(Token::ParenClose);
+
+        let end = self.expect_end(Token::Semicolon);
+
+        self.node_at(start, end, ErrorDefinition { name, params })
+    }
+
+    /// `P` should be either `ContractPart` or `SourceUnit`
+    pub fn user_defined_value_type_definition<P>(&mut self) -> Option<Node<'ast, P>>
+    where
+        P: From<UserDefinedValueTypeDefinition<'ast>> + Copy,
+    {
+        let start = self.start_then_advance();
+        let name = self.expect_str_node(Token::Identifier);
+
+        self.expect(Token::KeywordIs);
+
+        let underlying = match self.elementary_type_name() {
+            Some(underlying) => underlying,
+            None => {
+                self.error();
+
+                return None;
+            }
+        };
+
+        let end = self.expect_end(Token::Semicolon);
+
+        self.node_at(
+            start,
+            end,
+            UserDefinedValueTypeDefinition { name, underlying },
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::mock;
+    use crate::{mock, parse};
 
     use super::*;
     use mock::{assert_units, Mock};
@@ -459,7 +609,9 @@ mod test {
                             61,
                             UsingForDeclaration {
                                 id: m.node(51, 54, "foo"),
+                                functions: NodeList::empty(),
                                 type_name: None,
+                                global: None,
                             },
                         ),
                         m.node(
@@ -467,7 +619,9 @@ mod test {
                             98,
                             UsingForDeclaration {
                                 id: m.node(84, 87, "bar"),
+                                functions: NodeList::empty(),
                                 type_name: m.node(92, 97, ElementaryTypeName::Int(4)),
+                                global: None,
                             },
                         ),
                     ]),
@@ -476,6 +630,48 @@ mod test {
         );
     }
 
+    #[test]
+    fn using_for_global_within_contract() {
+        let errors = parse("contract Foo { using Math for uint256 global; }")
+            .err()
+            .unwrap();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, ErrorKind::MisplacedGlobal);
+        assert_eq!(errors[0].span, 38..44);
+    }
+
+    #[test]
+    fn user_defined_value_type_definition() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                type Price is uint64;
+            }
+
+        "#,
+            [m.node(
+                14,
+                80,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        66,
+                        UserDefinedValueTypeDefinition {
+                            name: m.node(50, 55, "Price"),
+                            underlying: m.node(59, 65, ElementaryTypeName::Uint(8)),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
+
     #[test]
     fn struct_defintion() {
         let m = Mock::new();
@@ -588,6 +784,7 @@ mod test {
                                     111,
                                     Parameter {
                                         type_name: m.node(102, 107, ElementaryTypeName::Uint(1)),
+                                        location: None,
                                         name: m.node(108, 111, "bar"),
                                     },
                                 )]),
@@ -819,4 +1016,73 @@ mod test {
             )],
         );
     }
+
+    #[test]
+    fn error_definition() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                error Unauthorized();
+                error InsufficientBalance(uint256 available, uint256 required);
+            }
+
+        "#,
+            [m.node(
+                14,
+                160,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([
+                        m.node(
+                            45,
+                            66,
+                            ErrorDefinition {
+                                name: m.node(51, 63, "Unauthorized"),
+                                params: NodeList::empty(),
+                            },
+                        ),
+                        m.node(
+                            83,
+                            146,
+                            ErrorDefinition {
+                                name: m.node(89, 108, "InsufficientBalance"),
+                                params: m.list([
+                                    m.node(
+                                        109,
+                                        126,
+                                        Parameter {
+                                            type_name: m.node(
+                                                109,
+                                                116,
+                                                ElementaryTypeName::Uint(32),
+                                            ),
+                                            location: None,
+                                            name: m.node(117, 126, "available"),
+                                        },
+                                    ),
+                                    m.node(
+                                        128,
+                                        144,
+                                        Parameter {
+                                            type_name: m.node(
+                                                128,
+                                                135,
+                                                ElementaryTypeName::Uint(32),
+                                            ),
+                                            location: None,
+                                            name: m.node(136, 144, "required"),
+                                        },
+                                    ),
+                                ]),
+                            },
+                        ),
+                    ]),
+                },
+            )],
+        );
+    }
 }
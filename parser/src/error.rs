@@ -1,13 +1,171 @@
+use ast::{LineCol, SourceMap};
 use lexer::Token;
-use std::fmt::{self, Debug};
+use std::fmt::{self, Debug, Display, Write};
 use std::ops::Range;
 
 /// Error type used by the tokenizer and the parser internally.
 #[derive(PartialEq, Clone)]
 pub struct Error {
+    pub kind: ErrorKind,
     pub token: Token,
     pub raw: Box<str>,
     pub span: Range<usize>,
+    /// Descriptions of the tokens that would have been accepted, like `` `;` `` or `identifier`
+    pub expected: Vec<Box<str>>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ErrorKind {
+    UnexpectedToken,
+    UnexpectedEndOfProgram,
+    DuplicateVisibility,
+    DuplicateMutability,
+    DuplicateFlag,
+    InvalidPragma,
+    MissingCatchClause,
+    MisplacedGlobal,
+}
+
+impl ErrorKind {
+    /// Stable code identifying the kind of the error, like `E001`.
+    pub fn code(self) -> &'static str {
+        match self {
+            ErrorKind::UnexpectedToken => "E001",
+            ErrorKind::UnexpectedEndOfProgram => "E002",
+            ErrorKind::DuplicateVisibility => "E003",
+            ErrorKind::DuplicateMutability => "E004",
+            ErrorKind::DuplicateFlag => "E005",
+            ErrorKind::InvalidPragma => "E006",
+            ErrorKind::MissingCatchClause => "E007",
+            ErrorKind::MisplacedGlobal => "E008",
+        }
+    }
+}
+
+impl Error {
+    pub fn message(&self) -> String {
+        let mut message = match self.kind {
+            ErrorKind::UnexpectedToken => format!("unexpected `{}`", self.raw),
+            ErrorKind::UnexpectedEndOfProgram => "unexpected end of file".to_string(),
+            ErrorKind::DuplicateVisibility => {
+                format!("can't redeclare visibility with `{}`", self.raw)
+            }
+            ErrorKind::DuplicateMutability => {
+                format!("can't redeclare mutability with `{}`", self.raw)
+            }
+            ErrorKind::DuplicateFlag => format!("`{}` is declared more than once", self.raw),
+            ErrorKind::InvalidPragma if self.raw.is_empty() => "missing pragma version".to_string(),
+            ErrorKind::InvalidPragma => format!("invalid pragma `{}`", self.raw),
+            ErrorKind::MissingCatchClause => {
+                "`try` needs at least one `catch` clause".to_string()
+            }
+            ErrorKind::MisplacedGlobal => {
+                "`global` is only allowed in `using` directives at file level".to_string()
+            }
+        };
+
+        if let Some((last, rest)) = self.expected.split_last() {
+            message.push_str(", expected ");
+
+            if !rest.is_empty() {
+                message.push_str(&rest.join(", "));
+                message.push_str(" or ");
+            }
+
+            message.push_str(last);
+        }
+
+        message
+    }
+
+    /// One based line and column of the start of the error within `source`, with
+    /// the column counted in characters.
+    pub fn line_column(&self, source: &SourceMap) -> (usize, usize) {
+        let LineCol { line, col } = source.line_col(self.span.start as u32);
+        let column = source
+            .line(line)
+            .and_then(|text| text.get(..col as usize))
+            .map_or(col as usize, |before| before.chars().count());
+
+        (line as usize + 1, column + 1)
+    }
+
+    /// Renders the error along with the offending line of `source`, pointing at
+    /// the error with carets.
+    pub fn render(&self, path: &str, source: &SourceMap) -> String {
+        let (line, column) = self.line_column(source);
+        let text = source.line(line as u32 - 1).unwrap_or("");
+        let gutter = line.to_string().len();
+
+        // Carets only cover the first line of the span
+        let width = source
+            .source()
+            .get(self.span.clone())
+            .and_then(|span| span.lines().next())
+            .map_or(0, |span| span.chars().count())
+            .max(1);
+
+        let mut rendered = String::new();
+
+        // Writing into a `String` can't fail
+        let _ = writeln!(rendered, "{}", self);
+        let _ = writeln!(rendered, "{:gutter$}--> {}:{}:{}", "", path, line, column);
+        let _ = writeln!(rendered, "{:gutter$} |", "");
+        let _ = writeln!(rendered, "{} | {}", line, text);
+        let _ = writeln!(
+            rendered,
+            "{:gutter$} | {:padding$}{}",
+            "",
+            "",
+            "^".repeat(width),
+            padding = column - 1,
+        );
+
+        rendered
+    }
+}
+
+/// Human readable name of a token the parser expects.
+pub fn describe(token: Token) -> Box<str> {
+    let text = match token {
+        Token::EndOfProgram => return "end of file".into(),
+        Token::Identifier => return "identifier".into(),
+        Token::Semicolon => ";",
+        Token::Colon => ":",
+        Token::Comma => ",",
+        Token::Accessor => ".",
+        Token::ParenOpen => "(",
+        Token::ParenClose => ")",
+        Token::BraceOpen => "{",
+        Token::BraceClose => "}",
+        Token::BracketOpen => "[",
+        Token::BracketClose => "]",
+        Token::Arrow => "=>",
+        Token::Assign => "=",
+        Token::AssemblyBind => ":=",
+        Token::AssemblyAssign => "=:",
+        Token::AssemblyArrow => "->",
+        _ => {
+            // Keywords are named after their text, like `KeywordReturn` or `DeclarationContract`
+            let name = format!("{:?}", token);
+            let keyword = ["Keyword", "Declaration"]
+                .iter()
+                .find_map(|prefix| name.strip_prefix(prefix));
+
+            return match keyword {
+                Some(keyword) => format!("`{}`", keyword.to_lowercase()).into(),
+                None => name.into(),
+            };
+        }
+    };
+
+    format!("`{}`", text).into()
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "error[{}]: {}", self.kind.code(), self.message())
+    }
 }
 
 impl Debug for Error {
@@ -20,3 +178,52 @@ impl Debug for Error {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::parse;
+    use ast::SourceMap;
+
+    #[test]
+    fn renders_expected_tokens() {
+        let source = SourceMap::new("contract Foo {\n    uint256 a = 1 )\n}\n");
+        let errors = parse(source.source()).err().unwrap();
+
+        assert_eq!(errors[0].line_column(&source), (2, 19));
+        assert_eq!(
+            errors[0].render("Foo.sol", &source),
+            concat!(
+                "error[E001]: unexpected `)`, expected `;`\n",
+                " --> Foo.sol:2:19\n",
+                "  |\n",
+                "2 |     uint256 a = 1 )\n",
+                "  |                   ^\n",
+            )
+        );
+    }
+
+    #[test]
+    fn distinct_messages() {
+        let errors = parse("contract Foo { function foo() public private view pure {} }")
+            .err().unwrap();
+
+        assert_eq!(
+            errors.iter().map(|error| error.to_string()).collect::<Vec<_>>(),
+            [
+                "error[E003]: can't redeclare visibility with `private`",
+                "error[E004]: can't redeclare mutability with `pure`",
+            ]
+        );
+
+        let errors = parse("contract Foo {").err().unwrap();
+
+        assert_eq!(
+            errors[0].to_string(),
+            "error[E002]: unexpected end of file, expected `}`"
+        );
+
+        let errors = parse("pragma solidity ^foo;").err().unwrap();
+
+        assert_eq!(errors[0].to_string(), "error[E006]: invalid pragma `^foo`");
+    }
+}
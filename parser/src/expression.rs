@@ -1,6 +1,6 @@
-use toolshed::list::ListBuilder;
+use toolshed::list::{GrowableList, ListBuilder};
 
-use crate::{Parser, Precedence, P2, TOP};
+use crate::{Parser, Precedence, RegularTypeNameContext, P2, TOP};
 use ast::*;
 use lexer::{lookup, Logos, Token};
 
@@ -11,6 +11,8 @@ static EXPRESSION_LUT: [HandlerFn; Token::SIZE] = lookup! {
     Token::Identifier          => |par| par.node_from_slice(|ident| ident),
     Token::IdentifierBuiltin   => |par| par.node_from_slice(|ident| ident),
     Token::ParenOpen           => |par| par.tuple_expression(),
+    Token::KeywordNew          => |par| par.new_expression(),
+    Token::KeywordType         => |par| par.type_expression(),
     Token::OperatorLogicalNot  => |par| par.prefix_expression(PrefixOperator::LogicalNot),
     Token::OperatorBitNot      => |par| par.prefix_expression(PrefixOperator::BitNot),
     Token::KeywordDelete       => |par| par.prefix_expression(PrefixOperator::Delete),
@@ -20,12 +22,15 @@ static EXPRESSION_LUT: [HandlerFn; Token::SIZE] = lookup! {
     Token::OperatorSubtraction => |par| par.prefix_expression(PrefixOperator::Minus),
     Token::LiteralTrue         => |par| par.node_at_token(Primitive::Bool(true)),
     Token::LiteralFalse        => |par| par.node_at_token(Primitive::Bool(false)),
-    Token::LiteralHex          => |par| par.node_from_slice(|slice| Primitive::HexNumber(slice)),
+    Token::LiteralHex          => |par| par.number_from_slice(|slice| Primitive::HexNumber(slice)),
     Token::LiteralInteger      => |par| par.integer_number(),
-    Token::LiteralRational     => |par| par.node_from_slice(|slice| Primitive::RationalNumber(slice)),
+    Token::LiteralRational     => |par| par.number_from_slice(|slice| Primitive::RationalNumber(slice)),
     Token::LiteralString       => |par| par.node_from_slice(|slice| Primitive::String(slice)),
+    Token::LiteralHexString    => |par| par.node_from_slice(|slice| Primitive::HexString(&slice[3..])),
+    Token::LiteralUnicodeString => |par| par.node_from_slice(|slice| Primitive::UnicodeString(&slice[7..])),
     Token::TypeBool            => |par| par.node_at_token(ElementaryTypeName::Bool),
     Token::TypeAddress         => |par| par.node_at_token(ElementaryTypeName::Address),
+    Token::KeywordPayable      => |par| par.node_at_token(ElementaryTypeName::AddressPayable),
     Token::TypeString          => |par| par.node_at_token(ElementaryTypeName::String),
     Token::TypeByte            => |par| {
         let size = par.lexer.extras.0;
@@ -82,6 +87,62 @@ impl<'ast> Parser<'ast> {
         builder.as_list()
     }
 
+    /// Arguments of a call, the opening parenthesis should already be consumed.
+    pub fn call_arguments(&mut self) -> CallArguments<'ast> {
+        if !self.allow(Token::BraceOpen) {
+            return self.expression_list().into();
+        }
+
+        let arguments = self.named_argument_list();
+
+        self.expect(Token::BraceClose);
+
+        arguments.into()
+    }
+
+    /// `name: value, ...` within braces, used for both named arguments and call options.
+    pub fn named_argument_list(&mut self) -> NamedArgumentList<'ast> {
+        let arguments = GrowableList::new();
+
+        while let Some(argument) = self.named_argument() {
+            arguments.push(self.arena, argument);
+
+            if !self.allow(Token::Comma) {
+                break;
+            }
+        }
+
+        arguments.as_list()
+    }
+
+    fn named_argument(&mut self) -> Option<NamedArgumentNode<'ast>> {
+        let name = self.allow_str_node(Token::Identifier)?;
+
+        self.expect(Token::Colon);
+
+        let value = expect!(self, self.expression(TOP));
+
+        self.node_at(name.start, value.end, NamedArgument { name, value })
+    }
+
+    fn new_expression(&mut self) -> Option<ExpressionNode<'ast>> {
+        let start = self.start_then_advance();
+        let type_name = expect!(self, self.type_name::<RegularTypeNameContext>());
+
+        self.node_at(start, type_name.end, NewExpression { type_name })
+    }
+
+    fn type_expression(&mut self) -> Option<ExpressionNode<'ast>> {
+        let start = self.start_then_advance();
+
+        self.expect(Token::ParenOpen);
+
+        let type_name = expect!(self, self.type_name::<RegularTypeNameContext>());
+        let end = self.expect_end(Token::ParenClose);
+
+        self.node_at(start, end, TypeExpression { type_name })
+    }
+
     fn tuple_expression(&mut self) -> Option<ExpressionNode<'ast>> {
         let start = self.start_then_advance();
         let expressions = self.expression_list();
@@ -102,7 +163,7 @@ impl<'ast> Parser<'ast> {
     }
 
     fn integer_number(&mut self) -> Option<ExpressionNode<'ast>> {
-        let number = self.lexer.slice();
+        let number = self.number_slice();
         let (start, end) = self.loc();
 
         self.lexer.advance();
@@ -111,6 +172,7 @@ impl<'ast> Parser<'ast> {
             Token::UnitEther => NumberUnit::Ether(EtherUnit::Ether),
             Token::UnitFinney => NumberUnit::Ether(EtherUnit::Finney),
             Token::UnitSzabo => NumberUnit::Ether(EtherUnit::Szabo),
+            Token::UnitGwei => NumberUnit::Ether(EtherUnit::Gwei),
             Token::UnitWei => NumberUnit::Ether(EtherUnit::Wei),
             Token::UnitTimeYears => NumberUnit::Time(TimeUnit::Years),
             Token::UnitTimeWeeks => NumberUnit::Time(TimeUnit::Weeks),
@@ -266,4 +328,224 @@ mod test {
             )],
         );
     }
+
+    #[test]
+    fn new_and_type_expressions() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                function() {
+                    new Token(1);
+                    new uint256[](n);
+                    type(uint256).max;
+                    f({to: a, amount: b});
+                }
+            }
+
+        "#,
+            [m.node(
+                14,
+                243,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        229,
+                        FunctionDefinition {
+                            name: None,
+                            params: NodeList::empty(),
+                            visibility: None,
+                            mutability: None,
+                            modifiers: NodeList::empty(),
+                            returns: NodeList::empty(),
+                            block: m.node(
+                                56,
+                                229,
+                                Block {
+                                    body: m.list([
+                                        m.stmt_expr(
+                                            78,
+                                            90,
+                                            91,
+                                            CallExpression {
+                                                callee: m.node(
+                                                    78,
+                                                    87,
+                                                    NewExpression {
+                                                        type_name: m.node(82, 87, "Token"),
+                                                    },
+                                                ),
+                                                arguments: CallArguments::Positional(m.list([
+                                                    m.node(
+                                                        88,
+                                                        89,
+                                                        Primitive::IntegerNumber(
+                                                            "1",
+                                                            NumberUnit::None,
+                                                        ),
+                                                    ),
+                                                ])),
+                                            },
+                                        ),
+                                        m.stmt_expr(
+                                            112,
+                                            128,
+                                            129,
+                                            CallExpression {
+                                                callee: m.node(
+                                                    112,
+                                                    125,
+                                                    NewExpression {
+                                                        type_name: m.node(
+                                                            116,
+                                                            125,
+                                                            ArrayTypeName {
+                                                                base: m.node(
+                                                                    116,
+                                                                    123,
+                                                                    ElementaryTypeName::Uint(32),
+                                                                ),
+                                                                length: None,
+                                                            },
+                                                        ),
+                                                    },
+                                                ),
+                                                arguments: CallArguments::Positional(
+                                                    m.list([m.node(126, 127, "n")]),
+                                                ),
+                                            },
+                                        ),
+                                        m.stmt_expr(
+                                            150,
+                                            167,
+                                            168,
+                                            MemberAccessExpression {
+                                                object: m.node(
+                                                    150,
+                                                    163,
+                                                    TypeExpression {
+                                                        type_name: m.node(
+                                                            155,
+                                                            162,
+                                                            ElementaryTypeName::Uint(32),
+                                                        ),
+                                                    },
+                                                ),
+                                                member: m.node(164, 167, "max"),
+                                            },
+                                        ),
+                                        m.stmt_expr(
+                                            189,
+                                            210,
+                                            211,
+                                            CallExpression {
+                                                callee: m.node(189, 190, "f"),
+                                                arguments: CallArguments::Named(m.list([
+                                                    m.node(
+                                                        192,
+                                                        197,
+                                                        NamedArgument {
+                                                            name: m.node(192, 194, "to"),
+                                                            value: m.node(196, 197, "a"),
+                                                        },
+                                                    ),
+                                                    m.node(
+                                                        199,
+                                                        208,
+                                                        NamedArgument {
+                                                            name: m.node(199, 205, "amount"),
+                                                            value: m.node(207, 208, "b"),
+                                                        },
+                                                    ),
+                                                ])),
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
+
+    #[test]
+    fn literals() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                function() {
+                    1_000 gwei;
+                    0xdead_beef;
+                    hex"00ff";
+                    unicode"hi";
+                }
+            }
+
+        "#,
+            [m.node(
+                14,
+                218,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        204,
+                        FunctionDefinition {
+                            name: None,
+                            params: NodeList::empty(),
+                            visibility: None,
+                            mutability: None,
+                            modifiers: NodeList::empty(),
+                            returns: NodeList::empty(),
+                            block: m.node(
+                                56,
+                                204,
+                                Block {
+                                    body: m.list([
+                                        m.stmt_expr(
+                                            78,
+                                            88,
+                                            89,
+                                            Primitive::IntegerNumber(
+                                                "1000",
+                                                NumberUnit::Ether(EtherUnit::Gwei),
+                                            ),
+                                        ),
+                                        m.stmt_expr(
+                                            110,
+                                            121,
+                                            122,
+                                            Primitive::HexNumber("0xdeadbeef"),
+                                        ),
+                                        m.stmt_expr(
+                                            143,
+                                            152,
+                                            153,
+                                            Primitive::HexString("\"00ff\""),
+                                        ),
+                                        m.stmt_expr(
+                                            174,
+                                            185,
+                                            186,
+                                            Primitive::UnicodeString("\"hi\""),
+                                        ),
+                                    ]),
+                                },
+                            ),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
 }
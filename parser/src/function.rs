@@ -5,7 +5,11 @@ use ast::*;
 use lexer::Token;
 
 impl<'ast> Parser<'ast> {
-    pub fn function_definition(&mut self) -> Option<ContractPartNode<'ast>> {
+    /// `P` should be either `ContractPart` or `SourceUnit`
+    pub fn function_definition<P>(&mut self) -> Option<Node<'ast, P>>
+    where
+        P: From<FunctionDefinition<'ast>> + Copy,
+    {
         let start = self.start_then_advance();
 
         let name = match self.lexer.token {
@@ -13,6 +17,8 @@ impl<'ast> Parser<'ast> {
             _ => None,
         };
 
+        trace_span!("function", name = name.map(|name| name.value));
+
         self.expect(Token::ParenOpen);
 
         let params = self.parameter_list();
@@ -69,7 +75,12 @@ impl<'ast> Parser<'ast> {
 
         let (end, block) = match self.lexer.token {
             Token::BraceOpen => {
-                let block = self.block::<FunctionContext, _>();
+                let block: BlockNode<'ast> = self.block::<FunctionContext, _>();
+
+                trace_event!(
+                    statements = block.value.body.iter().count(),
+                    "parsed function body"
+                );
 
                 (block.end, Some(block))
             }
@@ -128,11 +139,23 @@ impl<'ast> Parser<'ast> {
 
     fn parameter(&mut self) -> Option<Node<'ast, Parameter<'ast>>> {
         let type_name = self.type_name::<RegularTypeNameContext>()?;
+        let location = self.storage_location();
         let name = self.allow_str_node(Token::Identifier);
 
-        let end = name.end().unwrap_or_else(|| type_name.end);
+        let end = name
+            .end()
+            .or_else(|| location.end())
+            .unwrap_or_else(|| type_name.end);
 
-        self.node_at(type_name.start, end, Parameter { type_name, name })
+        self.node_at(
+            type_name.start,
+            end,
+            Parameter {
+                type_name,
+                location,
+                name,
+            },
+        )
     }
 }
 
@@ -224,6 +247,7 @@ mod test {
                                     60,
                                     Parameter {
                                         type_name: m.node(54, 60, ElementaryTypeName::Uint(7)),
+                                        location: None,
                                         name: None,
                                     },
                                 ),
@@ -232,6 +256,7 @@ mod test {
                                     66,
                                     Parameter {
                                         type_name: m.node(62, 66, ElementaryTypeName::Bool),
+                                        location: None,
                                         name: None,
                                     },
                                 ),
@@ -277,6 +302,7 @@ mod test {
                                     69,
                                     Parameter {
                                         type_name: m.node(59, 65, ElementaryTypeName::Uint(7)),
+                                        location: None,
                                         name: m.node(66, 69, "wow"),
                                     },
                                 ),
@@ -285,6 +311,7 @@ mod test {
                                     80,
                                     Parameter {
                                         type_name: m.node(71, 75, ElementaryTypeName::Bool),
+                                        location: None,
                                         name: m.node(76, 80, "moon"),
                                     },
                                 ),
@@ -301,6 +328,61 @@ mod test {
         );
     }
 
+    #[test]
+    fn function_parameter_locations() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                function f(bytes calldata data, string memory s) external;
+            }
+
+        "#,
+            [m.node(
+                14,
+                117,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        103,
+                        FunctionDefinition {
+                            name: m.node(54, 55, "f"),
+                            params: m.list([
+                                m.node(
+                                    56,
+                                    75,
+                                    Parameter {
+                                        type_name: m.node(56, 61, ElementaryTypeName::Bytes),
+                                        location: m.node(62, 70, StorageLocation::Calldata),
+                                        name: m.node(71, 75, "data"),
+                                    },
+                                ),
+                                m.node(
+                                    77,
+                                    92,
+                                    Parameter {
+                                        type_name: m.node(77, 83, ElementaryTypeName::String),
+                                        location: m.node(84, 90, StorageLocation::Memory),
+                                        name: m.node(91, 92, "s"),
+                                    },
+                                ),
+                            ]),
+                            visibility: m.node(94, 102, FunctionVisibility::External),
+                            mutability: None,
+                            modifiers: NodeList::empty(),
+                            returns: NodeList::empty(),
+                            block: None,
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
+
     #[test]
     fn function_returns() {
         let m = Mock::new();
@@ -334,6 +416,7 @@ mod test {
                                     76,
                                     Parameter {
                                         type_name: m.node(70, 76, ElementaryTypeName::Uint(7)),
+                                        location: None,
                                         name: None,
                                     },
                                 ),
@@ -342,6 +425,7 @@ mod test {
                                     82,
                                     Parameter {
                                         type_name: m.node(78, 82, ElementaryTypeName::Bool),
+                                        location: None,
                                         name: None,
                                     },
                                 ),
@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use toolshed::list::GrowableList;
+use toolshed::Arena;
+
+use crate::{parse_with_errors, Error, Parser};
+use ast::*;
+use lexer::Token;
+
+/// Replacement of the bytes `start..end` of a source with `len` bytes of new text,
+/// like a change an editor reports as the user types.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: u32,
+    pub end: u32,
+    pub len: u32,
+}
+
+impl TextEdit {
+    pub fn new(start: u32, end: u32, len: u32) -> Self {
+        TextEdit { start, end, len }
+    }
+
+    /// How many bytes the text following the edit moves by.
+    #[inline]
+    pub fn delta(&self) -> i64 {
+        self.len as i64 - (self.end as i64 - self.start as i64)
+    }
+
+    /// Whether the edit falls within the node, leaving its first and last byte alone
+    /// so that its boundaries with the neighbouring tokens can't change.
+    #[inline]
+    fn is_within<T>(&self, node: &NodeInner<T>) -> bool {
+        node.start < self.start && self.end < node.end
+    }
+}
+
+/// Parses `source`, the result of applying `edit` to the source of `previous`,
+/// reusing as much of `previous` as possible.
+///
+/// Only the innermost contract part or source unit enclosing the edit is parsed
+/// again. Nodes preceding it are kept as they are and nodes following it are kept
+/// with their spans shifted, all of it on the `Arena` of `previous`. Whenever that
+/// can't be done, like for an edit between two source units, one that doesn't
+/// parse on its own, or a `previous` program that had errors, `source` is parsed
+/// in full, so that the result is always the same as the one of `parse_with_errors`.
+///
+/// `previous` must be the program as it was parsed, not rewritten since.
+pub fn reparse<'ast>(
+    previous: Program<'ast>,
+    edit: TextEdit,
+    source: &str,
+) -> (Program<'ast>, Vec<Error>) {
+    let file = previous.file();
+    let path = previous.path().map(Path::to_path_buf);
+
+    if can_reparse(&previous, edit, source) {
+        // The `Reparser` holding on to the `Arena` is dropped within the closure
+        let reparsed = unsafe {
+            previous.rebuild(SourceMap::new(source), |body, arena| {
+                Reparser {
+                    arena,
+                    edit,
+                    source,
+                }
+                .source_units(body)
+            })
+        };
+
+        if let Some(program) = reparsed {
+            return (program, Vec::new());
+        }
+    }
+
+    let (program, errors) = parse_with_errors(source);
+    let program = program.with_file(file);
+
+    match path {
+        Some(path) => (program.with_path(path), errors),
+        None => (program, errors),
+    }
+}
+
+fn can_reparse(previous: &Program, edit: TextEdit, source: &str) -> bool {
+    let index = previous.index();
+    let len = previous.source().len() as i64;
+
+    edit.start <= edit.end
+        && edit.end as i64 <= len
+        && len + edit.delta() == source.len() as i64
+        && (0..index.len() as u32).all(|id| index.kind(NodeId(id)) != NodeKind::Unknown)
+}
+
+struct Reparser<'ast, 'src> {
+    arena: &'ast Arena,
+    edit: TextEdit,
+    source: &'src str,
+}
+
+impl<'ast, 'src> Reparser<'ast, 'src> {
+    fn source_units(&self, body: SourceUnitList<'ast>) -> Option<SourceUnitList<'ast>> {
+        let (index, unit) = self.enclosing(body)?;
+
+        // Within a contract, only the part enclosing the edit is parsed again
+        let parts = match unit.value {
+            SourceUnit::ContractDefinition(contract) => self
+                .contract_parts(contract.body)
+                .map(|body| ContractDefinition { body, ..contract }),
+            _ => None,
+        };
+
+        let edited = match parts {
+            Some(contract) => self.alloc(unit.start, self.shift(unit.end), contract.into()),
+            None => self.parse(unit, Parser::source_unit)?,
+        };
+
+        Some(self.splice(body, index, edited))
+    }
+
+    fn contract_parts(&self, body: ContractPartList<'ast>) -> Option<ContractPartList<'ast>> {
+        let (index, part) = self.enclosing(body)?;
+        let edited = self.parse(part, Parser::contract_part)?;
+
+        Some(self.splice(body, index, edited))
+    }
+
+    /// The node of the list the edit falls within, along with its position.
+    fn enclosing<T: Copy>(&self, list: NodeList<'ast, T>) -> Option<(usize, Node<'ast, T>)> {
+        list.iter()
+            .enumerate()
+            .find(|(_, node)| self.edit.is_within(node))
+            .map(|(index, node)| (index, *node))
+    }
+
+    /// Parses the edited source of `node` with `item`, which has to consume all of it
+    /// without errors.
+    fn parse<T, F>(&self, node: Node<'ast, T>, item: F) -> Option<Node<'ast, T>>
+    where
+        T: 'ast + Copy + Shift<'ast>,
+        F: FnOnce(&mut Parser<'ast>) -> Option<Node<'ast, T>>,
+    {
+        let end = self.shift(node.end);
+        let text = self.source.get(node.start as usize..end as usize)?;
+
+        let mut parser = Parser::new(text, self.arena);
+        let parsed = item(&mut parser)?;
+
+        let complete = parser.errors.is_empty()
+            && parser.lexer.token == Token::EndOfProgram
+            && parsed.start == 0
+            && parsed.end as usize == text.len();
+
+        match complete {
+            true => Some(parsed.shifted(self.arena, node.start as i64)),
+            false => None,
+        }
+    }
+
+    /// Copy of `list` with the node at `index` replaced by `edited`. Nodes before it
+    /// are shared, nodes after it are shifted past the edit.
+    fn splice<T>(
+        &self,
+        list: NodeList<'ast, T>,
+        index: usize,
+        edited: Node<'ast, T>,
+    ) -> NodeList<'ast, T>
+    where
+        T: 'ast + Copy + Shift<'ast>,
+    {
+        let builder = GrowableList::new();
+
+        for (position, node) in list.iter().enumerate() {
+            let node = if position < index {
+                *node
+            } else if position == index {
+                edited
+            } else {
+                node.shifted(self.arena, self.edit.delta())
+            };
+
+            builder.push(self.arena, node);
+        }
+
+        builder.as_list()
+    }
+
+    #[inline]
+    fn shift(&self, offset: u32) -> u32 {
+        (offset as i64 + self.edit.delta()) as u32
+    }
+
+    #[inline]
+    fn alloc<T: Copy>(&self, start: u32, end: u32, value: T) -> Node<'ast, T> {
+        Node::new(self.arena.alloc(NodeInner::new(start, end, value)))
+    }
+}
@@ -6,6 +6,8 @@ extern crate toolshed;
 mod mock;
 #[macro_use]
 mod expect_macro;
+#[macro_use]
+mod trace_macro;
 
 #[cfg(test)]
 #[macro_use]
@@ -16,22 +18,25 @@ mod contract;
 mod error;
 mod expression;
 mod function;
+mod incremental;
 mod nested;
 mod source;
 mod statement;
 mod type_name;
 
+use std::ops::Range;
 use toolshed::list::GrowableList;
 use toolshed::{Arena, NulTermStr};
 
+pub use self::error::{Error, ErrorKind};
+pub use self::incremental::*;
 pub use self::nested::*;
 pub use self::statement::{FunctionContext, ModifierContext, StatementContext};
 pub use self::type_name::{RegularTypeNameContext, StatementTypeNameContext, TypeNameContext};
 
 use ast::*;
-use error::Error;
 use lexer::Token::*;
-use lexer::{Lexer, Token};
+use lexer::{Lexer, Logos, Source, Token};
 
 pub struct Parser<'ast> {
     arena: &'ast Arena,
@@ -42,6 +47,9 @@ pub struct Parser<'ast> {
     /// Errors occurred during parsing
     errors: Vec<Error>,
 
+    /// Errors since the innermost list item started, decides whether it has to be recovered
+    pending_errors: usize,
+
     /// AST under construction
     body: SourceUnitList<'ast>,
 }
@@ -54,6 +62,7 @@ impl<'ast> Parser<'ast> {
             arena,
             lexer: Lexer::new(source),
             errors: Vec::new(),
+            pending_errors: 0,
             body: NodeList::empty(),
         }
     }
@@ -73,7 +82,7 @@ impl<'ast> Parser<'ast> {
         if self.lexer.token == token {
             self.lexer.advance();
         } else {
-            self.error();
+            self.error_expected(&[token]);
         }
     }
 
@@ -82,7 +91,7 @@ impl<'ast> Parser<'ast> {
         if self.lexer.token == token && self.lexer.slice() == expected {
             self.lexer.advance();
         } else {
-            self.error();
+            self.report(ErrorKind::UnexpectedToken, vec![format!("`{}`", expected).into()]);
         }
     }
 
@@ -115,6 +124,41 @@ impl<'ast> Parser<'ast> {
         self.node_at(start, end, val)
     }
 
+    /// `Identifier ('.' Identifier)*` starting at the current token, which has to be an
+    /// identifier, with `is_identifier` telling which tokens are. Kept as a single node
+    /// spanning the whole path, named after the identifiers joined with `.` so that
+    /// anything between them, like `x . slot`, is left out. A missing identifier is
+    /// reported, ending the path.
+    fn identifier_path_with<F>(&mut self, is_identifier: F) -> IdentifierNode<'ast>
+    where
+        F: Fn(&Self) -> bool,
+    {
+        let first: IdentifierNode = self.str_node();
+        let mut path = vec![first.value];
+        let mut end = first.end;
+
+        while self.allow(Token::Accessor) {
+            if !is_identifier(self) {
+                self.error_expected(&[Token::Identifier]);
+
+                break;
+            }
+
+            let id: IdentifierNode = self.str_node();
+
+            path.push(id.value);
+            end = id.end;
+        }
+
+        if path.len() == 1 {
+            return first;
+        }
+
+        let path = self.arena.alloc_str(&path.join("."));
+
+        self.node_at(first.start, end, path)
+    }
+
     #[inline]
     fn allow_str_node(&mut self, token: Token) -> Option<Node<'ast, &'ast str>> {
         if self.lexer.token == token {
@@ -133,6 +177,26 @@ impl<'ast> Parser<'ast> {
         }
     }
 
+    /// Look at the token following the current one without advancing the lexer.
+    #[inline]
+    fn peek(&self) -> Token {
+        let rest = &self.lexer.source[self.lexer.range().end..];
+
+        Token::lexer(rest).token
+    }
+
+    /// Look at the two tokens following the current one without advancing the lexer.
+    #[inline]
+    fn peek_two(&self) -> (Token, Token) {
+        let rest = &self.lexer.source[self.lexer.range().end..];
+        let mut lexer = Token::lexer(rest);
+        let first = lexer.token;
+
+        lexer.advance();
+
+        (first, lexer.token)
+    }
+
     #[inline]
     fn loc(&mut self) -> (u32, u32) {
         let range = self.lexer.range();
@@ -159,11 +223,141 @@ impl<'ast> Parser<'ast> {
     }
 
     fn error(&mut self) {
-        let token = self.lexer.token;
-        let raw = self.lexer.slice().into();
+        self.report(ErrorKind::UnexpectedToken, Vec::new());
+    }
+
+    fn error_expected(&mut self, expected: &[Token]) {
+        let expected = expected.iter().map(|token| error::describe(*token)).collect();
+
+        self.report(ErrorKind::UnexpectedToken, expected);
+    }
+
+    /// Records an error at the current token.
+    fn report(&mut self, kind: ErrorKind, expected: Vec<Box<str>>) {
         let span = self.lexer.range();
 
-        self.errors.push(Error { token, raw, span });
+        self.report_at(kind, span, expected);
+    }
+
+    fn report_at(&mut self, mut kind: ErrorKind, span: Range<usize>, expected: Vec<Box<str>>) {
+        let token = self.lexer.token;
+        let raw = self.lexer.source.get(span.clone()).unwrap_or("").into();
+
+        if let EndOfProgram | UnexpectedEndOfProgram = token {
+            if kind == ErrorKind::UnexpectedToken {
+                kind = ErrorKind::UnexpectedEndOfProgram;
+            }
+        }
+
+        self.pending_errors += 1;
+
+        // Failing rules unwinding on the same token add what they expected to the
+        // diagnostic already there
+        if let Some(last) = self.errors.last_mut().filter(|last| last.span == span) {
+            for expected in expected {
+                if !last.expected.contains(&expected) {
+                    last.expected.push(expected);
+                }
+            }
+
+            return;
+        }
+
+        self.errors.push(Error {
+            kind,
+            token,
+            raw,
+            span,
+            expected,
+        });
+    }
+
+    /// Parses a single item of a list closed by `close`, returns `None` at the end of the list.
+    ///
+    /// If the item doesn't parse, everything up to the next `;` or balanced `}` is skipped
+    /// and returned as an `Unknown` node, so that parsing can resume with the next item.
+    fn list_item<T, F>(&mut self, close: Token, item: F) -> Option<Node<'ast, T>>
+    where
+        T: 'ast + Copy + From<Unknown<'ast>>,
+        F: FnOnce(&mut Self) -> Option<Node<'ast, T>>,
+    {
+        match self.lexer.token {
+            EndOfProgram | UnexpectedEndOfProgram => return None,
+            token if token == close => return None,
+            _ => {}
+        }
+
+        let start = self.lexer.range().start;
+        let outer_errors = std::mem::replace(&mut self.pending_errors, 0);
+        let node = item(self);
+
+        if node.is_none() && self.pending_errors == 0 {
+            self.error();
+        }
+
+        let failed = self.pending_errors != 0;
+
+        self.pending_errors = outer_errors;
+
+        if !failed {
+            return node;
+        }
+
+        if !self.consumed_terminator(start) {
+            self.synchronize(close);
+        }
+
+        let end = self.consumed_end();
+
+        trace_event!(start, end, "skipped unparsable source");
+
+        if end <= start {
+            return None;
+        }
+
+        let raw = self
+            .lexer
+            .source
+            .slice(start..end)
+            .expect("Range is within the source; qed");
+
+        self.node_at(start as u32, end as u32, Unknown { raw })
+    }
+
+    /// Skips tokens up to and including the next `;`, or the `}` closing a block opened
+    /// along the way. Stops in front of `close` if it's found outside of any block.
+    fn synchronize(&mut self, close: Token) {
+        let mut depth = 0u32;
+
+        loop {
+            match self.lexer.token {
+                EndOfProgram | UnexpectedEndOfProgram => return,
+                token if token == close && depth == 0 => return,
+                Semicolon if depth == 0 => break,
+                BraceOpen | ParenOpen | BracketOpen => depth += 1,
+                BraceClose if depth <= 1 => break,
+                BraceClose | ParenClose | BracketClose => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+
+            self.lexer.advance();
+        }
+
+        self.lexer.advance();
+    }
+
+    /// End of the last consumed token.
+    #[inline]
+    fn consumed_end(&self) -> usize {
+        self.lexer.source[..self.lexer.range().start].trim_end().len()
+    }
+
+    /// Checks whether tokens past `start` were consumed, the last of them being a `;` or `}`.
+    #[inline]
+    fn consumed_terminator(&self, start: usize) -> bool {
+        let consumed = &self.lexer.source[start..self.lexer.range().start];
+
+        consumed.trim_end().ends_with([';', '}'])
     }
 
     #[inline]
@@ -214,38 +408,98 @@ impl<'ast> Parser<'ast> {
         self.node_at(start, end, func(slice))
     }
 
+    /// Like `node_from_slice`, with the `_` separators of number literals removed.
+    #[inline]
+    fn number_from_slice<T, F, I, R>(&mut self, func: F) -> R
+    where
+        T: 'ast + Copy,
+        F: FnOnce(&'ast str) -> I,
+        I: Into<T>,
+        R: From<Node<'ast, T>>,
+    {
+        let number = self.number_slice();
+
+        self.node_from_slice(|_| func(number))
+    }
+
+    #[inline]
+    fn number_slice(&mut self) -> &'ast str {
+        let slice = self.lexer.slice();
+
+        if slice.contains('_') {
+            self.arena.alloc_string(slice.replace('_', ""))
+        } else {
+            slice
+        }
+    }
+
     #[inline]
     fn parse(&mut self) {
+        trace_span!("parse", len = self.lexer.source.len());
+
         let builder = GrowableList::new();
 
-        while let Some(unit) = self.source_unit() {
-            println!("{:#?} unit", unit);
+        while let Some(unit) = self.list_item(EndOfProgram, Self::source_unit) {
             builder.push(self.arena, unit);
         }
-        // The issue is that ] doesnot get pushed in
+
         self.body = builder.as_list();
-        // println!("{:#?} ASDADAASDASDAS", self.body);
         self.expect(EndOfProgram);
+
+        trace_event!(
+            units = self.body.iter().count(),
+            errors = self.errors.len(),
+            "parsed source units"
+        );
     }
 
     #[inline]
     fn unique_flag<F>(&mut self, at: &mut Option<Node<'ast, F>>, flag: F)
     where
-        F: Copy,
+        F: Copy + UniqueFlag,
     {
         if at.is_some() {
-            self.lexer.advance();
+            self.report(F::REDECLARED, Vec::new());
 
-            // TODO: More descriptive errors, something like "Can't redeclare visibility/mutability"
-            return self.error();
+            return self.lexer.advance();
         }
 
         *at = self.node_at_token(flag);
     }
 }
 
+/// Flags that can only be declared once, like the visibility of a function.
+trait UniqueFlag {
+    const REDECLARED: ErrorKind;
+}
+
+impl UniqueFlag for FunctionVisibility {
+    const REDECLARED: ErrorKind = ErrorKind::DuplicateVisibility;
+}
+
+impl UniqueFlag for StateVariableVisibility {
+    const REDECLARED: ErrorKind = ErrorKind::DuplicateVisibility;
+}
+
+impl UniqueFlag for StateMutability {
+    const REDECLARED: ErrorKind = ErrorKind::DuplicateMutability;
+}
+
+impl UniqueFlag for Flag {
+    const REDECLARED: ErrorKind = ErrorKind::DuplicateFlag;
+}
+
 /// Parse the Solidity source from `&str` and produce an Abstract Syntax Tree for it.
 pub fn parse<'src, 'ast>(source: &'src str) -> Result<Program<'ast>, Vec<Error>> {
+    match parse_with_errors(source) {
+        (program, errors) if errors.is_empty() => Ok(program),
+        (_, errors) => Err(errors),
+    }
+}
+
+/// Like `parse`, but always produces a `Program`. Source that couldn't be parsed
+/// is kept as `Unknown` nodes next to the errors explaining why.
+pub fn parse_with_errors<'ast>(source: &str) -> (Program<'ast>, Vec<Error>) {
     let arena = Arena::new();
     let (body, errors) = {
         let mut parser = Parser::new(source, &arena);
@@ -254,10 +508,7 @@ pub fn parse<'src, 'ast>(source: &'src str) -> Result<Program<'ast>, Vec<Error>>
         (parser.body.into_unsafe(), parser.errors)
     };
 
-    match errors.len() {
-        0 => Ok(Program::new(body, arena)),
-        _ => Err(errors),
-    }
+    (Program::new(body, arena, SourceMap::new(source)), errors)
 }
 
 #[cfg(test)]
@@ -270,4 +521,273 @@ mod test {
 
         let ast = parse(source).unwrap();
     }
+
+    #[test]
+    fn recovers_from_unknown_constructs() {
+        let source = r#"
+            contract Foo {
+                uint256 a = ;
+                function bar() public {
+                    uint256 b = 1 +;
+                    b = 2;
+                }
+                ~~~ { nested; }
+                uint256 c;
+            }
+            }
+            contract Bar {}
+        "#;
+
+        let (program, errors) = parse_with_errors(source);
+
+        assert_eq!(errors.len(), 4);
+
+        let body: Vec<_> = program.body().iter().map(|unit| unit.value).collect();
+
+        let contract = match body[..] {
+            [SourceUnit::ContractDefinition(contract), SourceUnit::Unknown(Unknown { raw: "}" }), SourceUnit::ContractDefinition(bar)] => {
+                assert_eq!(bar.name.value, "Bar");
+
+                contract
+            }
+            _ => panic!("Unexpected body: {:#?}", body),
+        };
+
+        let parts: Vec<_> = contract.body.iter().map(|part| part.value).collect();
+
+        let function = match parts[..] {
+            [ContractPart::Unknown(Unknown { raw: "uint256 a = ;" }), ContractPart::FunctionDefinition(function), ContractPart::Unknown(Unknown { raw: "~~~ { nested; }" }), ContractPart::StateVariableDeclaration(c)] => {
+                assert_eq!(c.name.value, "c");
+
+                function
+            }
+            _ => panic!("Unexpected contract parts: {:#?}", parts),
+        };
+
+        let statements: Vec<_> = function
+            .block
+            .unwrap()
+            .value
+            .body
+            .iter()
+            .map(|statement| statement.value)
+            .collect();
+
+        assert!(matches!(
+            statements[..],
+            [
+                Statement::Unknown(Unknown {
+                    raw: "uint256 b = 1 +;"
+                }),
+                Statement::ExpressionStatement(_)
+            ]
+        ));
+    }
+
+    #[test]
+    fn unknown_nodes_span_the_skipped_source() {
+        let source = "pragma solidity ^0.8.0; contract Foo { ) }";
+        let (program, errors) = parse_with_errors(source);
+
+        assert_eq!(errors.len(), 1);
+
+        let contract = match program.body().iter().nth(1).map(|unit| unit.value) {
+            Some(SourceUnit::ContractDefinition(contract)) => contract,
+            unit => panic!("Unexpected unit: {:#?}", unit),
+        };
+
+        let part = contract.body.iter().next().unwrap();
+
+        assert_eq!((part.start, part.end), (39, 40));
+        assert_eq!(part.value, ContractPart::Unknown(Unknown { raw: ")" }));
+        assert!(parse(source).is_err());
+    }
+
+    #[test]
+    fn reports_a_token_once() {
+        let source = r#"
+            contract Foo {
+                function bar() public {
+                    (bool ok, ) = msg.sender.call("");
+                }
+            }
+        "#;
+
+        let (_, errors) = parse_with_errors(source);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].span, 94..96);
+        assert_eq!(errors[0].message(), "unexpected `ok`, expected `)` or `;`");
+    }
+
+    #[test]
+    fn indexes_parents_and_offsets() {
+        let source = "contract Foo {\n    function bar() {\n        total = total + price(1);\n    }\n}\n";
+        let program = parse(source).unwrap();
+        let index = program.index();
+
+        let price = index.at_offset(62).unwrap();
+        let call = index.parent(price).unwrap();
+        let function = index.enclosing(price, NodeKind::FunctionDefinition).unwrap();
+        let contract = index.enclosing(price, NodeKind::ContractDefinition).unwrap();
+
+        assert_eq!(index.kind(price), NodeKind::IdentifierExpression);
+        assert_eq!((index.entry(price).start, index.entry(price).end), (60, 65));
+        assert_eq!(index.kind(call), NodeKind::CallExpression);
+        assert_eq!(index.entry(function).start, 19);
+        assert_eq!(index.parent(function), Some(contract));
+        assert_eq!(index.parent(contract), None);
+        assert_eq!(index.ancestors(price).last(), Some(contract));
+
+        assert_eq!(index.kind(index.at_offset(66).unwrap()), NodeKind::PrimitiveExpression);
+        assert_eq!(index.at_offset(74), Some(function));
+        assert_eq!(index.at_offset(source.len() as u32), None);
+
+        let unit = program.body().iter().next().copied().unwrap();
+
+        assert_eq!(program.node_id(unit), Some(contract));
+        assert_eq!(program.node_id(program.builder().ident("total")), None);
+    }
+
+    #[test]
+    fn owned_ast_round_trip() {
+        let source = include_str!("../../examples/second-price-auction.sol");
+        let program = parse(source).unwrap();
+        let owned = program.to_owned_ast();
+
+        // The owned AST can be parked on another thread and come back
+        let owned = std::thread::spawn(move || owned).join().unwrap();
+
+        assert_eq!(owned.body.len(), program.body().iter().count());
+        assert_eq!(owned.body[0].start, program.body().first_element().unwrap().start);
+        assert_eq!(owned.to_program().body(), program.body());
+        assert_eq!(owned.to_program().to_owned_ast(), owned);
+        assert_eq!(owned.to_program().source(), source);
+    }
+
+    #[test]
+    fn compares_and_hashes_ignoring_spans() {
+        let compact = parse("contract A { function f() { x = a + 1; } }").unwrap();
+        let spaced = parse(
+            "contract A {\n    function f() {\n        x = a\n          + 1;\n    }\n}\n",
+        )
+        .unwrap();
+        let changed = parse("contract A { function f() { x = a - 1; } }").unwrap();
+
+        assert_ne!(compact.body(), spaced.body());
+        assert!(compact.body().syntax_eq(&spaced.body()));
+        assert_eq!(syntax_hash(&compact.body()), syntax_hash(&spaced.body()));
+
+        assert!(!compact.body().syntax_eq(&changed.body()));
+        assert_ne!(syntax_hash(&compact.body()), syntax_hash(&changed.body()));
+
+        // The hash doesn't depend on the run, unlike `DefaultHasher` with `RandomState`
+        let unit = compact.body().first_element().copied().unwrap();
+
+        assert_eq!(syntax_hash(&unit), syntax_hash(&unit.value));
+        assert_eq!(syntax_hash(&"x"), 0x08f1_c907_b58e_ba68);
+
+        // Nor on how the compiler derives `Hash` for the enums
+        assert_eq!(syntax_hash(&BinaryOperator::Addition), 0xaf63_b94c_8601_b113);
+    }
+
+    #[test]
+    fn joins_identifier_paths_with_dots() {
+        let compact = parse(
+            "using Math.sub for uint;\
+             contract A { function f() { assembly { x := y.slot } } }",
+        )
+        .unwrap();
+        let spaced = parse(
+            "using Math . sub for uint;\
+             contract A { function f() { assembly { x := y . slot } } }",
+        )
+        .unwrap();
+
+        assert!(compact.body().syntax_eq(&spaced.body()));
+    }
+
+    #[test]
+    fn keeps_the_source() {
+        let program = parse("contract Foo {\n    uint256 total;\n}\n").unwrap();
+        let contract = match program.body().first_element().unwrap().value {
+            SourceUnit::ContractDefinition(contract) => contract,
+            _ => panic!("Expected a contract"),
+        };
+        let part = contract.body.first_element().unwrap();
+
+        assert_eq!(program.slice(&contract.name), Some("Foo"));
+        assert_eq!(program.line_col(part.start), LineCol { line: 1, col: 4 });
+        assert_eq!(program.file(), FileId(0));
+        assert_eq!(program.path(), None);
+        assert_eq!(program.with_path("Foo.sol").path(), Some(std::path::Path::new("Foo.sol")));
+    }
+
+    #[test]
+    fn reparse_matches_a_full_parse() {
+        let mut source = String::from(
+            "pragma solidity ^0.8.0;\n\
+             \n\
+             contract A {\n    \
+                 uint x = 1;\n\n    \
+                 function f(uint a) public returns (uint) {\n        \
+                     return a + x;\n    \
+                 }\n\n    \
+                 event E(uint y);\n\
+             }\n\
+             \n\
+             function g() pure returns (uint) {\n    \
+                 return 2;\n\
+             }\n\
+             \n\
+             contract B is A {\n    \
+                 function h() public {}\n\
+             }\n",
+        );
+
+        // Each edit replaces the first occurrence of some text in the previous source, and
+        // whether the untouched pragma is reused rather than parsed again
+        let edits = [
+            // Within contract parts
+            ("a + x", "a * x + 1", true),
+            ("uint x = 1", "uint x = 10", true),
+            ("uint y", "uint", true),
+            ("public {}", "public {\n        x;\n    }", true),
+            // Within the header of a contract and a free function
+            ("B is A", "B is A, C", true),
+            ("return 2", "return 20", true),
+            // Between source units
+            ("}\n\nfunction g", "}\n\n\nfunction g", false),
+            // Unparsable on its own, and a program with errors to start from
+            ("x;\n", "x /* ;\n", false),
+            ("x /* ;\n", "x;\n", false),
+            ("uint x = 10", "uint x =", false),
+            ("uint x =", "uint x = 1", false),
+        ];
+
+        let mut program = parse(&source).unwrap().with_file(FileId(3));
+
+        for (old, new, reused) in edits {
+            let start = source.find(old).unwrap();
+            let edit = TextEdit::new(start as u32, (start + old.len()) as u32, new.len() as u32);
+            let pragma = &**program.body().first_element().unwrap() as *const _;
+
+            source.replace_range(start..start + old.len(), new);
+
+            let (reparsed, errors) = reparse(program, edit, &source);
+            let (expected, expected_errors) = parse_with_errors(&source);
+
+            assert_eq!(reparsed.body(), expected.body(), "after replacing {:?}", old);
+            assert_eq!(errors, expected_errors, "after replacing {:?}", old);
+            assert_eq!(reparsed.source(), source);
+            assert_eq!(reparsed.index().len(), expected.index().len());
+            assert_eq!(reparsed.file(), FileId(3));
+
+            let first = &**reparsed.body().first_element().unwrap() as *const _;
+
+            assert_eq!(std::ptr::eq(pragma, first), reused, "after replacing {:?}", old);
+
+            program = reparsed;
+        }
+    }
 }
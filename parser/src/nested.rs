@@ -31,6 +31,7 @@ const INVALID: Precedence = Precedence(100);
 static NESTED_LUT: [NestedHandler; Token::SIZE] = lookup! {
     Token::Accessor               => NestedHandler(P2, MEMBER),
     Token::ParenOpen              => NestedHandler(P2, CALL),
+    Token::BraceOpen              => NestedHandler(P2, CALL_OPTIONS),
     Token::BracketOpen            => NestedHandler(P2, INDEX),
     Token::OperatorIncrement      => NestedHandler(P2, INC),
     Token::OperatorDecrement      => NestedHandler(P2, DEC),
@@ -82,12 +83,26 @@ impl NestedHandler {
 const CALL: HandlerFn = |par, callee| {
     par.lexer.advance();
 
-    let arguments = par.expression_list();
+    let arguments = par.call_arguments();
     let end = par.expect_end(Token::ParenClose);
 
     par.node_at(callee.start, end, CallExpression { callee, arguments })
 };
 
+const CALL_OPTIONS: HandlerFn = |par, callee| {
+    // Otherwise the brace opens a block, like the body of `try foo() { ... }`
+    if par.peek_two() != (Token::Identifier, Token::Colon) {
+        return None;
+    }
+
+    par.lexer.advance();
+
+    let options = par.named_argument_list();
+    let end = par.expect_end(Token::BraceClose);
+
+    par.node_at(callee.start, end, CallOptionsExpression { callee, options })
+};
+
 const MEMBER: HandlerFn = |par, object| {
     par.lexer.advance();
 
@@ -300,7 +315,7 @@ mod test {
                                             119,
                                             CallExpression {
                                                 callee: m.node(109, 112, "add"),
-                                                arguments: m.list([
+                                                arguments: CallArguments::Positional(m.list([
                                                     m.node(
                                                         113,
                                                         114,
@@ -317,7 +332,7 @@ mod test {
                                                             NumberUnit::None,
                                                         ),
                                                     ),
-                                                ]),
+                                                ])),
                                             },
                                         ),
                                         m.stmt_expr(
@@ -1054,4 +1069,141 @@ mod test {
             )],
         );
     }
+
+    #[test]
+    fn call_options_and_payable() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                function() {
+                    addr.call{value: 1}("");
+                    payable(owner).transfer(x);
+                    address payable to;
+                }
+            }
+
+        "#,
+            [m.node(
+                14,
+                222,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        208,
+                        FunctionDefinition {
+                            name: None,
+                            params: NodeList::empty(),
+                            visibility: None,
+                            mutability: None,
+                            modifiers: NodeList::empty(),
+                            returns: NodeList::empty(),
+                            block: m.node(
+                                56,
+                                208,
+                                Block {
+                                    body: m.list([
+                                        m.stmt_expr(
+                                            78,
+                                            101,
+                                            102,
+                                            CallExpression {
+                                                callee: m.node(
+                                                    78,
+                                                    97,
+                                                    CallOptionsExpression {
+                                                        callee: m.node(
+                                                            78,
+                                                            87,
+                                                            MemberAccessExpression {
+                                                                object: m.node(78, 82, "addr"),
+                                                                member: m.node(83, 87, "call"),
+                                                            },
+                                                        ),
+                                                        options: m.list([m.node(
+                                                            88,
+                                                            96,
+                                                            NamedArgument {
+                                                                name: m.node(88, 93, "value"),
+                                                                value: m.node(
+                                                                    95,
+                                                                    96,
+                                                                    Primitive::IntegerNumber(
+                                                                        "1",
+                                                                        NumberUnit::None,
+                                                                    ),
+                                                                ),
+                                                            },
+                                                        )]),
+                                                    },
+                                                ),
+                                                arguments: CallArguments::Positional(m.list([
+                                                    m.node(98, 100, Primitive::String("\"\"")),
+                                                ])),
+                                            },
+                                        ),
+                                        m.stmt_expr(
+                                            123,
+                                            149,
+                                            150,
+                                            CallExpression {
+                                                callee: m.node(
+                                                    123,
+                                                    146,
+                                                    MemberAccessExpression {
+                                                        object: m.node(
+                                                            123,
+                                                            137,
+                                                            CallExpression {
+                                                                callee: m.node(
+                                                                    123,
+                                                                    130,
+                                                                    ElementaryTypeName::AddressPayable,
+                                                                ),
+                                                                arguments: CallArguments::Positional(
+                                                                    m.list([m.node(131, 136, "owner")]),
+                                                                ),
+                                                            },
+                                                        ),
+                                                        member: m.node(138, 146, "transfer"),
+                                                    },
+                                                ),
+                                                arguments: CallArguments::Positional(m.list([
+                                                    m.node(147, 148, "x"),
+                                                ])),
+                                            },
+                                        ),
+                                        m.node(
+                                            171,
+                                            190,
+                                            VariableDefinitionStatement {
+                                                declaration: m.node(
+                                                    171,
+                                                    189,
+                                                    VariableDeclaration {
+                                                        type_name: m.node(
+                                                            171,
+                                                            186,
+                                                            ElementaryTypeName::AddressPayable,
+                                                        ),
+                                                        location: None,
+                                                        id: m.node(187, 189, "to"),
+                                                    },
+                                                ),
+                                                init: None,
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
 }
@@ -1,6 +1,6 @@
-use toolshed::list::ListBuilder;
+use toolshed::list::{GrowableList, ListBuilder};
 
-use crate::Parser;
+use crate::{ErrorKind, Parser, RegularTypeNameContext, TOP};
 use ast::*;
 use lexer::Token;
 
@@ -11,21 +11,209 @@ impl<'ast> Parser<'ast> {
             Token::KeywordPragma => self.pragma_directive(),
             Token::KeywordImport => self.import_directive(),
             Token::DeclarationContract => self.contract_definition(), // issue lies here + State Variable Declaration
-            _ => None,
+            Token::DeclarationStruct => self.struct_defintion(),
+            Token::DeclarationEnum => self.enum_definition(),
+            Token::DeclarationFunction => self.function_definition(),
+            Token::KeywordType => self.user_defined_value_type_definition(),
+            Token::KeywordUsing => self.using_for_declaration(true),
+            Token::Identifier
+                if self.lexer.slice() == "error" && self.peek() == Token::Identifier =>
+            {
+                self.error_definition()
+            }
+            _ => self.constant_declaration(),
         }
     }
 
+    fn constant_declaration(&mut self) -> Option<SourceUnitNode<'ast>> {
+        let type_name = self.type_name::<RegularTypeNameContext>()?;
+
+        self.expect(Token::KeywordConstant);
+
+        let name = self.expect_str_node(Token::Identifier);
+
+        self.expect(Token::Assign);
+
+        let init = expect!(self, self.expression(TOP));
+        let end = self.expect_end(Token::Semicolon);
+
+        self.node_at(
+            type_name.start,
+            end,
+            ConstantDeclaration {
+                type_name,
+                name,
+                init,
+            },
+        )
+    }
+
     fn pragma_directive(&mut self) -> Option<SourceUnitNode<'ast>> {
         let start = self.start_then_advance();
 
-        if self.lexer.token != Token::Identifier || self.lexer.slice() != "solidity" {
-            self.error();
+        if self.lexer.token != Token::Identifier {
+            self.error_expected(&[Token::Identifier]);
+
+            return None;
         }
 
-        let version = ::lexer::read_pragma(&mut self.lexer);
+        let pragma = match self.lexer.slice() {
+            "solidity" => {
+                let offset = self.lexer.range().start as u32;
+                let version = ::lexer::read_pragma(&mut self.lexer);
+                let ranges = self.version_ranges(&version["solidity".len()..], offset + 8)?;
+
+                PragmaDirective::Solidity { ranges }
+            }
+            "abicoder" => {
+                self.lexer.advance();
+
+                PragmaDirective::Abicoder {
+                    version: self.expect_str_node(Token::Identifier),
+                }
+            }
+            "experimental" => {
+                self.lexer.advance();
+
+                PragmaDirective::Experimental {
+                    feature: self.expect_str_node(Token::Identifier),
+                }
+            }
+            _ => {
+                self.report(ErrorKind::InvalidPragma, Vec::new());
+
+                return None;
+            }
+        };
+
         let end = self.expect_end(Token::Semicolon);
 
-        self.node_at(start, end, PragmaDirective { version })
+        self.node_at(start, end, pragma)
+    }
+
+    /// Parses `^0.8.0 || >=0.6 <0.9` out of the raw text of a version pragma,
+    /// `offset` is the position of the text within the source. A hyphen range
+    /// `0.8.0 - 0.8.19` is read as `>=0.8.0 <=0.8.19`.
+    fn version_ranges(&mut self, text: &str, offset: u32) -> Option<VersionRangeList<'ast>> {
+        let ranges = GrowableList::new();
+        let mut comparators: Vec<(u32, u32, VersionComparator)> = Vec::new();
+        let mut range = None;
+        let mut operator = None;
+        let mut hyphen = false;
+
+        // Errors point at the whole version requirement
+        let trimmed = text.trim();
+        let span_start = offset as usize + (trimmed.as_ptr() as usize - text.as_ptr() as usize);
+        let span = span_start..span_start + trimmed.len();
+
+        let words = text
+            .split_whitespace()
+            .map(|word| (offset + (word.as_ptr() as usize - text.as_ptr() as usize) as u32, word))
+            .chain(Some((offset + text.len() as u32, "||")));
+
+        for (start, word) in words {
+            let end = start + word.len() as u32;
+
+            if word == "||" {
+                let (range_start, range_end) = match range.take() {
+                    Some(range) if operator.is_none() && !hyphen => range,
+                    _ => {
+                        self.report_at(ErrorKind::InvalidPragma, span, Vec::new());
+
+                        return None;
+                    }
+                };
+
+                let list = GrowableList::new();
+
+                for (start, end, comparator) in comparators.drain(..) {
+                    list.push(self.arena, self.node_at(start, end, comparator));
+                }
+
+                let node = self.node_at(
+                    range_start,
+                    range_end,
+                    VersionRange {
+                        comparators: list.as_list(),
+                    },
+                );
+
+                ranges.push(self.arena, node);
+
+                continue;
+            }
+
+            // The lower bound of a hyphen range has to be a bare version
+            if word == "-" {
+                match comparators.last_mut() {
+                    Some((_, _, lower))
+                        if lower.operator == VersionOperator::Exact
+                            && operator.is_none()
+                            && !hyphen =>
+                    {
+                        lower.operator = VersionOperator::GreaterEquals;
+                        hyphen = true;
+                    }
+                    _ => {
+                        self.report_at(ErrorKind::InvalidPragma, span, Vec::new());
+
+                        return None;
+                    }
+                }
+
+                continue;
+            }
+
+            let (parsed, version) = version_operator(word);
+
+            // The operator can be separated from the version, as in `>= 0.6`
+            let (start, parsed) = match operator.take() {
+                Some(_) if parsed != VersionOperator::Exact || word.starts_with('=') => {
+                    self.report_at(ErrorKind::InvalidPragma, span, Vec::new());
+
+                    return None;
+                }
+                Some(operator) => operator,
+                None => (start, parsed),
+            };
+
+            // So does its upper bound
+            let parsed = if !hyphen {
+                parsed
+            } else if parsed == VersionOperator::Exact && !word.starts_with('=') {
+                VersionOperator::LesserEquals
+            } else {
+                self.report_at(ErrorKind::InvalidPragma, span, Vec::new());
+
+                return None;
+            };
+
+            if version.is_empty() {
+                operator = Some((start, parsed));
+
+                continue;
+            }
+
+            let version = match pragma_version(version) {
+                Some(version) => version,
+                None => {
+                    self.report_at(ErrorKind::InvalidPragma, span, Vec::new());
+
+                    return None;
+                }
+            };
+
+            let comparator = VersionComparator {
+                operator: parsed,
+                version,
+            };
+
+            comparators.push((start, end, comparator));
+            hyphen = false;
+            range = Some((range.map_or(start, |(start, _)| start), end));
+        }
+
+        Some(ranges.as_list())
     }
 
     fn import_directive(&mut self) -> Option<SourceUnitNode<'ast>> {
@@ -115,10 +303,53 @@ impl<'ast> Parser<'ast> {
     }
 }
 
+/// Splits the operator off a comparator like `>=0.6.0`.
+fn version_operator(comparator: &str) -> (VersionOperator, &str) {
+    let operators = [
+        (">=", VersionOperator::GreaterEquals),
+        ("<=", VersionOperator::LesserEquals),
+        (">", VersionOperator::Greater),
+        ("<", VersionOperator::Lesser),
+        ("^", VersionOperator::Caret),
+        ("~", VersionOperator::Tilde),
+        ("=", VersionOperator::Exact),
+    ];
+
+    for (prefix, operator) in operators {
+        if let Some(version) = comparator.strip_prefix(prefix) {
+            return (operator, version);
+        }
+    }
+
+    (VersionOperator::Exact, comparator)
+}
+
+fn pragma_version(version: &str) -> Option<PragmaVersion> {
+    let mut parts = version.split('.').map(|part| match part {
+        "x" | "X" | "*" => Some(None),
+        _ => part.parse().ok().map(Some),
+    });
+
+    let major = parts.next()???;
+    let minor = parts.next().unwrap_or(Some(None))?;
+    let patch = parts.next().unwrap_or(Some(None))?;
+
+    if parts.next().is_some() {
+        return None;
+    }
+
+    Some(PragmaVersion {
+        major,
+        minor,
+        patch,
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::mock::{assert_units, Mock};
+    use crate::parse;
 
     #[test]
     fn pragma() {
@@ -129,13 +360,179 @@ mod test {
             [m.node(
                 0,
                 24,
-                PragmaDirective {
-                    version: "solidity ^0.4.17",
+                PragmaDirective::Solidity {
+                    ranges: m.list([m.node(
+                        16,
+                        23,
+                        VersionRange {
+                            comparators: m.list([m.node(
+                                16,
+                                23,
+                                VersionComparator {
+                                    operator: VersionOperator::Caret,
+                                    version: PragmaVersion {
+                                        major: 0,
+                                        minor: Some(4),
+                                        patch: Some(17),
+                                    },
+                                },
+                            )]),
+                        },
+                    )]),
                 },
             )],
         );
     }
 
+    #[test]
+    fn pragma_ranges_and_features() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            pragma solidity >= 0.6 <0.9.0 || 0.8.x;
+            pragma abicoder v2;
+            pragma experimental SMTChecker;
+
+            "#,
+            [
+                m.node(
+                    14,
+                    53,
+                    PragmaDirective::Solidity {
+                        ranges: m.list([
+                            m.node(
+                                30,
+                                43,
+                                VersionRange {
+                                    comparators: m.list([
+                                        m.node(
+                                            30,
+                                            36,
+                                            VersionComparator {
+                                                operator: VersionOperator::GreaterEquals,
+                                                version: PragmaVersion {
+                                                    major: 0,
+                                                    minor: Some(6),
+                                                    patch: None,
+                                                },
+                                            },
+                                        ),
+                                        m.node(
+                                            37,
+                                            43,
+                                            VersionComparator {
+                                                operator: VersionOperator::Lesser,
+                                                version: PragmaVersion {
+                                                    major: 0,
+                                                    minor: Some(9),
+                                                    patch: Some(0),
+                                                },
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                            m.node(
+                                47,
+                                52,
+                                VersionRange {
+                                    comparators: m.list([m.node(
+                                        47,
+                                        52,
+                                        VersionComparator {
+                                            operator: VersionOperator::Exact,
+                                            version: PragmaVersion {
+                                                major: 0,
+                                                minor: Some(8),
+                                                patch: None,
+                                            },
+                                        },
+                                    )]),
+                                },
+                            ),
+                        ]),
+                    },
+                ),
+                m.node(
+                    66,
+                    85,
+                    PragmaDirective::Abicoder {
+                        version: m.node(82, 84, "v2"),
+                    },
+                ),
+                m.node(
+                    98,
+                    129,
+                    PragmaDirective::Experimental {
+                        feature: m.node(118, 128, "SMTChecker"),
+                    },
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn pragma_hyphen_range() {
+        let m = Mock::new();
+
+        assert_units(
+            "pragma solidity 0.8.0 - 0.8.19;",
+            [m.node(
+                0,
+                31,
+                PragmaDirective::Solidity {
+                    ranges: m.list([m.node(
+                        16,
+                        30,
+                        VersionRange {
+                            comparators: m.list([
+                                m.node(
+                                    16,
+                                    21,
+                                    VersionComparator {
+                                        operator: VersionOperator::GreaterEquals,
+                                        version: PragmaVersion {
+                                            major: 0,
+                                            minor: Some(8),
+                                            patch: Some(0),
+                                        },
+                                    },
+                                ),
+                                m.node(
+                                    24,
+                                    30,
+                                    VersionComparator {
+                                        operator: VersionOperator::LesserEquals,
+                                        version: PragmaVersion {
+                                            major: 0,
+                                            minor: Some(8),
+                                            patch: Some(19),
+                                        },
+                                    },
+                                ),
+                            ]),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
+
+    #[test]
+    fn invalid_pragma_versions() {
+        assert!(parse("pragma solidity ^foo;").is_err());
+        assert!(parse("pragma solidity >=;").is_err());
+        assert!(parse("pragma solidity 0.8 ||;").is_err());
+        assert!(parse("pragma solidity ^0.8 - 0.9;").is_err());
+        assert!(parse("pragma solidity 0.8 - >=0.9;").is_err());
+        assert!(parse("pragma solidity 0.8 -;").is_err());
+        assert!(parse("pragma solidity - 0.9;").is_err());
+        assert!(parse("pragma solidity;").is_err());
+        assert!(parse("pragma foo bar;").is_err());
+    }
+
     #[test]
     fn import() {
         let m = Mock::new();
@@ -289,4 +686,140 @@ mod test {
             ],
         );
     }
+
+    #[test]
+    fn file_level_declarations() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            struct Point { uint128 x; }
+            enum Side { Buy, Sell }
+            function noop() {}
+            error Unauthorized(address caller);
+            uint256 constant MAX = 100;
+            type Price is uint128;
+
+            "#,
+            [
+                m.node(
+                    14,
+                    41,
+                    StructDefinition {
+                        name: m.node(21, 26, "Point"),
+                        body: m.list([m.node(
+                            29,
+                            38,
+                            VariableDeclaration {
+                                type_name: m.node(29, 36, ElementaryTypeName::Uint(16)),
+                                location: None,
+                                id: m.node(37, 38, "x"),
+                            },
+                        )]),
+                    },
+                ),
+                m.node(
+                    54,
+                    77,
+                    EnumDefinition {
+                        name: m.node(59, 63, "Side"),
+                        variants: m.list([m.node(66, 69, "Buy"), m.node(71, 75, "Sell")]),
+                    },
+                ),
+                m.node(
+                    90,
+                    108,
+                    FunctionDefinition {
+                        name: m.node(99, 103, "noop"),
+                        params: NodeList::empty(),
+                        visibility: None,
+                        mutability: None,
+                        modifiers: NodeList::empty(),
+                        returns: NodeList::empty(),
+                        block: m.node(
+                            106,
+                            108,
+                            Block {
+                                body: NodeList::empty(),
+                            },
+                        ),
+                    },
+                ),
+                m.node(
+                    121,
+                    156,
+                    ErrorDefinition {
+                        name: m.node(127, 139, "Unauthorized"),
+                        params: m.list([m.node(
+                            140,
+                            154,
+                            Parameter {
+                                type_name: m.node(140, 147, ElementaryTypeName::Address),
+                                location: None,
+                                name: m.node(148, 154, "caller"),
+                            },
+                        )]),
+                    },
+                ),
+                m.node(
+                    169,
+                    196,
+                    ConstantDeclaration {
+                        type_name: m.node(169, 176, ElementaryTypeName::Uint(32)),
+                        name: m.node(186, 189, "MAX"),
+                        init: m.node(192, 195, Primitive::IntegerNumber("100", NumberUnit::None)),
+                    },
+                ),
+                m.node(
+                    209,
+                    231,
+                    UserDefinedValueTypeDefinition {
+                        name: m.node(214, 219, "Price"),
+                        underlying: m.node(223, 230, ElementaryTypeName::Uint(16)),
+                    },
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn using_for_global() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            using {add, Math.sub as -} for Amount global;
+
+            "#,
+            [m.node(
+                14,
+                59,
+                UsingForDeclaration {
+                    id: None,
+                    functions: m.list([
+                        m.node(
+                            21,
+                            24,
+                            UsingForFunction {
+                                id: m.node(21, 24, "add"),
+                                operator: None,
+                            },
+                        ),
+                        m.node(
+                            26,
+                            39,
+                            UsingForFunction {
+                                id: m.node(26, 34, "Math.sub"),
+                                operator: m.node(38, 39, UserDefinableOperator::Subtraction),
+                            },
+                        ),
+                    ]),
+                    type_name: m.node(45, 51, "Amount"),
+                    global: m.node(52, 58, Flag),
+                },
+            )],
+        );
+    }
 }
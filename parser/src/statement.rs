@@ -1,6 +1,6 @@
 use toolshed::list::{GrowableList, List, ListBuilder};
 
-use crate::{Parser, StatementTypeNameContext, TOP};
+use crate::{ErrorKind, Parser, StatementTypeNameContext, TOP};
 use ast::*;
 use lexer::Token;
 
@@ -82,8 +82,16 @@ impl<'ast> Parser<'ast> {
             Token::KeywordDo => self.do_while_statement::<Context>(),
             Token::KeywordReturn => self.return_statement(),
             Token::KeywordThrow => self.token_statement(ThrowStatement),
+            Token::KeywordEmit => self.emit_statement(),
+            Token::KeywordUnchecked => self.unchecked_block::<Context>(),
+            Token::KeywordTry => self.try_statement::<Context>(),
             Token::KeywordAssembly => self.inline_assembly_statement(),
             Token::DeclarationVar => self.inferred_definition_statement(),
+            Token::IdentifierBuiltin
+                if self.lexer.slice() == "revert" && self.peek() == Token::Identifier =>
+            {
+                self.revert_statement()
+            }
 
             _ => match self.variable_definition_statement() {
                 None => self.expression_statement(),
@@ -112,7 +120,7 @@ impl<'ast> Parser<'ast> {
         let start = self.start_then_advance();
         let body = GrowableList::new();
 
-        while let Some(statement) = self.statement::<Context>() {
+        while let Some(statement) = self.list_item(Token::BraceClose, Self::statement::<Context>) {
             body.push(self.arena, statement);
         }
 
@@ -256,17 +264,153 @@ impl<'ast> Parser<'ast> {
         self.node_at(start, end, ReturnStatement { value })
     }
 
+    fn emit_statement(&mut self) -> Option<StatementNode<'ast>> {
+        let start = self.start_then_advance();
+        let event = expect!(self, self.expression(TOP));
+        let end = self.expect_end(Token::Semicolon);
+
+        self.node_at(start, end, EmitStatement { event })
+    }
+
+    fn unchecked_block<Context>(&mut self) -> Option<StatementNode<'ast>>
+    where
+        Context: StatementContext<'ast>,
+    {
+        let start = self.start_then_advance();
+
+        if self.lexer.token != Token::BraceOpen {
+            self.error_expected(&[Token::BraceOpen]);
+
+            return None;
+        }
+
+        let block = self.block::<Context, _>();
+
+        self.node_at(start, block.end, UncheckedBlock { block })
+    }
+
+    fn try_statement<Context>(&mut self) -> Option<StatementNode<'ast>>
+    where
+        Context: StatementContext<'ast>,
+    {
+        let start = self.start_then_advance();
+        let call = expect!(self, self.expression(TOP));
+        let returns;
+
+        if self.allow(Token::KeywordReturns) {
+            self.expect(Token::ParenOpen);
+
+            returns = self.parameter_list();
+
+            self.expect(Token::ParenClose);
+        } else {
+            returns = NodeList::empty();
+        }
+
+        if self.lexer.token != Token::BraceOpen {
+            self.error_expected(&[Token::BraceOpen]);
+
+            return None;
+        }
+
+        let body = self.block::<Context, _>();
+        let mut end = body.end;
+        let clauses = GrowableList::new();
+
+        while let Some(clause) = self.catch_clause::<Context>() {
+            end = clause.end;
+
+            clauses.push(self.arena, clause);
+        }
+
+        // At least one `catch` is required
+        if clauses.as_list().is_empty() {
+            self.report(ErrorKind::MissingCatchClause, Vec::new());
+        }
+
+        self.node_at(
+            start,
+            end,
+            TryStatement {
+                call,
+                returns,
+                body,
+                clauses: clauses.as_list(),
+            },
+        )
+    }
+
+    fn catch_clause<Context>(&mut self) -> Option<CatchClauseNode<'ast>>
+    where
+        Context: StatementContext<'ast>,
+    {
+        if self.lexer.token != Token::KeywordCatch {
+            return None;
+        }
+
+        let start = self.start_then_advance();
+        let id = self.allow_str_node(Token::Identifier);
+        let params;
+
+        if self.allow(Token::ParenOpen) {
+            params = self.parameter_list();
+
+            self.expect(Token::ParenClose);
+        } else {
+            params = NodeList::empty();
+        }
+
+        if self.lexer.token != Token::BraceOpen {
+            self.error_expected(&[Token::BraceOpen]);
+
+            return None;
+        }
+
+        let body = self.block::<Context, _>();
+
+        self.node_at(start, body.end, CatchClause { id, params, body })
+    }
+
+    fn revert_statement(&mut self) -> Option<StatementNode<'ast>> {
+        let start = self.start_then_advance();
+        let error = expect!(self, self.expression(TOP));
+        let end = self.expect_end(Token::Semicolon);
+
+        self.node_at(start, end, RevertStatement { error })
+    }
+
     fn inline_assembly_statement(&mut self) -> Option<StatementNode<'ast>> {
         let start = self.start_then_advance();
         let string = self.allow_str_node(Token::LiteralString);
+        let flags = GrowableList::new();
+
+        if self.allow(Token::ParenOpen) {
+            while let Some(flag) = self.allow_str_node(Token::LiteralString) {
+                flags.push(self.arena, flag);
+
+                if !self.allow(Token::Comma) {
+                    break;
+                }
+            }
+
+            self.expect(Token::ParenClose);
+        }
 
         if self.lexer.token != Token::BraceOpen {
-            self.error();
+            self.error_expected(&[Token::BraceOpen]);
         }
 
         let block = expect!(self, self.inline_assembly_block());
 
-        self.node_at(start, block.end, InlineAssemblyStatement { string, block })
+        self.node_at(
+            start,
+            block.end,
+            InlineAssemblyStatement {
+                string,
+                flags: flags.as_list(),
+                block,
+            },
+        )
     }
 
     fn expression_statement<S>(&mut self) -> Option<Node<'ast, S>>
@@ -1076,6 +1220,322 @@ mod test {
         );
     }
 
+    #[test]
+    fn emit_and_unchecked_statements() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                function bar() {
+                    emit Transfer(a, b);
+                    unchecked { i++; }
+                }
+            }
+
+        "#,
+            [m.node(
+                14,
+                173,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        159,
+                        FunctionDefinition {
+                            name: m.node(54, 57, "bar"),
+                            params: NodeList::empty(),
+                            visibility: None,
+                            mutability: None,
+                            modifiers: NodeList::empty(),
+                            returns: NodeList::empty(),
+                            block: m.node(
+                                60,
+                                159,
+                                Block {
+                                    body: m.list([
+                                        m.node(
+                                            82,
+                                            102,
+                                            EmitStatement {
+                                                event: m.node(
+                                                    87,
+                                                    101,
+                                                    CallExpression {
+                                                        callee: m.node(87, 95, "Transfer"),
+                                                        arguments: CallArguments::Positional(
+                                                            m.list([
+                                                                m.node(96, 97, "a"),
+                                                                m.node(99, 100, "b"),
+                                                            ]),
+                                                        ),
+                                                    },
+                                                ),
+                                            },
+                                        ),
+                                        m.node(
+                                            123,
+                                            141,
+                                            UncheckedBlock {
+                                                block: m.node(
+                                                    133,
+                                                    141,
+                                                    Block {
+                                                        body: m.list([m.stmt_expr(
+                                                            135,
+                                                            138,
+                                                            139,
+                                                            PostfixExpression {
+                                                                operand: m.node(135, 136, "i"),
+                                                                operator: m.node(
+                                                                    136,
+                                                                    138,
+                                                                    PostfixOperator::Increment,
+                                                                ),
+                                                            },
+                                                        )]),
+                                                    },
+                                                ),
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
+
+    #[test]
+    fn try_statement() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                function bar() {
+                    try foo.bar() returns (uint x) {
+                    } catch Error(string memory r) {
+                    } catch (bytes memory) {
+                    }
+                }
+            }
+
+        "#,
+            [m.node(
+                14,
+                266,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        252,
+                        FunctionDefinition {
+                            name: m.node(54, 57, "bar"),
+                            params: NodeList::empty(),
+                            visibility: None,
+                            mutability: None,
+                            modifiers: NodeList::empty(),
+                            returns: NodeList::empty(),
+                            block: m.node(
+                                60,
+                                252,
+                                Block {
+                                    body: m.list([m.node(
+                                        82,
+                                        234,
+                                        TryStatement {
+                                            call: m.node(
+                                                86,
+                                                95,
+                                                CallExpression {
+                                                    callee: m.node(
+                                                        86,
+                                                        93,
+                                                        MemberAccessExpression {
+                                                            object: m.node(86, 89, "foo"),
+                                                            member: m.node(90, 93, "bar"),
+                                                        },
+                                                    ),
+                                                    arguments: CallArguments::Positional(
+                                                        NodeList::empty(),
+                                                    ),
+                                                },
+                                            ),
+                                            returns: m.list([m.node(
+                                                105,
+                                                111,
+                                                Parameter {
+                                                    type_name: m.node(
+                                                        105,
+                                                        109,
+                                                        ElementaryTypeName::Uint(32),
+                                                    ),
+                                                    location: None,
+                                                    name: m.node(110, 111, "x"),
+                                                },
+                                            )]),
+                                            body: m.node(
+                                                113,
+                                                136,
+                                                Block {
+                                                    body: NodeList::empty(),
+                                                },
+                                            ),
+                                            clauses: m.list([
+                                                m.node(
+                                                    137,
+                                                    189,
+                                                    CatchClause {
+                                                        id: m.node(143, 148, "Error"),
+                                                        params: m.list([m.node(
+                                                            149,
+                                                            164,
+                                                            Parameter {
+                                                                type_name: m.node(
+                                                                    149,
+                                                                    155,
+                                                                    ElementaryTypeName::String,
+                                                                ),
+                                                                location: m.node(
+                                                                    156,
+                                                                    162,
+                                                                    StorageLocation::Memory,
+                                                                ),
+                                                                name: m.node(163, 164, "r"),
+                                                            },
+                                                        )]),
+                                                        body: m.node(
+                                                            166,
+                                                            189,
+                                                            Block {
+                                                                body: NodeList::empty(),
+                                                            },
+                                                        ),
+                                                    },
+                                                ),
+                                                m.node(
+                                                    190,
+                                                    234,
+                                                    CatchClause {
+                                                        id: None,
+                                                        params: m.list([m.node(
+                                                            197,
+                                                            209,
+                                                            Parameter {
+                                                                type_name: m.node(
+                                                                    197,
+                                                                    202,
+                                                                    ElementaryTypeName::Bytes,
+                                                                ),
+                                                                location: m.node(
+                                                                    203,
+                                                                    209,
+                                                                    StorageLocation::Memory,
+                                                                ),
+                                                                name: None,
+                                                            },
+                                                        )]),
+                                                        body: m.node(
+                                                            211,
+                                                            234,
+                                                            Block {
+                                                                body: NodeList::empty(),
+                                                            },
+                                                        ),
+                                                    },
+                                                ),
+                                            ]),
+                                        },
+                                    )]),
+                                },
+                            ),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
+
+    #[test]
+    fn revert_statement() {
+        let m = Mock::new();
+
+        assert_units(
+            r#"
+
+            contract Foo {
+                function bar() {
+                    revert Unauthorized();
+                    revert("nope");
+                }
+            }
+
+        "#,
+            [m.node(
+                14,
+                172,
+                ContractDefinition {
+                    name: m.node(23, 26, "Foo"),
+                    inherits: NodeList::empty(),
+                    body: m.list([m.node(
+                        45,
+                        158,
+                        FunctionDefinition {
+                            name: m.node(54, 57, "bar"),
+                            params: NodeList::empty(),
+                            visibility: None,
+                            mutability: None,
+                            modifiers: NodeList::empty(),
+                            returns: NodeList::empty(),
+                            block: m.node(
+                                60,
+                                158,
+                                Block {
+                                    body: m.list([
+                                        m.node(
+                                            82,
+                                            104,
+                                            RevertStatement {
+                                                error: m.node(
+                                                    89,
+                                                    103,
+                                                    CallExpression {
+                                                        callee: m.node(89, 101, "Unauthorized"),
+                                                        arguments: CallArguments::Positional(
+                                                            NodeList::empty(),
+                                                        ),
+                                                    },
+                                                ),
+                                            },
+                                        ),
+                                        m.stmt_expr(
+                                            125,
+                                            139,
+                                            140,
+                                            CallExpression {
+                                                callee: m.node(125, 131, "revert"),
+                                                arguments: CallArguments::Positional(m.list([
+                                                    m.node(132, 138, Primitive::String("\"nope\"")),
+                                                ])),
+                                            },
+                                        ),
+                                    ]),
+                                },
+                            ),
+                        },
+                    )]),
+                },
+            )],
+        );
+    }
+
     #[test]
     fn variable_definition_statement() {
         let m = Mock::new();
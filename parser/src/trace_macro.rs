@@ -0,0 +1,16 @@
+/// Enters a `tracing` span until the end of the enclosing block. Compiles to nothing
+/// unless the `tracing` feature is enabled.
+macro_rules! trace_span {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::debug_span!($($args)*).entered();
+    }
+}
+
+/// Emits a `tracing` event. Compiles to nothing unless the `tracing` feature is enabled.
+macro_rules! trace_event {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($args)*);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Parser;
+use crate::{Parser, TOP};
 use ast::*;
 use lexer::Token;
 
@@ -38,7 +38,23 @@ impl<'ast> Parser<'ast> {
     where
         Context: TypeNameContext<'ast>,
     {
-        Context::parse(self)
+        let mut type_name = Context::parse(self)?;
+
+        while self.lexer.token == Token::BracketOpen {
+            self.lexer.advance();
+
+            let length = self.expression(TOP);
+            let end = self.expect_end(Token::BracketClose);
+
+            let array = ArrayTypeName {
+                base: type_name,
+                length,
+            };
+
+            type_name = self.node_at(type_name.start, end, array);
+        }
+
+        Some(type_name)
     }
 
     pub fn elementary_type_name<E>(&mut self) -> Option<Node<'ast, E>>
@@ -62,6 +78,13 @@ impl<'ast> Parser<'ast> {
             }
         };
 
+        if elementary == ElementaryTypeName::Address && self.peek() == Token::KeywordPayable {
+            let start = self.start_then_advance();
+            let end = self.end_then_advance();
+
+            return self.node_at(start, end, ElementaryTypeName::AddressPayable);
+        }
+
         self.node_at_token(elementary)
     }
 
@@ -71,12 +94,7 @@ impl<'ast> Parser<'ast> {
     {
         let type_name = self.type_name::<Context>()?;
 
-        let location = match self.lexer.token {
-            Token::KeywordStorage => self.node_at_token(StorageLocation::Storage),
-            Token::KeywordMemory => self.node_at_token(StorageLocation::Memory),
-            _ => None,
-        };
-
+        let location = self.storage_location();
         let id = self.expect_str_node(Token::Identifier);
 
         self.node_at(
@@ -90,6 +108,15 @@ impl<'ast> Parser<'ast> {
         )
     }
 
+    pub fn storage_location(&mut self) -> Option<Node<'ast, StorageLocation>> {
+        match self.lexer.token {
+            Token::KeywordStorage => self.node_at_token(StorageLocation::Storage),
+            Token::KeywordMemory => self.node_at_token(StorageLocation::Memory),
+            Token::KeywordCalldata => self.node_at_token(StorageLocation::Calldata),
+            _ => None,
+        }
+    }
+
     fn user_defined_type(&mut self) -> Option<TypeNameNode<'ast>> {
         let (start, end) = self.loc();
         let identifier = self.lexer.slice();
@@ -0,0 +1,119 @@
+use std::fs;
+use std::path::PathBuf;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use optimizoor_optimizers::Pipeline;
+use optimizoor_project::Project;
+
+const FILES: usize = 500;
+
+/// Writes a project of `FILES` contracts, each importing the two that follow it.
+fn generate_corpus() -> (PathBuf, Vec<PathBuf>) {
+    let root = std::env::temp_dir().join(format!("optimizoor-bench-{}", std::process::id()));
+
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+
+    let entries = (0..FILES)
+        .map(|index| {
+            let path = root.join(format!("Token{}.sol", index));
+
+            fs::write(&path, contract(index)).unwrap();
+            path
+        })
+        .collect();
+
+    (root, entries)
+}
+
+fn contract(index: usize) -> String {
+    let imports: String = (index + 1..FILES.min(index + 3))
+        .map(|import| format!("import \"./Token{}.sol\";\n", import))
+        .collect();
+
+    format!(
+        r#"pragma solidity ^0.8.4;
+
+{imports}
+contract Token{index} {{
+    struct Account {{
+        bool frozen;
+        uint256 balance;
+        address owner;
+        uint64 nonce;
+    }}
+
+    mapping(address => Account) accounts;
+    address[] holders;
+    uint256 public totalSupply;
+
+    event Transfer(address indexed from, address indexed to, uint256 amount);
+
+    function transfer(address to, uint256 amount) public returns (bool) {{
+        require(to != address(0), "transfer to the zero address");
+        require(accounts[msg.sender].balance >= amount, "insufficient balance");
+
+        accounts[msg.sender].balance -= amount;
+        accounts[to].balance += amount;
+
+        emit Transfer(msg.sender, to, amount);
+        return true;
+    }}
+
+    function total() public view returns (uint256 sum) {{
+        for (uint256 i = 0; i < holders.length; i++) {{
+            sum += accounts[holders[i]].balance;
+        }}
+    }}
+}}
+"#
+    )
+}
+
+fn bench_parallel(c: &mut Criterion) {
+    let (root, entries) = generate_corpus();
+    let jobs = [1, 2, 4, 8];
+
+    let graph = Project::new(&root).load(&entries).unwrap();
+
+    assert!(graph.files().all(|(_, file)| file.errors.is_empty()));
+
+    let mut group = c.benchmark_group("load");
+
+    group.sample_size(10);
+
+    for jobs in jobs {
+        group.bench_with_input(BenchmarkId::from_parameter(jobs), &jobs, |b, &jobs| {
+            let project = Project::new(&root).with_jobs(jobs);
+
+            b.iter(|| project.load(&entries).unwrap().len())
+        });
+    }
+
+    group.finish();
+
+    let pipeline = Pipeline::default();
+    let mut group = c.benchmark_group("analyze");
+
+    group.sample_size(10);
+
+    for jobs in jobs {
+        group.bench_with_input(BenchmarkId::from_parameter(jobs), &jobs, |b, &jobs| {
+            let project = Project::new(&root).with_jobs(jobs);
+
+            b.iter(|| {
+                project
+                    .analyze(&entries, |program| pipeline.run(program).reports.len())
+                    .unwrap()
+                    .len()
+            })
+        });
+    }
+
+    group.finish();
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+criterion_group!(benches, bench_parallel);
+criterion_main!(benches);
@@ -0,0 +1,218 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use optimizoor_ast::{ContractDefinition, Program, SourceUnit};
+use optimizoor_parser::Error;
+
+/// Index of a file within a `SourceGraph`, also set as the file of its `Program`.
+pub use optimizoor_ast::FileId;
+
+pub struct SourceFile<'ast> {
+    /// Canonical path of the file
+    pub path: PathBuf,
+    /// Holds the source of the file, along with its path and id
+    pub program: Program<'ast>,
+    /// Syntax errors, the parts of the file that did parse are still in `program`
+    pub errors: Vec<Error>,
+    pub imports: Vec<Import>,
+    /// Imports that don't lead to a file, along with why
+    pub unresolved: Vec<(String, io::Error)>,
+    /// Parsed so that its symbols resolve, but never rewritten, like libraries and tests
+    pub excluded: bool,
+}
+
+/// An `import` directive followed to the file it refers to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Import {
+    /// The path as written in the directive
+    pub path: String,
+    pub file: FileId,
+}
+
+/// Parsed files of a project, linked by their imports.
+#[derive(Default)]
+pub struct SourceGraph<'ast> {
+    files: Vec<SourceFile<'ast>>,
+    ids: HashMap<PathBuf, FileId>,
+}
+
+impl<'ast> SourceGraph<'ast> {
+    pub(crate) fn add(&mut self, mut file: SourceFile<'ast>) -> FileId {
+        let id = FileId(self.files.len());
+
+        file.program = file.program.with_file(id);
+        self.ids.insert(file.path.clone(), id);
+        self.files.push(file);
+
+        id
+    }
+
+    pub fn file(&self, id: FileId) -> &SourceFile<'ast> {
+        &self.files[id.0]
+    }
+
+    /// Id of the file at the canonical `path`.
+    pub fn id(&self, path: &Path) -> Option<FileId> {
+        self.ids.get(path).copied()
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = (FileId, &SourceFile<'ast>)> {
+        self.files
+            .iter()
+            .enumerate()
+            .map(|(index, file)| (FileId(index), file))
+    }
+
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Files imported by `id`, directly or through other imports, including `id` itself.
+    pub fn visible_files(&self, id: FileId) -> Vec<FileId> {
+        let mut seen = HashSet::new();
+        let mut stack = vec![id];
+        let mut visible = Vec::new();
+
+        while let Some(id) = stack.pop() {
+            if seen.insert(id) {
+                visible.push(id);
+                stack.extend(self.file(id).imports.iter().rev().map(|import| import.file));
+            }
+        }
+
+        visible
+    }
+
+    /// Finds the contract called `name` as seen from the file `from`, looking
+    /// through everything the file imports.
+    pub fn contract(&self, from: FileId, name: &str) -> Option<(FileId, ContractDefinition<'ast>)> {
+        self.visible_files(from).into_iter().find_map(|id| {
+            self.file(id)
+                .program
+                .body()
+                .iter()
+                .find_map(|unit| match unit.value {
+                    SourceUnit::ContractDefinition(contract) if contract.name.value == name => {
+                        Some((id, contract))
+                    }
+                    _ => None,
+                })
+        })
+    }
+
+    /// Groups of files that import each other, directly or indirectly.
+    pub fn cycles(&self) -> Vec<Vec<FileId>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: 0,
+            indices: vec![None; self.files.len()],
+            lowlinks: vec![0; self.files.len()],
+            stack: Vec::new(),
+            on_stack: vec![false; self.files.len()],
+            components: Vec::new(),
+        };
+
+        for index in 0..self.files.len() {
+            if tarjan.indices[index].is_none() {
+                tarjan.connect(FileId(index));
+            }
+        }
+
+        tarjan
+            .components
+            .into_iter()
+            .filter(|component| match component[..] {
+                [id] => self.file(id).imports.iter().any(|import| import.file == id),
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// All files ordered so that every file comes after the files it imports. Fails
+    /// with the files of a cycle if there is one.
+    pub fn dependency_order(&self) -> Result<Vec<FileId>, Vec<FileId>> {
+        if let Some(cycle) = self.cycles().into_iter().next() {
+            return Err(cycle);
+        }
+
+        let mut order = Vec::with_capacity(self.files.len());
+        let mut visited = vec![false; self.files.len()];
+
+        for index in 0..self.files.len() {
+            self.post_order(FileId(index), &mut visited, &mut order);
+        }
+
+        Ok(order)
+    }
+
+    fn post_order(&self, id: FileId, visited: &mut [bool], order: &mut Vec<FileId>) {
+        if visited[id.0] {
+            return;
+        }
+
+        visited[id.0] = true;
+
+        for import in &self.file(id).imports {
+            self.post_order(import.file, visited, order);
+        }
+
+        order.push(id);
+    }
+}
+
+/// Tarjan's strongly connected components algorithm.
+struct Tarjan<'graph, 'ast> {
+    graph: &'graph SourceGraph<'ast>,
+    index: usize,
+    indices: Vec<Option<usize>>,
+    lowlinks: Vec<usize>,
+    stack: Vec<FileId>,
+    on_stack: Vec<bool>,
+    components: Vec<Vec<FileId>>,
+}
+
+impl Tarjan<'_, '_> {
+    fn connect(&mut self, id: FileId) {
+        self.indices[id.0] = Some(self.index);
+        self.lowlinks[id.0] = self.index;
+        self.index += 1;
+        self.stack.push(id);
+        self.on_stack[id.0] = true;
+
+        for import in &self.graph.file(id).imports {
+            let next = import.file;
+
+            match self.indices[next.0] {
+                None => {
+                    self.connect(next);
+                    self.lowlinks[id.0] = self.lowlinks[id.0].min(self.lowlinks[next.0]);
+                }
+                Some(index) if self.on_stack[next.0] => {
+                    self.lowlinks[id.0] = self.lowlinks[id.0].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.lowlinks[id.0]) == self.indices[id.0] {
+            let mut component = Vec::new();
+
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member.0] = false;
+                component.push(member);
+
+                if member == id {
+                    break;
+                }
+            }
+
+            component.sort();
+            self.components.push(component);
+        }
+    }
+}
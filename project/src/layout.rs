@@ -0,0 +1,478 @@
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{Project, Remapping};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Framework {
+    Foundry,
+    Hardhat,
+}
+
+/// Where the sources, tests and libraries of a project live.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    pub framework: Framework,
+    pub root: PathBuf,
+    /// Directories are relative to the root
+    pub sources: PathBuf,
+    pub tests: PathBuf,
+    pub libraries: Vec<PathBuf>,
+    pub remappings: Vec<Remapping>,
+}
+
+#[derive(Debug)]
+pub enum LayoutError {
+    /// Neither `foundry.toml` nor a Hardhat config was found
+    UnknownLayout(PathBuf),
+    Read { path: PathBuf, error: io::Error },
+    InvalidConfig { path: PathBuf, message: String },
+}
+
+const HARDHAT_CONFIGS: [&str; 4] = [
+    "hardhat.config.js",
+    "hardhat.config.ts",
+    "hardhat.config.cjs",
+    "hardhat.config.mjs",
+];
+
+impl Layout {
+    /// Detects a Foundry or Hardhat project at `root`.
+    pub fn detect(root: impl Into<PathBuf>) -> Result<Self, LayoutError> {
+        let root = root.into();
+
+        if root.join("foundry.toml").is_file() {
+            return Layout::foundry(root);
+        }
+
+        if HARDHAT_CONFIGS.iter().any(|config| root.join(config).is_file()) {
+            return Layout::hardhat(root);
+        }
+
+        Err(LayoutError::UnknownLayout(root))
+    }
+
+    /// Reads the default profile of `foundry.toml` and `remappings.txt`, then guesses
+    /// remappings for the libraries they leave out, like `forge` does.
+    fn foundry(root: PathBuf) -> Result<Self, LayoutError> {
+        let path = root.join("foundry.toml");
+        let config: toml::Table = read(&path)?
+            .parse()
+            .map_err(|error: toml::de::Error| invalid(&path, error.message()))?;
+
+        let profile = config
+            .get("profile")
+            .and_then(|profile| profile.get("default"))
+            .and_then(|profile| profile.as_table())
+            .cloned()
+            .unwrap_or_default();
+
+        let string = |key: &str, default: &str| match profile.get(key) {
+            None => Ok(PathBuf::from(default)),
+            Some(toml::Value::String(value)) => Ok(PathBuf::from(value)),
+            Some(_) => Err(invalid(&path, &format!("`{}` must be a string", key))),
+        };
+
+        let strings = |key: &str| match profile.get(key) {
+            None => Ok(None),
+            Some(toml::Value::Array(values)) => values
+                .iter()
+                .map(|value| value.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+                .map(Some)
+                .ok_or_else(|| invalid(&path, &format!("`{}` must only hold strings", key))),
+            Some(_) => Err(invalid(&path, &format!("`{}` must be an array", key))),
+        };
+
+        let sources = string("src", "src")?;
+        let tests = string("test", "test")?;
+        let libraries = match strings("libs")? {
+            Some(libraries) => libraries.into_iter().map(PathBuf::from).collect(),
+            None => vec![PathBuf::from("lib")],
+        };
+
+        let mut remappings = Vec::new();
+
+        for remapping in strings("remappings")?.unwrap_or_default() {
+            remappings.push(parse_remapping(&path, &remapping)?);
+        }
+
+        remappings.extend(remappings_txt(&root)?);
+
+        for remapping in library_remappings(&root, &libraries)? {
+            if !remappings.iter().any(|explicit| explicit.prefix == remapping.prefix) {
+                remappings.push(remapping);
+            }
+        }
+
+        Ok(Layout {
+            framework: Framework::Foundry,
+            root,
+            sources,
+            tests,
+            libraries,
+            remappings,
+        })
+    }
+
+    /// Hardhat configs are scripts, so the default paths are assumed.
+    fn hardhat(root: PathBuf) -> Result<Self, LayoutError> {
+        let remappings = remappings_txt(&root)?;
+
+        Ok(Layout {
+            framework: Framework::Hardhat,
+            root,
+            sources: PathBuf::from("contracts"),
+            tests: PathBuf::from("test"),
+            libraries: vec![PathBuf::from("node_modules")],
+            remappings,
+        })
+    }
+
+    /// A `Project` resolving imports the way the framework does, with the tests
+    /// and libraries excluded from rewriting.
+    pub fn project(&self) -> Project {
+        let mut project = Project::new(&self.root)
+            .with_remappings(self.remappings.iter().cloned())
+            .with_excluded(&self.tests);
+
+        for library in &self.libraries {
+            project = project.with_excluded(library);
+        }
+
+        // Packages are imported like `@openzeppelin/contracts/access/Ownable.sol`
+        if self.framework == Framework::Hardhat {
+            project = project.with_include_path("node_modules");
+        }
+
+        project
+    }
+
+    /// Every `.sol` file within the sources and tests, sorted by path.
+    pub fn entries(&self) -> Result<Vec<PathBuf>, LayoutError> {
+        let mut entries = Vec::new();
+
+        for dir in [&self.sources, &self.tests] {
+            collect_sources(&self.root.join(dir), &mut entries)?;
+        }
+
+        entries.sort();
+        entries.dedup();
+
+        Ok(entries)
+    }
+}
+
+fn collect_sources(dir: &Path, entries: &mut Vec<PathBuf>) -> Result<(), LayoutError> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    let read_dir = |dir: &Path| {
+        fs::read_dir(dir).map_err(|error| LayoutError::Read {
+            path: dir.to_path_buf(),
+            error,
+        })
+    };
+
+    for entry in read_dir(dir)? {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(error) => {
+                return Err(LayoutError::Read {
+                    path: dir.to_path_buf(),
+                    error,
+                })
+            }
+        };
+
+        if path.is_dir() {
+            collect_sources(&path, entries)?;
+        } else if path.extension().is_some_and(|extension| extension == "sol") {
+            entries.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// `<name>/=<lib>/<name>/src/` for every directory within the libraries, or
+/// `<lib>/<name>/` when it has no `src`. The first library holding a name wins.
+fn library_remappings(root: &Path, libraries: &[PathBuf]) -> Result<Vec<Remapping>, LayoutError> {
+    let mut remappings: Vec<Remapping> = Vec::new();
+
+    for library in libraries {
+        let dir = root.join(library);
+
+        if !dir.is_dir() {
+            continue;
+        }
+
+        let entries = fs::read_dir(&dir)
+            .and_then(|entries| entries.collect::<Result<Vec<_>, _>>())
+            .map_err(|error| LayoutError::Read {
+                path: dir.clone(),
+                error,
+            })?;
+        let mut names: Vec<_> = entries
+            .into_iter()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+
+        names.sort();
+
+        for name in names {
+            let prefix = format!("{}/", name);
+
+            if remappings.iter().any(|remapping| remapping.prefix == prefix) {
+                continue;
+            }
+
+            // Remapping targets are written with forward slashes
+            let mut target = format!("{}/{}/", library.to_string_lossy(), name).replace('\\', "/");
+
+            if dir.join(&name).join("src").is_dir() {
+                target.push_str("src/");
+            }
+
+            remappings.push(Remapping {
+                context: String::new(),
+                prefix,
+                target,
+            });
+        }
+    }
+
+    Ok(remappings)
+}
+
+/// Remappings listed one per line, if the file exists.
+fn remappings_txt(root: &Path) -> Result<Vec<Remapping>, LayoutError> {
+    let path = root.join("remappings.txt");
+
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    read(&path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| parse_remapping(&path, line))
+        .collect()
+}
+
+fn parse_remapping(path: &Path, remapping: &str) -> Result<Remapping, LayoutError> {
+    remapping
+        .parse()
+        .map_err(|error: crate::InvalidRemapping| invalid(path, &error.to_string()))
+}
+
+fn read(path: &Path) -> Result<String, LayoutError> {
+    fs::read_to_string(path).map_err(|error| LayoutError::Read {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn invalid(path: &Path, message: &str) -> LayoutError {
+    LayoutError::InvalidConfig {
+        path: path.to_path_buf(),
+        message: message.to_string(),
+    }
+}
+
+impl Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LayoutError::UnknownLayout(root) => write!(
+                f,
+                "{} has neither a foundry.toml nor a hardhat config",
+                root.display()
+            ),
+            LayoutError::Read { path, error } => {
+                write!(f, "can't read {}: {}", path.display(), error)
+            }
+            LayoutError::InvalidConfig { path, message } => {
+                write!(f, "invalid {}: {}", path.display(), message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::project_dir;
+
+    #[test]
+    fn detects_foundry_projects() {
+        let root = project_dir(
+            "foundry",
+            &[
+                (
+                    "foundry.toml",
+                    r#"
+                        [profile.default]
+                        src = "contracts"
+                        libs = ["lib", "dependencies"]
+                        remappings = ["@oz/=lib/oz/"]
+                    "#,
+                ),
+                ("remappings.txt", "# comment\nsolmate/=lib/solmate/src/\n"),
+                (
+                    "contracts/Token.sol",
+                    "import \"@oz/Ownable.sol\"; import \"solmate/ERC20.sol\"; contract Token {}",
+                ),
+                (
+                    "test/Token.t.sol",
+                    "import \"../contracts/Token.sol\"; contract TokenTest {}",
+                ),
+                ("lib/oz/Ownable.sol", "contract Ownable {}"),
+                ("lib/solmate/src/ERC20.sol", "contract ERC20 {}"),
+            ],
+        );
+
+        let layout = Layout::detect(&root).unwrap();
+
+        assert_eq!(layout.framework, Framework::Foundry);
+        assert_eq!(layout.sources, PathBuf::from("contracts"));
+        assert_eq!(layout.tests, PathBuf::from("test"));
+        assert_eq!(
+            layout.libraries,
+            [PathBuf::from("lib"), PathBuf::from("dependencies")]
+        );
+        assert_eq!(
+            layout.remappings,
+            [
+                "@oz/=lib/oz/".parse().unwrap(),
+                "solmate/=lib/solmate/src/".parse().unwrap(),
+                "oz/=lib/oz/".parse().unwrap(),
+            ]
+        );
+
+        let entries = layout.entries().unwrap();
+
+        assert_eq!(
+            entries,
+            [root.join("contracts/Token.sol"), root.join("test/Token.t.sol")]
+        );
+
+        let graph = layout.project().load(&entries).unwrap();
+        let mut excluded: Vec<_> = graph
+            .files()
+            .map(|(_, file)| {
+                let path = file.path.strip_prefix(root.canonicalize().unwrap()).unwrap();
+
+                (path.to_str().unwrap().to_string(), file.excluded)
+            })
+            .collect();
+
+        excluded.sort();
+
+        assert_eq!(
+            excluded,
+            [
+                ("contracts/Token.sol".to_string(), false),
+                ("lib/oz/Ownable.sol".to_string(), true),
+                ("lib/solmate/src/ERC20.sol".to_string(), true),
+                ("test/Token.t.sol".to_string(), true),
+            ]
+        );
+
+        fs::remove_dir_all(root).unwrap();
+
+        // As laid out by `forge init`, with nothing but the libraries to remap
+        let root = project_dir(
+            "forge-init",
+            &[
+                (
+                    "foundry.toml",
+                    "[profile.default]\nsrc = \"src\"\nout = \"out\"\nlibs = [\"lib\"]\n",
+                ),
+                ("src/Counter.sol", "contract Counter {}"),
+                (
+                    "test/Counter.t.sol",
+                    r#"
+                        import {Test} from "forge-std/Test.sol";
+                        import {Counter} from "../src/Counter.sol";
+                        contract CounterTest is Test {}
+                    "#,
+                ),
+                ("lib/forge-std/src/Test.sol", "contract Test {}"),
+                ("lib/forge-std/foundry.toml", "[profile.default]\n"),
+            ],
+        );
+
+        let layout = Layout::detect(&root).unwrap();
+
+        assert_eq!(
+            layout.remappings,
+            ["forge-std/=lib/forge-std/src/".parse().unwrap()]
+        );
+
+        let graph = layout.project().load(&layout.entries().unwrap()).unwrap();
+        let test = graph
+            .id(&root.join("test/Counter.t.sol").canonicalize().unwrap())
+            .unwrap();
+        let forge_std = graph.file(graph.file(test).imports[0].file);
+
+        assert_eq!(graph.len(), 3);
+        assert!(graph.files().all(|(_, file)| file.unresolved.is_empty()));
+        assert_eq!(
+            forge_std.path,
+            root.join("lib/forge-std/src/Test.sol").canonicalize().unwrap()
+        );
+        assert!(forge_std.excluded);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn detects_hardhat_projects() {
+        let root = project_dir(
+            "hardhat",
+            &[
+                ("hardhat.config.ts", "export default {};"),
+                (
+                    "contracts/Token.sol",
+                    "import \"@openzeppelin/contracts/Ownable.sol\"; contract Token {}",
+                ),
+                (
+                    "node_modules/@openzeppelin/contracts/Ownable.sol",
+                    "contract Ownable {}",
+                ),
+            ],
+        );
+
+        let layout = Layout::detect(&root).unwrap();
+
+        assert_eq!(layout.framework, Framework::Hardhat);
+
+        let graph = layout.project().load(&layout.entries().unwrap()).unwrap();
+        let token = graph.file(crate::FileId(0));
+        let ownable = graph.file(token.imports[0].file);
+
+        assert!(!token.excluded);
+        assert!(ownable.excluded);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn rejects_unknown_layouts() {
+        let root = project_dir("unknown", &[("Token.sol", "contract Token {}")]);
+
+        assert!(matches!(
+            Layout::detect(&root),
+            Err(LayoutError::UnknownLayout(_))
+        ));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
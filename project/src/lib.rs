@@ -0,0 +1,29 @@
+//! Loads multi-file projects by following their `import` directives.
+
+mod graph;
+mod layout;
+mod loader;
+mod pool;
+mod remapping;
+
+pub use self::graph::*;
+pub use self::layout::*;
+pub use self::loader::*;
+pub use self::remapping::*;
+
+/// Writes the files into a fresh temporary directory.
+#[cfg(test)]
+pub(crate) fn project_dir(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+    let root = std::env::temp_dir().join(format!("optimizoor-{}-{}", name, std::process::id()));
+
+    let _ = std::fs::remove_dir_all(&root);
+
+    for (path, source) in files {
+        let path = root.join(path);
+
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+    }
+
+    root
+}
@@ -0,0 +1,556 @@
+use std::collections::HashMap;
+use std::fmt::{self, Display};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use optimizoor_ast::{ImportDirective, Program, SourceUnit};
+use optimizoor_parser::{parse_with_errors, Error};
+
+use crate::{pool, remap, FileId, Import, Remapping, SourceFile, SourceGraph};
+
+/// Settings for loading the files of a project.
+#[derive(Clone, Debug, Default)]
+pub struct Project {
+    root: PathBuf,
+    remappings: Vec<Remapping>,
+    /// Searched after `root` for non relative imports, like `node_modules`
+    include_paths: Vec<PathBuf>,
+    /// Files within these directories are loaded, but marked as excluded from rewriting
+    excluded: Vec<PathBuf>,
+    /// Number of threads the files are parsed on
+    jobs: usize,
+}
+
+/// Findings of `Project::analyze` for one of the files of a project.
+#[derive(Debug)]
+pub struct Analysis<R> {
+    /// Canonical path of the file
+    pub path: PathBuf,
+    /// Id the file gets when the project is loaded into a `SourceGraph`
+    pub file: FileId,
+    pub errors: Vec<Error>,
+    /// Imports that don't lead to a file, along with why
+    pub unresolved: Vec<(String, io::Error)>,
+    pub excluded: bool,
+    pub findings: R,
+}
+
+/// A file found by `Project::walk`, along with what the worker thread made of it.
+struct Walked<R> {
+    path: PathBuf,
+    imports: Vec<Import>,
+    unresolved: Vec<(String, io::Error)>,
+    excluded: bool,
+    result: R,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Read {
+        path: PathBuf,
+        error: io::Error,
+    },
+}
+
+impl Project {
+    /// Non relative imports are resolved from `root`, after applying the remappings.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Project {
+            root: root.into(),
+            remappings: Vec::new(),
+            include_paths: Vec::new(),
+            excluded: Vec::new(),
+            jobs: 1,
+        }
+    }
+
+    pub fn with_remapping(mut self, remapping: Remapping) -> Self {
+        self.remappings.push(remapping);
+        self
+    }
+
+    pub fn with_remappings(mut self, remappings: impl IntoIterator<Item = Remapping>) -> Self {
+        self.remappings.extend(remappings);
+        self
+    }
+
+    /// Paths relative to the root are relative to the root of the project.
+    pub fn with_include_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.include_paths.push(self.root.join(path));
+        self
+    }
+
+    /// Paths relative to the root are relative to the root of the project.
+    pub fn with_excluded(mut self, path: impl AsRef<Path>) -> Self {
+        self.excluded.push(self.root.join(path));
+        self
+    }
+
+    /// Parses files on up to `jobs` threads, each file on its own `Arena`. The files
+    /// are numbered the same whatever the number of threads.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn remappings(&self) -> &[Remapping] {
+        &self.remappings
+    }
+
+    /// Parses the `entries` and every file they import, each file once.
+    pub fn load<'ast, P>(&self, entries: &[P]) -> Result<SourceGraph<'ast>, LoadError>
+    where
+        P: AsRef<Path>,
+    {
+        // Programs stay on the thread they're parsed on, they're sent back as owned ASTs
+        let files = self.walk(entries, |path, _| {
+            let (program, errors) = read_file(path)?;
+
+            Ok((import_paths(&program), (program.to_owned_ast(), errors)))
+        })?;
+
+        let mut graph = SourceGraph::default();
+
+        for file in files {
+            let (program, errors) = file.result;
+
+            graph.add(SourceFile {
+                program: program.to_program(),
+                path: file.path,
+                errors,
+                imports: file.imports,
+                unresolved: file.unresolved,
+                excluded: file.excluded,
+            });
+        }
+
+        Ok(graph)
+    }
+
+    /// Parses the files like `load`, running `analysis` on each of them right after
+    /// it's parsed, on the same thread. Only the findings are kept, in the order of
+    /// the ids `load` gives the files.
+    pub fn analyze<P, R, F>(
+        &self,
+        entries: &[P],
+        analysis: F,
+    ) -> Result<Vec<Analysis<R>>, LoadError>
+    where
+        P: AsRef<Path>,
+        R: Send,
+        F: for<'ast> Fn(&'ast Program<'ast>) -> R + Sync,
+    {
+        let files = self.walk(entries, |path, id| {
+            let (program, errors) = read_file(path)?;
+            let program = program.with_file(id);
+            let imports = import_paths(&program);
+            let findings = analysis(&program);
+
+            Ok((imports, (errors, findings)))
+        })?;
+
+        let analyses = files
+            .into_iter()
+            .enumerate()
+            .map(|(index, file)| {
+                let (errors, findings) = file.result;
+
+                Analysis {
+                    path: file.path,
+                    file: FileId(index),
+                    excluded: file.excluded,
+                    errors,
+                    unresolved: file.unresolved,
+                    findings,
+                }
+            })
+            .collect();
+
+        Ok(analyses)
+    }
+
+    /// Walks the `entries` and every file they import, each file once, handing them to
+    /// `read` on the worker threads. `read` gives back the imports of the file, which
+    /// are followed one level at a time so that the files are numbered in the order a
+    /// breadth first walk finds them, whatever the number of threads. Imports that
+    /// don't resolve are recorded with the file importing them, the walk goes on.
+    fn walk<P, R, F>(&self, entries: &[P], read: F) -> Result<Vec<Walked<R>>, LoadError>
+    where
+        P: AsRef<Path>,
+        R: Send,
+        F: Fn(&Path, FileId) -> Result<(Vec<String>, R), LoadError> + Sync,
+    {
+        let root = canonical(&self.root)?;
+        let excluded: Vec<_> = self
+            .excluded
+            .iter()
+            .filter_map(|path| fs::canonicalize(path).ok())
+            .collect();
+
+        let mut files = Vec::new();
+        let mut ids = HashMap::new();
+        let mut level = Vec::new();
+
+        let mut discover = |path: PathBuf, level: &mut Vec<PathBuf>| {
+            let next = FileId(ids.len());
+
+            *ids.entry(path.clone()).or_insert_with(|| {
+                level.push(path);
+                next
+            })
+        };
+
+        for entry in entries {
+            discover(canonical(entry.as_ref())?, &mut level);
+        }
+
+        while !level.is_empty() {
+            let first = files.len();
+            let results = pool::map(&level, self.jobs, |index, path| {
+                read(path, FileId(first + index))
+            });
+            let mut next = Vec::new();
+
+            for (path, result) in level.into_iter().zip(results) {
+                let (paths, result) = result?;
+                let mut imports = Vec::with_capacity(paths.len());
+                let mut unresolved = Vec::new();
+
+                for import in paths {
+                    let resolved = self.resolve(&root, &path, &import);
+
+                    match fs::canonicalize(&resolved) {
+                        Ok(resolved) => imports.push(Import {
+                            path: import,
+                            file: discover(resolved, &mut next),
+                        }),
+                        Err(error) => unresolved.push((import, error)),
+                    }
+                }
+
+                files.push(Walked {
+                    excluded: is_test(&path) || excluded.iter().any(|dir| path.starts_with(dir)),
+                    path,
+                    imports,
+                    unresolved,
+                    result,
+                });
+            }
+
+            level = next;
+        }
+
+        Ok(files)
+    }
+
+    /// Path that `import` within the file at `importer` refers to.
+    fn resolve(&self, root: &Path, importer: &Path, import: &str) -> PathBuf {
+        if import.starts_with("./") || import.starts_with("../") {
+            return importer.parent().unwrap_or(root).join(import);
+        }
+
+        // Remapping contexts are written with forward slashes
+        let relative = importer
+            .strip_prefix(root)
+            .unwrap_or(importer)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        let remapped = remap(&self.remappings, &relative, import);
+        let import = remapped.as_deref().unwrap_or(import);
+
+        std::iter::once(root)
+            .chain(self.include_paths.iter().map(PathBuf::as_path))
+            .map(|dir| dir.join(import))
+            .find(|path| path.exists())
+            .unwrap_or_else(|| root.join(import))
+    }
+}
+
+/// Foundry tests are named like `Token.t.sol`.
+fn is_test(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(".t.sol"))
+}
+
+fn canonical(path: &Path) -> Result<PathBuf, LoadError> {
+    fs::canonicalize(path).map_err(|error| LoadError::Read {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+fn read_file<'ast>(path: &Path) -> Result<(Program<'ast>, Vec<Error>), LoadError> {
+    let source = fs::read_to_string(path).map_err(|error| LoadError::Read {
+        path: path.to_path_buf(),
+        error,
+    })?;
+
+    let (program, errors) = parse_with_errors(&source);
+
+    Ok((program.with_path(path), errors))
+}
+
+/// Paths of the `import` directives of the program, without the quotes.
+fn import_paths(program: &Program) -> Vec<String> {
+    program
+        .body()
+        .iter()
+        .filter_map(|unit| match unit.value {
+            SourceUnit::ImportDirective(import) => Some(match import {
+                ImportDirective::Global { source, .. }
+                | ImportDirective::From { source, .. }
+                | ImportDirective::ManyFrom { source, .. } => source.value,
+            }),
+            _ => None,
+        })
+        .map(|source| source[1..source.len() - 1].to_string())
+        .collect()
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Read { path, error } => {
+                write!(f, "can't read {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::project_dir;
+
+    #[test]
+    fn loads_imported_files_once() {
+        let root = project_dir(
+            "imports",
+            &[
+                (
+                    "src/Token.sol",
+                    r#"
+                        import "./lib/Math.sol";
+                        import {Ownable} from "@oz/access/Ownable.sol";
+                        contract Token is Ownable {}
+                    "#,
+                ),
+                (
+                    "src/lib/Math.sol",
+                    r#"
+                        import "../Token.sol";
+                        import "@oz/access/Ownable.sol";
+                        contract Math {}
+                    "#,
+                ),
+                (
+                    "lib/openzeppelin/access/Ownable.sol",
+                    "contract Ownable { address owner; }",
+                ),
+            ],
+        );
+
+        let project = Project::new(&root).with_remapping("@oz/=lib/openzeppelin/".parse().unwrap());
+        let graph = project.load(&[root.join("src/Token.sol")]).unwrap();
+
+        assert_eq!(graph.len(), 3);
+
+        let token = graph
+            .id(&root.join("src/Token.sol").canonicalize().unwrap())
+            .unwrap();
+        let math = graph
+            .id(&root.join("src/lib/Math.sol").canonicalize().unwrap())
+            .unwrap();
+        let ownable = graph
+            .id(&root
+                .join("lib/openzeppelin/access/Ownable.sol")
+                .canonicalize()
+                .unwrap())
+            .unwrap();
+
+        assert_eq!(
+            graph.file(token).imports,
+            [
+                Import {
+                    path: "./lib/Math.sol".to_string(),
+                    file: math,
+                },
+                Import {
+                    path: "@oz/access/Ownable.sol".to_string(),
+                    file: ownable,
+                },
+            ]
+        );
+
+        assert_eq!(graph.cycles(), [vec![token, math]]);
+        assert_eq!(graph.dependency_order(), Err(vec![token, math]));
+
+        let (file, contract) = graph.contract(token, "Ownable").unwrap();
+
+        assert_eq!(file, ownable);
+        assert_eq!(graph.file(ownable).program.file(), ownable);
+        assert_eq!(
+            graph.file(ownable).program.source(),
+            "contract Ownable { address owner; }"
+        );
+        assert_eq!(contract.name.value, "Ownable");
+        assert!(graph.contract(ownable, "Token").is_none());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn orders_files_by_dependencies() {
+        let root = project_dir(
+            "order",
+            &[
+                ("A.sol", "import \"B.sol\"; import \"C.sol\"; contract A {}"),
+                ("B.sol", "import \"C.sol\"; contract B {}"),
+                ("C.sol", "contract C {}"),
+            ],
+        );
+
+        let graph = Project::new(&root).load(&[root.join("A.sol")]).unwrap();
+        let names: Vec<_> = graph
+            .dependency_order()
+            .unwrap()
+            .into_iter()
+            .map(|id| {
+                graph
+                    .file(id)
+                    .path
+                    .file_name()
+                    .unwrap()
+                    .to_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+
+        assert!(graph.cycles().is_empty());
+        assert_eq!(names, ["C.sol", "B.sol", "A.sol"]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn loads_and_analyzes_in_parallel() {
+        // `File{n}.sol` imports the files numbered twice and twice plus one
+        let files: Vec<_> = (0..24)
+            .map(|index| {
+                let imports: String = [2 * index, 2 * index + 1]
+                    .iter()
+                    .filter(|&&import| import > index && import < 24)
+                    .map(|import| format!("import \"./File{}.sol\";\n", import))
+                    .collect();
+
+                (
+                    format!("File{}.sol", index),
+                    format!("{}contract C{} {{ uint x; }}", imports, index),
+                )
+            })
+            .collect();
+        let files: Vec<_> = files
+            .iter()
+            .map(|(path, source)| (path.as_str(), source.as_str()))
+            .collect();
+        let root = project_dir("parallel", &files);
+        let entries = [root.join("File0.sol"), root.join("File5.sol")];
+
+        let sequential = Project::new(&root).load(&entries).unwrap();
+        let parallel = Project::new(&root).with_jobs(4).load(&entries).unwrap();
+
+        assert_eq!(parallel.len(), 24);
+
+        for ((id, expected), (_, file)) in sequential.files().zip(parallel.files()) {
+            assert_eq!(file.path, expected.path);
+            assert_eq!(file.imports, expected.imports);
+            assert_eq!(file.program.file(), id);
+            assert_eq!(file.program.body(), expected.program.body());
+        }
+
+        let analyses: Vec<_> = [1, 4]
+            .iter()
+            .map(|&jobs| {
+                let analyses = Project::new(&root)
+                    .with_jobs(jobs)
+                    .analyze(&entries, |program| (program.file(), program.index().len()))
+                    .unwrap();
+
+                analyses
+                    .into_iter()
+                    .map(|analysis| (analysis.path, analysis.file, analysis.findings))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        assert_eq!(analyses[0], analyses[1]);
+
+        for ((path, id, (file, nodes)), (expected_id, expected)) in
+            analyses[0].iter().zip(sequential.files())
+        {
+            assert_eq!(*path, expected.path);
+            assert_eq!((*id, *file), (expected_id, expected_id));
+            assert_eq!(*nodes, expected.program.index().len());
+        }
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn records_unresolved_imports() {
+        let root = project_dir(
+            "missing",
+            &[
+                (
+                    "A.sol",
+                    "import \"./Missing.sol\"; import \"./B.sol\"; contract A {}",
+                ),
+                ("B.sol", "import \"@oz/Ownable.sol\"; contract B {}"),
+            ],
+        );
+        let entries = [root.join("A.sol")];
+
+        let graph = Project::new(&root).load(&entries).unwrap();
+        let unresolved: Vec<_> = graph
+            .files()
+            .map(|(_, file)| {
+                let imports: Vec<_> = file.imports.iter().map(|import| &import.path[..]).collect();
+                let unresolved: Vec<_> = file
+                    .unresolved
+                    .iter()
+                    .map(|(import, error)| (&import[..], error.kind()))
+                    .collect();
+
+                (imports, unresolved)
+            })
+            .collect();
+
+        assert_eq!(
+            unresolved,
+            [
+                (vec!["./B.sol"], vec![("./Missing.sol", io::ErrorKind::NotFound)]),
+                (vec![], vec![("@oz/Ownable.sol", io::ErrorKind::NotFound)]),
+            ]
+        );
+
+        let analyses = Project::new(&root).analyze(&entries, |_| ()).unwrap();
+        let unresolved: Vec<_> = analyses
+            .iter()
+            .map(|analysis| analysis.unresolved.len())
+            .collect();
+
+        assert_eq!(unresolved, [1, 1]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,76 @@
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Maps the `items` with `f` on up to `jobs` threads, each of them taking the next
+/// item as soon as it's done with the previous one. The results are in the order of
+/// the items, whatever the number of threads.
+pub(crate) fn map<T, R, F>(items: &[T], jobs: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(usize, &T) -> R + Sync,
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+
+    if jobs == 1 {
+        return items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| f(index, item))
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+
+                        match items.get(index) {
+                            Some(item) => results.push((index, f(index, item))),
+                            None => return results,
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn keeps_the_order_of_the_items() {
+        let items: Vec<u64> = (0..100).collect();
+        let squares: Vec<u64> = items.iter().map(|item| item * item).collect();
+
+        for jobs in [0, 1, 4, 200] {
+            assert_eq!(map(&items, jobs, |_, item| item * item), squares);
+        }
+
+        assert_eq!(
+            map(&items, 4, |index, _| index),
+            (0..100).collect::<Vec<_>>()
+        );
+        assert!(map(&[] as &[u64], 4, |_, item| *item).is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// An import remapping in the `solc` format, `context:prefix=target`, such as
+/// `@openzeppelin/=lib/openzeppelin-contracts/`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Remapping {
+    /// Only files within this directory use the remapping, all files do if empty.
+    pub context: String,
+    pub prefix: String,
+    pub target: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidRemapping(pub String);
+
+impl Remapping {
+    /// Rewrites `import` if it starts with the prefix, `importer` is the path of
+    /// the importing file relative to the project root.
+    pub fn apply(&self, importer: &str, import: &str) -> Option<String> {
+        if !importer.starts_with(&self.context) {
+            return None;
+        }
+
+        let rest = import.strip_prefix(&self.prefix)?;
+
+        Some(format!("{}{}", self.target, rest))
+    }
+}
+
+/// Applies the remapping with the longest matching context and prefix, like `solc` does.
+pub fn remap(remappings: &[Remapping], importer: &str, import: &str) -> Option<String> {
+    remappings
+        .iter()
+        .filter(|remapping| {
+            importer.starts_with(&remapping.context) && import.starts_with(&remapping.prefix)
+        })
+        .max_by_key(|remapping| (remapping.context.len(), remapping.prefix.len()))
+        .and_then(|remapping| remapping.apply(importer, import))
+}
+
+impl FromStr for Remapping {
+    type Err = InvalidRemapping;
+
+    fn from_str(remapping: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidRemapping(remapping.to_string());
+        let (key, target) = remapping.trim().split_once('=').ok_or_else(invalid)?;
+
+        let (context, prefix) = match key.split_once(':') {
+            Some((context, prefix)) => (context, prefix),
+            None => ("", key),
+        };
+
+        if prefix.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Remapping {
+            context: context.to_string(),
+            prefix: prefix.to_string(),
+            target: target.to_string(),
+        })
+    }
+}
+
+impl Display for Remapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.context.is_empty() {
+            write!(f, "{}:", self.context)?;
+        }
+
+        write!(f, "{}={}", self.prefix, self.target)
+    }
+}
+
+impl Display for InvalidRemapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid remapping `{}`, expected `prefix=target`", self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_and_applies_remappings() {
+        let remappings: Vec<Remapping> = [
+            "@openzeppelin/=lib/openzeppelin-contracts/",
+            "@openzeppelin/contracts/=lib/oz/contracts/",
+            "src/legacy:@openzeppelin/=lib/oz-legacy/",
+        ]
+        .iter()
+        .map(|remapping| remapping.parse().unwrap())
+        .collect();
+
+        assert_eq!(remappings[2].context, "src/legacy");
+        assert_eq!(
+            remappings[2].to_string(),
+            "src/legacy:@openzeppelin/=lib/oz-legacy/"
+        );
+
+        assert_eq!(
+            remap(&remappings, "src/Token.sol", "@openzeppelin/utils/Strings.sol"),
+            Some("lib/openzeppelin-contracts/utils/Strings.sol".to_string())
+        );
+        assert_eq!(
+            remap(&remappings, "src/Token.sol", "@openzeppelin/contracts/Ownable.sol"),
+            Some("lib/oz/contracts/Ownable.sol".to_string())
+        );
+        assert_eq!(
+            remap(&remappings, "src/legacy/Old.sol", "@openzeppelin/Ownable.sol"),
+            Some("lib/oz-legacy/Ownable.sol".to_string())
+        );
+        assert_eq!(remap(&remappings, "src/Token.sol", "./Other.sol"), None);
+
+        assert!("no-target".parse::<Remapping>().is_err());
+        assert!("=target".parse::<Remapping>().is_err());
+    }
+}